@@ -0,0 +1,1241 @@
+use crate::{
+    prelude::*, AccountFilter, BasicOrderInfo, BookDepth, BracketOrderStatus, BuilderInfo,
+    CancelOutcome, CandlesSnapshotResponse, ClientBracketOrder, ClientCancelRequest,
+    ClientCancelRequestCloid, ClientModifyRequest, ClientModifyRequestCloid, ClientOrderRequest,
+    ClientTwapRequest, CloseOutcome, CreateSubAccountResponseStatus, CreateVaultResponseStatus,
+    DelegationResponse, DelegatorRewardResponse, DelegatorSummaryResponse, Error, ExchangeClient,
+    ExchangeResponseStatus, FillEstimate, FundingHistoryResponse, HyperliquidSigner, InfoClient,
+    L2SnapshotResponse, MarketCloseParams, MarketOrderParams, Message, Meta, OpenOrdersResponse,
+    OrderGrouping, OrderInfo, OrderStatusResponse, PaperExchangeClient, RecentTradesResponse,
+    ReferralResponse, ScaleOrder, ScaleOrderStatus, SignedAction, SpotMeta, SpotMetaAndAssetCtxs,
+    SubAccountResponse, Subscription, TwapResponseStatus, UserFeesResponse, UserFillsResponse,
+    UserFundingResponse, UserRateLimitResponse, UserStateResponse, UserTokenBalanceResponse,
+    UserVaultEquity, VaultDetailsResponse,
+};
+use ethers::types::H160;
+use std::{collections::HashMap, fmt::Debug};
+use tokio::sync::mpsc::UnboundedSender;
+use uuid::Uuid;
+
+/// Actions of an [`ExchangeClient`], for code that should also run against
+/// [`MockClient`](crate::MockClient), [`PaperExchangeClient`] or other implementations.
+///
+/// Every method behaves as the [`ExchangeClient`] method of the same name.
+/// [`PaperExchangeClient`] simulates placing, modifying and cancelling orders and fails the
+/// other methods, such as transfers and vault actions.
+#[async_trait::async_trait]
+pub trait ExchangeApi: Send + Sync + Debug {
+    async fn submit_signed(&self, signed: &SignedAction) -> Result<ExchangeResponseStatus>;
+    async fn usdc_transfer(
+        &self,
+        amount: &str,
+        destination: &str,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus>;
+    async fn class_transfer(
+        &self,
+        usdc: f64,
+        to_perp: bool,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus>;
+    async fn vault_transfer(
+        &self,
+        is_deposit: bool,
+        usd: u64,
+        vault_address: Option<H160>,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus>;
+    async fn create_vault(
+        &self,
+        name: String,
+        description: String,
+        initial_usd: u64,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<CreateVaultResponseStatus>;
+    async fn vault_modify(
+        &self,
+        vault_address: H160,
+        allow_deposits: Option<bool>,
+        always_close_on_withdraw: Option<bool>,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus>;
+    async fn vault_distribute(
+        &self,
+        vault_address: H160,
+        usd: u64,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus>;
+    async fn market_open(&self, params: MarketOrderParams<'_>) -> Result<ExchangeResponseStatus>;
+    async fn market_open_with_builder(
+        &self,
+        params: MarketOrderParams<'_>,
+        builder: BuilderInfo,
+    ) -> Result<ExchangeResponseStatus>;
+    async fn market_close(&self, params: MarketCloseParams<'_>) -> Result<ExchangeResponseStatus>;
+    async fn market_open_with_book(
+        &self,
+        params: MarketOrderParams<'_>,
+        book: Option<&BookDepth>,
+    ) -> Result<(ExchangeResponseStatus, FillEstimate)>;
+    async fn market_close_with_book(
+        &self,
+        params: MarketCloseParams<'_>,
+        book: Option<&BookDepth>,
+    ) -> Result<(ExchangeResponseStatus, FillEstimate)>;
+    async fn order(
+        &self,
+        order: ClientOrderRequest,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus>;
+    async fn order_with_builder(
+        &self,
+        order: ClientOrderRequest,
+        wallet: Option<&dyn HyperliquidSigner>,
+        builder: BuilderInfo,
+    ) -> Result<ExchangeResponseStatus>;
+    async fn bulk_order(
+        &self,
+        orders: Vec<ClientOrderRequest>,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus>;
+    async fn bracket_order(
+        &self,
+        bracket: ClientBracketOrder,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<BracketOrderStatus>;
+    async fn scale_order(
+        &self,
+        scale: ScaleOrder,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ScaleOrderStatus>;
+    async fn bulk_order_with_grouping(
+        &self,
+        orders: Vec<ClientOrderRequest>,
+        grouping: OrderGrouping,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus>;
+    async fn bulk_order_with_builder(
+        &self,
+        orders: Vec<ClientOrderRequest>,
+        wallet: Option<&dyn HyperliquidSigner>,
+        builder: BuilderInfo,
+    ) -> Result<ExchangeResponseStatus>;
+    async fn cancel(
+        &self,
+        cancel: ClientCancelRequest,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus>;
+    async fn bulk_cancel(
+        &self,
+        cancels: Vec<ClientCancelRequest>,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus>;
+    async fn modify(
+        &self,
+        modify: ClientModifyRequest,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus>;
+    async fn bulk_modify(
+        &self,
+        modifies: Vec<ClientModifyRequest>,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus>;
+    async fn modify_by_cloid(
+        &self,
+        modify: ClientModifyRequestCloid,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus>;
+    async fn bulk_modify_by_cloid(
+        &self,
+        modifies: Vec<ClientModifyRequestCloid>,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus>;
+    async fn cancel_by_cloid(
+        &self,
+        cancel: ClientCancelRequestCloid,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus>;
+    async fn bulk_cancel_by_cloid(
+        &self,
+        cancels: Vec<ClientCancelRequestCloid>,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus>;
+    async fn update_leverage(
+        &self,
+        leverage: u32,
+        coin: &str,
+        is_cross: bool,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus>;
+    async fn update_isolated_margin(
+        &self,
+        amount: f64,
+        coin: &str,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus>;
+    async fn approve_agent(
+        &self,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<(String, ExchangeResponseStatus)>;
+    async fn withdraw_from_bridge(
+        &self,
+        amount: &str,
+        destination: &str,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus>;
+    async fn spot_transfer(
+        &self,
+        amount: &str,
+        destination: &str,
+        token: &str,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus>;
+    async fn c_deposit(
+        &self,
+        wei: u64,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus>;
+    async fn c_withdraw(
+        &self,
+        wei: u64,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus>;
+    async fn token_delegate(
+        &self,
+        validator: H160,
+        wei: u64,
+        is_undelegate: bool,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus>;
+    async fn set_referrer(
+        &self,
+        code: String,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus>;
+    async fn approve_builder_fee(
+        &self,
+        builder: String,
+        max_fee_rate: String,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus>;
+    async fn schedule_cancel(
+        &self,
+        time: Option<u64>,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus>;
+    async fn create_sub_account(
+        &self,
+        name: String,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<CreateSubAccountResponseStatus>;
+    async fn sub_account_transfer(
+        &self,
+        sub_account_user: H160,
+        is_deposit: bool,
+        usd: u64,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus>;
+    async fn sub_account_spot_transfer(
+        &self,
+        sub_account_user: H160,
+        is_deposit: bool,
+        token: &str,
+        amount: &str,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus>;
+    async fn twap_order(
+        &self,
+        twap: ClientTwapRequest,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<TwapResponseStatus>;
+    async fn twap_cancel(
+        &self,
+        asset: &str,
+        twap_id: u64,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<TwapResponseStatus>;
+    async fn cancel_all(&self, filter: &AccountFilter) -> Result<Vec<CancelOutcome>>;
+    async fn close_all_positions(
+        &self,
+        filter: &AccountFilter,
+        slippage: Option<f64>,
+    ) -> Result<Vec<CloseOutcome>>;
+}
+
+/// Queries and subscriptions of an [`InfoClient`], for code that should also run against
+/// [`MockClient`](crate::MockClient) or other implementations.
+///
+/// Every method behaves as the [`InfoClient`] method of the same name.
+#[async_trait::async_trait]
+pub trait InfoApi: Send + Sync + Debug {
+    async fn subscribe(
+        &mut self,
+        subscription: Subscription,
+        sender_channel: UnboundedSender<Message>,
+    ) -> Result<u32>;
+    async fn unsubscribe(&mut self, subscription_id: u32) -> Result<()>;
+    async fn open_orders(&self, address: H160) -> Result<Vec<OpenOrdersResponse>>;
+    async fn frontend_open_orders(&self, address: H160) -> Result<Vec<BasicOrderInfo>>;
+    async fn user_state(&self, address: H160) -> Result<UserStateResponse>;
+    async fn user_states(&self, addresses: Vec<H160>) -> Result<Vec<UserStateResponse>>;
+    async fn user_token_balances(&self, address: H160) -> Result<UserTokenBalanceResponse>;
+    async fn user_fees(&self, address: H160) -> Result<UserFeesResponse>;
+    async fn user_rate_limit(&self, address: H160) -> Result<UserRateLimitResponse>;
+    async fn meta(&self) -> Result<Meta>;
+    async fn spot_meta(&self) -> Result<SpotMeta>;
+    async fn spot_meta_and_asset_contexts(&self) -> Result<Vec<SpotMetaAndAssetCtxs>>;
+    async fn all_mids(&self) -> Result<HashMap<String, String>>;
+    async fn user_fills(&self, address: H160) -> Result<Vec<UserFillsResponse>>;
+    async fn funding_history(
+        &self,
+        coin: String,
+        start_time: u64,
+        end_time: Option<u64>,
+    ) -> Result<Vec<FundingHistoryResponse>>;
+    async fn user_funding_history(
+        &self,
+        user: H160,
+        start_time: u64,
+        end_time: Option<u64>,
+    ) -> Result<Vec<UserFundingResponse>>;
+    async fn recent_trades(&self, coin: String) -> Result<Vec<RecentTradesResponse>>;
+    async fn l2_snapshot(&self, coin: String) -> Result<L2SnapshotResponse>;
+    async fn candles_snapshot(
+        &self,
+        coin: String,
+        interval: String,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<Vec<CandlesSnapshotResponse>>;
+    async fn query_order_by_oid(&self, address: H160, oid: u64) -> Result<OrderStatusResponse>;
+    async fn query_order_by_cloid(&self, address: H160, cloid: Uuid)
+        -> Result<OrderStatusResponse>;
+    async fn query_referral_state(&self, address: H160) -> Result<ReferralResponse>;
+    async fn historical_orders(&self, address: H160) -> Result<Vec<OrderInfo>>;
+    async fn sub_accounts(&self, address: H160) -> Result<Vec<SubAccountResponse>>;
+    async fn vault_details(
+        &self,
+        vault_address: H160,
+        user: Option<H160>,
+    ) -> Result<VaultDetailsResponse>;
+    async fn user_vault_equities(&self, address: H160) -> Result<Vec<UserVaultEquity>>;
+    async fn delegations(&self, address: H160) -> Result<Vec<DelegationResponse>>;
+    async fn delegator_summary(&self, address: H160) -> Result<DelegatorSummaryResponse>;
+    async fn delegator_rewards(&self, address: H160) -> Result<Vec<DelegatorRewardResponse>>;
+}
+
+#[async_trait::async_trait]
+impl ExchangeApi for ExchangeClient {
+    async fn submit_signed(&self, signed: &SignedAction) -> Result<ExchangeResponseStatus> {
+        ExchangeClient::submit_signed(self, signed).await
+    }
+
+    async fn usdc_transfer(
+        &self,
+        amount: &str,
+        destination: &str,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        ExchangeClient::usdc_transfer(self, amount, destination, wallet).await
+    }
+
+    async fn class_transfer(
+        &self,
+        usdc: f64,
+        to_perp: bool,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        ExchangeClient::class_transfer(self, usdc, to_perp, wallet).await
+    }
+
+    async fn vault_transfer(
+        &self,
+        is_deposit: bool,
+        usd: u64,
+        vault_address: Option<H160>,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        ExchangeClient::vault_transfer(self, is_deposit, usd, vault_address, wallet).await
+    }
+
+    async fn create_vault(
+        &self,
+        name: String,
+        description: String,
+        initial_usd: u64,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<CreateVaultResponseStatus> {
+        ExchangeClient::create_vault(self, name, description, initial_usd, wallet).await
+    }
+
+    async fn vault_modify(
+        &self,
+        vault_address: H160,
+        allow_deposits: Option<bool>,
+        always_close_on_withdraw: Option<bool>,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        ExchangeClient::vault_modify(
+            self,
+            vault_address,
+            allow_deposits,
+            always_close_on_withdraw,
+            wallet,
+        )
+        .await
+    }
+
+    async fn vault_distribute(
+        &self,
+        vault_address: H160,
+        usd: u64,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        ExchangeClient::vault_distribute(self, vault_address, usd, wallet).await
+    }
+
+    async fn market_open(&self, params: MarketOrderParams<'_>) -> Result<ExchangeResponseStatus> {
+        ExchangeClient::market_open(self, params).await
+    }
+
+    async fn market_open_with_builder(
+        &self,
+        params: MarketOrderParams<'_>,
+        builder: BuilderInfo,
+    ) -> Result<ExchangeResponseStatus> {
+        ExchangeClient::market_open_with_builder(self, params, builder).await
+    }
+
+    async fn market_close(&self, params: MarketCloseParams<'_>) -> Result<ExchangeResponseStatus> {
+        ExchangeClient::market_close(self, params).await
+    }
+
+    async fn market_open_with_book(
+        &self,
+        params: MarketOrderParams<'_>,
+        book: Option<&BookDepth>,
+    ) -> Result<(ExchangeResponseStatus, FillEstimate)> {
+        ExchangeClient::market_open_with_book(self, params, book).await
+    }
+
+    async fn market_close_with_book(
+        &self,
+        params: MarketCloseParams<'_>,
+        book: Option<&BookDepth>,
+    ) -> Result<(ExchangeResponseStatus, FillEstimate)> {
+        ExchangeClient::market_close_with_book(self, params, book).await
+    }
+
+    async fn order(
+        &self,
+        order: ClientOrderRequest,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        ExchangeClient::order(self, order, wallet).await
+    }
+
+    async fn order_with_builder(
+        &self,
+        order: ClientOrderRequest,
+        wallet: Option<&dyn HyperliquidSigner>,
+        builder: BuilderInfo,
+    ) -> Result<ExchangeResponseStatus> {
+        ExchangeClient::order_with_builder(self, order, wallet, builder).await
+    }
+
+    async fn bulk_order(
+        &self,
+        orders: Vec<ClientOrderRequest>,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        ExchangeClient::bulk_order(self, orders, wallet).await
+    }
+
+    async fn bracket_order(
+        &self,
+        bracket: ClientBracketOrder,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<BracketOrderStatus> {
+        ExchangeClient::bracket_order(self, bracket, wallet).await
+    }
+
+    async fn scale_order(
+        &self,
+        scale: ScaleOrder,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ScaleOrderStatus> {
+        ExchangeClient::scale_order(self, scale, wallet).await
+    }
+
+    async fn bulk_order_with_grouping(
+        &self,
+        orders: Vec<ClientOrderRequest>,
+        grouping: OrderGrouping,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        ExchangeClient::bulk_order_with_grouping(self, orders, grouping, wallet).await
+    }
+
+    async fn bulk_order_with_builder(
+        &self,
+        orders: Vec<ClientOrderRequest>,
+        wallet: Option<&dyn HyperliquidSigner>,
+        builder: BuilderInfo,
+    ) -> Result<ExchangeResponseStatus> {
+        ExchangeClient::bulk_order_with_builder(self, orders, wallet, builder).await
+    }
+
+    async fn cancel(
+        &self,
+        cancel: ClientCancelRequest,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        ExchangeClient::cancel(self, cancel, wallet).await
+    }
+
+    async fn bulk_cancel(
+        &self,
+        cancels: Vec<ClientCancelRequest>,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        ExchangeClient::bulk_cancel(self, cancels, wallet).await
+    }
+
+    async fn modify(
+        &self,
+        modify: ClientModifyRequest,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        ExchangeClient::modify(self, modify, wallet).await
+    }
+
+    async fn bulk_modify(
+        &self,
+        modifies: Vec<ClientModifyRequest>,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        ExchangeClient::bulk_modify(self, modifies, wallet).await
+    }
+
+    async fn modify_by_cloid(
+        &self,
+        modify: ClientModifyRequestCloid,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        ExchangeClient::modify_by_cloid(self, modify, wallet).await
+    }
+
+    async fn bulk_modify_by_cloid(
+        &self,
+        modifies: Vec<ClientModifyRequestCloid>,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        ExchangeClient::bulk_modify_by_cloid(self, modifies, wallet).await
+    }
+
+    async fn cancel_by_cloid(
+        &self,
+        cancel: ClientCancelRequestCloid,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        ExchangeClient::cancel_by_cloid(self, cancel, wallet).await
+    }
+
+    async fn bulk_cancel_by_cloid(
+        &self,
+        cancels: Vec<ClientCancelRequestCloid>,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        ExchangeClient::bulk_cancel_by_cloid(self, cancels, wallet).await
+    }
+
+    async fn update_leverage(
+        &self,
+        leverage: u32,
+        coin: &str,
+        is_cross: bool,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        ExchangeClient::update_leverage(self, leverage, coin, is_cross, wallet).await
+    }
+
+    async fn update_isolated_margin(
+        &self,
+        amount: f64,
+        coin: &str,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        ExchangeClient::update_isolated_margin(self, amount, coin, wallet).await
+    }
+
+    async fn approve_agent(
+        &self,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<(String, ExchangeResponseStatus)> {
+        ExchangeClient::approve_agent(self, wallet).await
+    }
+
+    async fn withdraw_from_bridge(
+        &self,
+        amount: &str,
+        destination: &str,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        ExchangeClient::withdraw_from_bridge(self, amount, destination, wallet).await
+    }
+
+    async fn spot_transfer(
+        &self,
+        amount: &str,
+        destination: &str,
+        token: &str,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        ExchangeClient::spot_transfer(self, amount, destination, token, wallet).await
+    }
+
+    async fn c_deposit(
+        &self,
+        wei: u64,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        ExchangeClient::c_deposit(self, wei, wallet).await
+    }
+
+    async fn c_withdraw(
+        &self,
+        wei: u64,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        ExchangeClient::c_withdraw(self, wei, wallet).await
+    }
+
+    async fn token_delegate(
+        &self,
+        validator: H160,
+        wei: u64,
+        is_undelegate: bool,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        ExchangeClient::token_delegate(self, validator, wei, is_undelegate, wallet).await
+    }
+
+    async fn set_referrer(
+        &self,
+        code: String,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        ExchangeClient::set_referrer(self, code, wallet).await
+    }
+
+    async fn approve_builder_fee(
+        &self,
+        builder: String,
+        max_fee_rate: String,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        ExchangeClient::approve_builder_fee(self, builder, max_fee_rate, wallet).await
+    }
+
+    async fn schedule_cancel(
+        &self,
+        time: Option<u64>,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        ExchangeClient::schedule_cancel(self, time, wallet).await
+    }
+
+    async fn create_sub_account(
+        &self,
+        name: String,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<CreateSubAccountResponseStatus> {
+        ExchangeClient::create_sub_account(self, name, wallet).await
+    }
+
+    async fn sub_account_transfer(
+        &self,
+        sub_account_user: H160,
+        is_deposit: bool,
+        usd: u64,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        ExchangeClient::sub_account_transfer(self, sub_account_user, is_deposit, usd, wallet).await
+    }
+
+    async fn sub_account_spot_transfer(
+        &self,
+        sub_account_user: H160,
+        is_deposit: bool,
+        token: &str,
+        amount: &str,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        ExchangeClient::sub_account_spot_transfer(
+            self,
+            sub_account_user,
+            is_deposit,
+            token,
+            amount,
+            wallet,
+        )
+        .await
+    }
+
+    async fn twap_order(
+        &self,
+        twap: ClientTwapRequest,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<TwapResponseStatus> {
+        ExchangeClient::twap_order(self, twap, wallet).await
+    }
+
+    async fn twap_cancel(
+        &self,
+        asset: &str,
+        twap_id: u64,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<TwapResponseStatus> {
+        ExchangeClient::twap_cancel(self, asset, twap_id, wallet).await
+    }
+
+    async fn cancel_all(&self, filter: &AccountFilter) -> Result<Vec<CancelOutcome>> {
+        ExchangeClient::cancel_all(self, filter).await
+    }
+
+    async fn close_all_positions(
+        &self,
+        filter: &AccountFilter,
+        slippage: Option<f64>,
+    ) -> Result<Vec<CloseOutcome>> {
+        ExchangeClient::close_all_positions(self, filter, slippage).await
+    }
+}
+
+/// Error returned by the [`ExchangeApi`] methods [`PaperExchangeClient`] has no simulation
+/// for, such as transfers and vault actions.
+fn not_simulated<T>(method: &str) -> Result<T> {
+    Err(Error::GenericRequest(format!(
+        "{method} is not simulated by PaperExchangeClient"
+    )))
+}
+
+#[async_trait::async_trait]
+impl ExchangeApi for PaperExchangeClient {
+    async fn submit_signed(&self, _signed: &SignedAction) -> Result<ExchangeResponseStatus> {
+        not_simulated("submit_signed")
+    }
+
+    async fn usdc_transfer(
+        &self,
+        _amount: &str,
+        _destination: &str,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        not_simulated("usdc_transfer")
+    }
+
+    async fn class_transfer(
+        &self,
+        _usdc: f64,
+        _to_perp: bool,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        not_simulated("class_transfer")
+    }
+
+    async fn vault_transfer(
+        &self,
+        _is_deposit: bool,
+        _usd: u64,
+        _vault_address: Option<H160>,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        not_simulated("vault_transfer")
+    }
+
+    async fn create_vault(
+        &self,
+        _name: String,
+        _description: String,
+        _initial_usd: u64,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<CreateVaultResponseStatus> {
+        not_simulated("create_vault")
+    }
+
+    async fn vault_modify(
+        &self,
+        _vault_address: H160,
+        _allow_deposits: Option<bool>,
+        _always_close_on_withdraw: Option<bool>,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        not_simulated("vault_modify")
+    }
+
+    async fn vault_distribute(
+        &self,
+        _vault_address: H160,
+        _usd: u64,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        not_simulated("vault_distribute")
+    }
+
+    async fn market_open(&self, params: MarketOrderParams<'_>) -> Result<ExchangeResponseStatus> {
+        PaperExchangeClient::market_open(self, params).await
+    }
+
+    async fn market_open_with_builder(
+        &self,
+        _params: MarketOrderParams<'_>,
+        _builder: BuilderInfo,
+    ) -> Result<ExchangeResponseStatus> {
+        not_simulated("market_open_with_builder")
+    }
+
+    async fn market_close(&self, params: MarketCloseParams<'_>) -> Result<ExchangeResponseStatus> {
+        PaperExchangeClient::market_close(self, params).await
+    }
+
+    async fn market_open_with_book(
+        &self,
+        _params: MarketOrderParams<'_>,
+        _book: Option<&BookDepth>,
+    ) -> Result<(ExchangeResponseStatus, FillEstimate)> {
+        not_simulated("market_open_with_book")
+    }
+
+    async fn market_close_with_book(
+        &self,
+        _params: MarketCloseParams<'_>,
+        _book: Option<&BookDepth>,
+    ) -> Result<(ExchangeResponseStatus, FillEstimate)> {
+        not_simulated("market_close_with_book")
+    }
+
+    async fn order(
+        &self,
+        order: ClientOrderRequest,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        PaperExchangeClient::order(self, order, wallet).await
+    }
+
+    async fn order_with_builder(
+        &self,
+        _order: ClientOrderRequest,
+        _wallet: Option<&dyn HyperliquidSigner>,
+        _builder: BuilderInfo,
+    ) -> Result<ExchangeResponseStatus> {
+        not_simulated("order_with_builder")
+    }
+
+    async fn bulk_order(
+        &self,
+        orders: Vec<ClientOrderRequest>,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        PaperExchangeClient::bulk_order(self, orders, wallet).await
+    }
+
+    async fn bracket_order(
+        &self,
+        _bracket: ClientBracketOrder,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<BracketOrderStatus> {
+        not_simulated("bracket_order")
+    }
+
+    async fn scale_order(
+        &self,
+        scale: ScaleOrder,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ScaleOrderStatus> {
+        PaperExchangeClient::scale_order(self, scale, wallet).await
+    }
+
+    async fn bulk_order_with_grouping(
+        &self,
+        _orders: Vec<ClientOrderRequest>,
+        _grouping: OrderGrouping,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        not_simulated("bulk_order_with_grouping")
+    }
+
+    async fn bulk_order_with_builder(
+        &self,
+        _orders: Vec<ClientOrderRequest>,
+        _wallet: Option<&dyn HyperliquidSigner>,
+        _builder: BuilderInfo,
+    ) -> Result<ExchangeResponseStatus> {
+        not_simulated("bulk_order_with_builder")
+    }
+
+    async fn cancel(
+        &self,
+        cancel: ClientCancelRequest,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        PaperExchangeClient::cancel(self, cancel, wallet).await
+    }
+
+    async fn bulk_cancel(
+        &self,
+        cancels: Vec<ClientCancelRequest>,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        PaperExchangeClient::bulk_cancel(self, cancels, wallet).await
+    }
+
+    async fn modify(
+        &self,
+        modify: ClientModifyRequest,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        PaperExchangeClient::modify(self, modify, wallet).await
+    }
+
+    async fn bulk_modify(
+        &self,
+        modifies: Vec<ClientModifyRequest>,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        PaperExchangeClient::bulk_modify(self, modifies, wallet).await
+    }
+
+    async fn modify_by_cloid(
+        &self,
+        _modify: ClientModifyRequestCloid,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        not_simulated("modify_by_cloid")
+    }
+
+    async fn bulk_modify_by_cloid(
+        &self,
+        _modifies: Vec<ClientModifyRequestCloid>,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        not_simulated("bulk_modify_by_cloid")
+    }
+
+    async fn cancel_by_cloid(
+        &self,
+        cancel: ClientCancelRequestCloid,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        PaperExchangeClient::cancel_by_cloid(self, cancel, wallet).await
+    }
+
+    async fn bulk_cancel_by_cloid(
+        &self,
+        cancels: Vec<ClientCancelRequestCloid>,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        PaperExchangeClient::bulk_cancel_by_cloid(self, cancels, wallet).await
+    }
+
+    async fn update_leverage(
+        &self,
+        _leverage: u32,
+        _coin: &str,
+        _is_cross: bool,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        not_simulated("update_leverage")
+    }
+
+    async fn update_isolated_margin(
+        &self,
+        _amount: f64,
+        _coin: &str,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        not_simulated("update_isolated_margin")
+    }
+
+    async fn approve_agent(
+        &self,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<(String, ExchangeResponseStatus)> {
+        not_simulated("approve_agent")
+    }
+
+    async fn withdraw_from_bridge(
+        &self,
+        _amount: &str,
+        _destination: &str,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        not_simulated("withdraw_from_bridge")
+    }
+
+    async fn spot_transfer(
+        &self,
+        _amount: &str,
+        _destination: &str,
+        _token: &str,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        not_simulated("spot_transfer")
+    }
+
+    async fn c_deposit(
+        &self,
+        _wei: u64,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        not_simulated("c_deposit")
+    }
+
+    async fn c_withdraw(
+        &self,
+        _wei: u64,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        not_simulated("c_withdraw")
+    }
+
+    async fn token_delegate(
+        &self,
+        _validator: H160,
+        _wei: u64,
+        _is_undelegate: bool,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        not_simulated("token_delegate")
+    }
+
+    async fn set_referrer(
+        &self,
+        _code: String,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        not_simulated("set_referrer")
+    }
+
+    async fn approve_builder_fee(
+        &self,
+        _builder: String,
+        _max_fee_rate: String,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        not_simulated("approve_builder_fee")
+    }
+
+    async fn schedule_cancel(
+        &self,
+        _time: Option<u64>,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        not_simulated("schedule_cancel")
+    }
+
+    async fn create_sub_account(
+        &self,
+        _name: String,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<CreateSubAccountResponseStatus> {
+        not_simulated("create_sub_account")
+    }
+
+    async fn sub_account_transfer(
+        &self,
+        _sub_account_user: H160,
+        _is_deposit: bool,
+        _usd: u64,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        not_simulated("sub_account_transfer")
+    }
+
+    async fn sub_account_spot_transfer(
+        &self,
+        _sub_account_user: H160,
+        _is_deposit: bool,
+        _token: &str,
+        _amount: &str,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        not_simulated("sub_account_spot_transfer")
+    }
+
+    async fn twap_order(
+        &self,
+        _twap: ClientTwapRequest,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<TwapResponseStatus> {
+        not_simulated("twap_order")
+    }
+
+    async fn twap_cancel(
+        &self,
+        _asset: &str,
+        _twap_id: u64,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<TwapResponseStatus> {
+        not_simulated("twap_cancel")
+    }
+
+    async fn cancel_all(&self, _filter: &AccountFilter) -> Result<Vec<CancelOutcome>> {
+        not_simulated("cancel_all")
+    }
+
+    async fn close_all_positions(
+        &self,
+        _filter: &AccountFilter,
+        _slippage: Option<f64>,
+    ) -> Result<Vec<CloseOutcome>> {
+        not_simulated("close_all_positions")
+    }
+}
+
+#[async_trait::async_trait]
+impl InfoApi for InfoClient {
+    async fn subscribe(
+        &mut self,
+        subscription: Subscription,
+        sender_channel: UnboundedSender<Message>,
+    ) -> Result<u32> {
+        InfoClient::subscribe(self, subscription, sender_channel).await
+    }
+
+    async fn unsubscribe(&mut self, subscription_id: u32) -> Result<()> {
+        InfoClient::unsubscribe(self, subscription_id).await
+    }
+
+    async fn open_orders(&self, address: H160) -> Result<Vec<OpenOrdersResponse>> {
+        InfoClient::open_orders(self, address).await
+    }
+
+    async fn frontend_open_orders(&self, address: H160) -> Result<Vec<BasicOrderInfo>> {
+        InfoClient::frontend_open_orders(self, address).await
+    }
+
+    async fn user_state(&self, address: H160) -> Result<UserStateResponse> {
+        InfoClient::user_state(self, address).await
+    }
+
+    async fn user_states(&self, addresses: Vec<H160>) -> Result<Vec<UserStateResponse>> {
+        InfoClient::user_states(self, addresses).await
+    }
+
+    async fn user_token_balances(&self, address: H160) -> Result<UserTokenBalanceResponse> {
+        InfoClient::user_token_balances(self, address).await
+    }
+
+    async fn user_fees(&self, address: H160) -> Result<UserFeesResponse> {
+        InfoClient::user_fees(self, address).await
+    }
+
+    async fn user_rate_limit(&self, address: H160) -> Result<UserRateLimitResponse> {
+        InfoClient::user_rate_limit(self, address).await
+    }
+
+    async fn meta(&self) -> Result<Meta> {
+        InfoClient::meta(self).await
+    }
+
+    async fn spot_meta(&self) -> Result<SpotMeta> {
+        InfoClient::spot_meta(self).await
+    }
+
+    async fn spot_meta_and_asset_contexts(&self) -> Result<Vec<SpotMetaAndAssetCtxs>> {
+        InfoClient::spot_meta_and_asset_contexts(self).await
+    }
+
+    async fn all_mids(&self) -> Result<HashMap<String, String>> {
+        InfoClient::all_mids(self).await
+    }
+
+    async fn user_fills(&self, address: H160) -> Result<Vec<UserFillsResponse>> {
+        InfoClient::user_fills(self, address).await
+    }
+
+    async fn funding_history(
+        &self,
+        coin: String,
+        start_time: u64,
+        end_time: Option<u64>,
+    ) -> Result<Vec<FundingHistoryResponse>> {
+        InfoClient::funding_history(self, coin, start_time, end_time).await
+    }
+
+    async fn user_funding_history(
+        &self,
+        user: H160,
+        start_time: u64,
+        end_time: Option<u64>,
+    ) -> Result<Vec<UserFundingResponse>> {
+        InfoClient::user_funding_history(self, user, start_time, end_time).await
+    }
+
+    async fn recent_trades(&self, coin: String) -> Result<Vec<RecentTradesResponse>> {
+        InfoClient::recent_trades(self, coin).await
+    }
+
+    async fn l2_snapshot(&self, coin: String) -> Result<L2SnapshotResponse> {
+        InfoClient::l2_snapshot(self, coin).await
+    }
+
+    async fn candles_snapshot(
+        &self,
+        coin: String,
+        interval: String,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<Vec<CandlesSnapshotResponse>> {
+        InfoClient::candles_snapshot(self, coin, interval, start_time, end_time).await
+    }
+
+    async fn query_order_by_oid(&self, address: H160, oid: u64) -> Result<OrderStatusResponse> {
+        InfoClient::query_order_by_oid(self, address, oid).await
+    }
+
+    async fn query_order_by_cloid(
+        &self,
+        address: H160,
+        cloid: Uuid,
+    ) -> Result<OrderStatusResponse> {
+        InfoClient::query_order_by_cloid(self, address, cloid).await
+    }
+
+    async fn query_referral_state(&self, address: H160) -> Result<ReferralResponse> {
+        InfoClient::query_referral_state(self, address).await
+    }
+
+    async fn historical_orders(&self, address: H160) -> Result<Vec<OrderInfo>> {
+        InfoClient::historical_orders(self, address).await
+    }
+
+    async fn sub_accounts(&self, address: H160) -> Result<Vec<SubAccountResponse>> {
+        InfoClient::sub_accounts(self, address).await
+    }
+
+    async fn vault_details(
+        &self,
+        vault_address: H160,
+        user: Option<H160>,
+    ) -> Result<VaultDetailsResponse> {
+        InfoClient::vault_details(self, vault_address, user).await
+    }
+
+    async fn user_vault_equities(&self, address: H160) -> Result<Vec<UserVaultEquity>> {
+        InfoClient::user_vault_equities(self, address).await
+    }
+
+    async fn delegations(&self, address: H160) -> Result<Vec<DelegationResponse>> {
+        InfoClient::delegations(self, address).await
+    }
+
+    async fn delegator_summary(&self, address: H160) -> Result<DelegatorSummaryResponse> {
+        InfoClient::delegator_summary(self, address).await
+    }
+
+    async fn delegator_rewards(&self, address: H160) -> Result<Vec<DelegatorRewardResponse>> {
+        InfoClient::delegator_rewards(self, address).await
+    }
+}
@@ -0,0 +1,243 @@
+use crate::{
+    meta::{Meta, SpotMeta},
+    prelude::*,
+    Error,
+};
+use std::collections::HashMap;
+
+const SPOT_ASSET_OFFSET: u32 = 10_000;
+const PERP_DEX_ASSET_OFFSET: u32 = 100_000;
+const PERP_DEX_ASSET_STRIDE: u32 = 10_000;
+
+/// Asset as the exchange identifies it on the wire.
+///
+/// Perps of the main dex use their index in `Meta::universe`, spot pairs are offset by
+/// 10000 and perps of builder-deployed dexes by `100000 + dex * 10000`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AssetId {
+    Perp(u32),
+    Spot(u32),
+    BuilderPerp { dex: u32, index: u32 },
+}
+
+impl AssetId {
+    pub fn to_wire(&self) -> u32 {
+        match *self {
+            AssetId::Perp(index) => index,
+            AssetId::Spot(index) => SPOT_ASSET_OFFSET + index,
+            AssetId::BuilderPerp { dex, index } => {
+                PERP_DEX_ASSET_OFFSET + dex * PERP_DEX_ASSET_STRIDE + index
+            }
+        }
+    }
+
+    pub fn from_wire(asset: u32) -> AssetId {
+        if asset >= PERP_DEX_ASSET_OFFSET {
+            let offset = asset - PERP_DEX_ASSET_OFFSET;
+            AssetId::BuilderPerp {
+                dex: offset / PERP_DEX_ASSET_STRIDE,
+                index: offset % PERP_DEX_ASSET_STRIDE,
+            }
+        } else if asset >= SPOT_ASSET_OFFSET {
+            AssetId::Spot(asset - SPOT_ASSET_OFFSET)
+        } else {
+            AssetId::Perp(asset)
+        }
+    }
+
+    pub fn is_spot(&self) -> bool {
+        matches!(self, AssetId::Spot(_))
+    }
+
+    /// Maximum number of decimals a price may have before subtracting `sz_decimals`.
+    pub fn max_price_decimals(&self) -> u32 {
+        if self.is_spot() {
+            8
+        } else {
+            6
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssetInfo {
+    pub id: AssetId,
+    /// Name the info endpoints use for this asset, e.g. in `all_mids` or `l2_snapshot`
+    pub coin: String,
+    pub sz_decimals: u32,
+    /// Delisted perps keep their index but can no longer be traded
+    pub is_delisted: bool,
+}
+
+/// Resolves user-facing asset names to [`AssetId`]s.
+///
+/// Perps resolve by name (`"ETH"`), builder-deployed perps by their prefixed name
+/// (`"dex:COIN"`) and spot pairs by token pair (`"PURR/USDC"`), universe name or index
+/// (`"@107"`).
+#[derive(Debug, Clone, Default)]
+pub struct AssetResolver {
+    assets: HashMap<String, AssetInfo>,
+}
+
+impl AssetResolver {
+    pub fn new(meta: &Meta, spot_meta: &SpotMeta) -> AssetResolver {
+        let mut resolver = AssetResolver::default();
+        resolver.add_perps(meta, AssetId::Perp);
+        resolver.add_spot(spot_meta);
+        resolver
+    }
+
+    /// Adds the perps of a builder-deployed dex, where `dex` is its index in the list of
+    /// perp dexes and `meta` its universe.
+    pub fn add_perp_dex(&mut self, dex: u32, meta: &Meta) {
+        self.add_perps(meta, |index| AssetId::BuilderPerp { dex, index });
+    }
+
+    fn add_perps(&mut self, meta: &Meta, to_id: impl Fn(u32) -> AssetId) {
+        for (index, asset) in meta.universe.iter().enumerate() {
+            self.assets.insert(
+                asset.name.clone(),
+                AssetInfo {
+                    id: to_id(index as u32),
+                    coin: asset.name.clone(),
+                    sz_decimals: asset.sz_decimals,
+                    is_delisted: asset.is_delisted,
+                },
+            );
+        }
+    }
+
+    fn add_spot(&mut self, spot_meta: &SpotMeta) {
+        let tokens: HashMap<usize, _> = spot_meta
+            .tokens
+            .iter()
+            .map(|token| (token.index, token))
+            .collect();
+
+        for asset in spot_meta.universe.iter() {
+            let Some(base) = tokens.get(&asset.tokens[0]) else {
+                continue;
+            };
+            let Some(quote) = tokens.get(&asset.tokens[1]) else {
+                continue;
+            };
+
+            let info = AssetInfo {
+                id: AssetId::Spot(asset.index as u32),
+                coin: asset.name.clone(),
+                sz_decimals: base.sz_decimals.into(),
+                is_delisted: false,
+            };
+            self.assets
+                .insert(format!("{}/{}", base.name, quote.name), info.clone());
+            self.assets
+                .insert(format!("@{}", asset.index), info.clone());
+            self.assets.insert(asset.name.clone(), info);
+        }
+    }
+
+    /// Looks up a tradable asset. Delisted assets fail with [`Error::AssetDelisted`].
+    pub fn resolve(&self, name: &str) -> Result<&AssetInfo> {
+        match self.assets.get(name) {
+            Some(info) if info.is_delisted => Err(Error::AssetDelisted(name.to_string())),
+            Some(info) => Ok(info),
+            None => Err(Error::AssetNotFound),
+        }
+    }
+
+    /// Whether `name` is known at all, including delisted assets.
+    pub fn contains(&self, name: &str) -> bool {
+        self.assets.contains_key(name)
+    }
+
+    pub fn asset_id(&self, name: &str) -> Result<AssetId> {
+        self.resolve(name).map(|info| info.id)
+    }
+
+    /// Wire representation of the asset, as used in signed actions.
+    pub fn asset_index(&self, name: &str) -> Result<u32> {
+        self.asset_id(name).map(|id| id.to_wire())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &AssetInfo)> {
+        self.assets.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolver() -> AssetResolver {
+        let meta: Meta = serde_json::from_str(
+            r#"{"universe":[{"name":"BTC","szDecimals":5},{"name":"ETH","szDecimals":4},{"name":"FTT","szDecimals":1,"isDelisted":true}]}"#,
+        )
+        .unwrap();
+        let spot_meta: SpotMeta = serde_json::from_str(
+            r#"{
+                "universe":[
+                    {"tokens":[1,0],"name":"PURR/USDC","index":0,"isCanonical":true},
+                    {"tokens":[2,0],"name":"@107","index":107,"isCanonical":false}
+                ],
+                "tokens":[
+                    {"name":"USDC","szDecimals":8,"weiDecimals":8,"index":0,"tokenId":"0x6d1e7cde53ba9467b783cb7c530ce054","isCanonical":true},
+                    {"name":"PURR","szDecimals":0,"weiDecimals":5,"index":1,"tokenId":"0xc1fb593aeffbeb02f85e0308e9956a90","isCanonical":true},
+                    {"name":"HYPE","szDecimals":2,"weiDecimals":8,"index":2,"tokenId":"0x0d01dc56dcaaca66ad901c959b4011ec","isCanonical":false}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let dex_meta: Meta =
+            serde_json::from_str(r#"{"universe":[{"name":"test:ABC","szDecimals":2}]}"#).unwrap();
+
+        let mut resolver = AssetResolver::new(&meta, &spot_meta);
+        resolver.add_perp_dex(1, &dex_meta);
+        resolver
+    }
+
+    #[test]
+    fn asset_id_wire_round_trip() {
+        for id in [
+            AssetId::Perp(0),
+            AssetId::Perp(173),
+            AssetId::Spot(0),
+            AssetId::Spot(107),
+            AssetId::BuilderPerp { dex: 1, index: 0 },
+            AssetId::BuilderPerp { dex: 3, index: 42 },
+        ] {
+            assert_eq!(AssetId::from_wire(id.to_wire()), id);
+        }
+        assert_eq!(AssetId::Spot(107).to_wire(), 10107);
+        assert_eq!(AssetId::BuilderPerp { dex: 1, index: 2 }.to_wire(), 110002);
+    }
+
+    #[test]
+    fn resolves_all_spellings() -> Result<()> {
+        let resolver = resolver();
+
+        assert_eq!(resolver.asset_id("ETH")?, AssetId::Perp(1));
+        assert_eq!(resolver.asset_id("PURR/USDC")?, AssetId::Spot(0));
+        assert_eq!(resolver.asset_id("@0")?, AssetId::Spot(0));
+        assert_eq!(resolver.asset_id("@107")?, AssetId::Spot(107));
+        assert_eq!(resolver.asset_id("HYPE/USDC")?, AssetId::Spot(107));
+        assert_eq!(
+            resolver.asset_id("test:ABC")?,
+            AssetId::BuilderPerp { dex: 1, index: 0 }
+        );
+
+        let hype = resolver.resolve("HYPE/USDC")?;
+        assert_eq!(hype.coin, "@107");
+        assert_eq!(hype.sz_decimals, 2);
+
+        assert!(matches!(
+            resolver.asset_id("DOGE"),
+            Err(Error::AssetNotFound)
+        ));
+        assert!(resolver.contains("FTT"));
+        assert!(matches!(
+            resolver.asset_id("FTT"),
+            Err(Error::AssetDelisted(_))
+        ));
+        Ok(())
+    }
+}
@@ -35,8 +35,8 @@ async fn main() {
         asset: "ETH".to_string(),
         is_buy: true,
         reduce_only: false,
-        limit_px: 1795.0,
-        sz: 0.01,
+        limit_px: "1795".parse().unwrap(),
+        sz: "0.01".parse().unwrap(),
         cloid: None,
         order_type: ClientOrder::Limit(ClientLimit {
             tif: "Gtc".to_string(),
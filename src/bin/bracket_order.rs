@@ -0,0 +1,52 @@
+use ethers::signers::LocalWallet;
+use log::info;
+
+use hyperliquid_rust_sdk::{
+    BaseUrl, BracketOrderStatus, ClientBracketOrder, ClientLimit, ClientOrder, ClientOrderRequest,
+    ClientTpslLeg, ExchangeClient,
+};
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    // Key was randomly generated for testing and shouldn't be used with any real funds
+    let wallet: LocalWallet = "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e"
+        .parse()
+        .unwrap();
+
+    let exchange_client = ExchangeClient::new(wallet, Some(BaseUrl::Testnet), None, None)
+        .await
+        .unwrap();
+
+    let bracket = ClientBracketOrder {
+        entry: ClientOrderRequest {
+            asset: "ETH".to_string(),
+            is_buy: true,
+            reduce_only: false,
+            limit_px: "1800".parse().unwrap(),
+            sz: "0.01".parse().unwrap(),
+            cloid: None,
+            order_type: ClientOrder::Limit(ClientLimit {
+                tif: "Gtc".to_string(),
+            }),
+        },
+        take_profit: Some(ClientTpslLeg {
+            trigger_px: "2000".parse().unwrap(),
+            limit_px: "2000".parse().unwrap(),
+            is_market: true,
+            cloid: None,
+        }),
+        stop_loss: Some(ClientTpslLeg {
+            trigger_px: "1700".parse().unwrap(),
+            limit_px: "1700".parse().unwrap(),
+            is_market: true,
+            cloid: None,
+        }),
+    };
+
+    let response = exchange_client.bracket_order(bracket, None).await.unwrap();
+    match response {
+        BracketOrderStatus::Ok(legs) => info!("Bracket placed: {legs:?}"),
+        BracketOrderStatus::Err(e) => panic!("error with exchange response: {e}"),
+    }
+}
@@ -0,0 +1,37 @@
+use ethers::signers::LocalWallet;
+use log::info;
+
+use hyperliquid_rust_sdk::{AccountFilter, BaseUrl, ExchangeClient};
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    // Key was randomly generated for testing and shouldn't be used with any real funds
+    let wallet: LocalWallet = "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e"
+        .parse()
+        .unwrap();
+
+    let exchange_client = ExchangeClient::new(wallet, Some(BaseUrl::Testnet), None, None)
+        .await
+        .unwrap();
+
+    // Cancel first so no resting order reopens a position while closing
+    let filter = AccountFilter::default();
+    for outcome in exchange_client.cancel_all(&filter).await.unwrap() {
+        info!(
+            "Cancel {} {}: {:?}",
+            outcome.order.coin, outcome.order.oid, outcome.status
+        );
+    }
+
+    for outcome in exchange_client
+        .close_all_positions(&filter, Some(0.01))
+        .await
+        .unwrap()
+    {
+        info!(
+            "Close {} {}: {:?}",
+            outcome.coin, outcome.szi, outcome.status
+        );
+    }
+}
@@ -0,0 +1,36 @@
+use ethers::signers::LocalWallet;
+use log::info;
+
+use hyperliquid_rust_sdk::{BaseUrl, DeadMansSwitch, ExchangeClient};
+use std::time::Duration;
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    // Key was randomly generated for testing and shouldn't be used with any real funds
+    let wallet: LocalWallet = "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e"
+        .parse()
+        .unwrap();
+
+    let exchange_client = ExchangeClient::new(wallet, Some(BaseUrl::Testnet), None, None)
+        .await
+        .unwrap();
+
+    // Orders are cancelled 30 seconds after the last successful renewal, and renewals stop
+    // once the loop below hasn't fed the switch for 30 seconds
+    let switch = DeadMansSwitch::start(
+        exchange_client,
+        Duration::from_secs(30),
+        Duration::from_secs(10),
+    )
+    .unwrap();
+
+    // Quote while the switch is armed, feeding it after every round
+    for _ in 0..6 {
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        switch.feed();
+    }
+
+    let response = switch.disarm().await.unwrap();
+    info!("Scheduled cancel removed: {response:?}");
+}
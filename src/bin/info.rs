@@ -1,13 +1,18 @@
 use ethers::types::H160;
-use hyperliquid_rust_sdk::{BaseUrl, InfoClient};
+use hyperliquid_rust_sdk::{BaseUrl, InfoClient, RateLimitMode, RateLimiter};
 use log::info;
 
 const ADDRESS: &str = "0xc64cc00b46101bd40aa1c3121195e85c0b0918d8";
+const VAULT_ADDRESS: &str = "0x1962905b0a2d0ce7907ae1a0d17f3e4a1f63dfb7";
 
 #[tokio::main]
 async fn main() {
     env_logger::init();
-    let info_client = InfoClient::new(Some(BaseUrl::Testnet)).await.unwrap();
+    // Requests beyond the IP budget wait for it to refill instead of getting a 429
+    let info_client = InfoClient::new(Some(BaseUrl::Testnet))
+        .await
+        .unwrap()
+        .with_rate_limiter(RateLimiter::new(RateLimitMode::Queue));
     open_orders_example(&info_client).await;
     user_state_example(&info_client).await;
     user_states_example(&info_client).await;
@@ -20,12 +25,17 @@ async fn main() {
     candles_snapshot_example(&info_client).await;
     user_token_balances_example(&info_client).await;
     user_fees_example(&info_client).await;
+    user_rate_limit_example(&info_client).await;
     user_funding_example(&info_client).await;
     spot_meta_example(&info_client).await;
     spot_meta_and_asset_contexts_example(&info_client).await;
     query_order_by_oid_example(&info_client).await;
     query_referral_state_example(&info_client).await;
     historical_orders_example(&info_client).await;
+    sub_accounts_example(&info_client).await;
+    vault_details_example(&info_client).await;
+    user_vault_equities_example(&info_client).await;
+    staking_example(&info_client).await;
 }
 
 fn address() -> H160 {
@@ -77,6 +87,15 @@ async fn user_fees_example(info_client: &InfoClient) {
     );
 }
 
+async fn user_rate_limit_example(info_client: &InfoClient) {
+    let user = address();
+
+    info!(
+        "User rate limit for {user}: {:?}",
+        info_client.user_rate_limit(user).await.unwrap()
+    );
+}
+
 async fn recent_trades(info_client: &InfoClient) {
     let coin = "ETH";
 
@@ -183,3 +202,50 @@ async fn historical_orders_example(info_client: &InfoClient) {
         info_client.historical_orders(user).await.unwrap()
     );
 }
+
+async fn sub_accounts_example(info_client: &InfoClient) {
+    let user = address();
+
+    info!(
+        "Sub-accounts for {user}: {:?}",
+        info_client.sub_accounts(user).await.unwrap()
+    );
+}
+
+async fn vault_details_example(info_client: &InfoClient) {
+    let vault_address: H160 = VAULT_ADDRESS.parse().unwrap();
+
+    info!(
+        "Vault details for {vault_address}: {:?}",
+        info_client
+            .vault_details(vault_address, Some(address()))
+            .await
+            .unwrap()
+    );
+}
+
+async fn user_vault_equities_example(info_client: &InfoClient) {
+    let user = address();
+
+    info!(
+        "Vault equities for {user}: {:?}",
+        info_client.user_vault_equities(user).await.unwrap()
+    );
+}
+
+async fn staking_example(info_client: &InfoClient) {
+    let user = address();
+
+    info!(
+        "Delegations for {user}: {:?}",
+        info_client.delegations(user).await.unwrap()
+    );
+    info!(
+        "Delegator summary for {user}: {:?}",
+        info_client.delegator_summary(user).await.unwrap()
+    );
+    info!(
+        "Staking rewards for {user}: {:?}",
+        info_client.delegator_rewards(user).await.unwrap()
+    );
+}
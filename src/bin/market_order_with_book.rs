@@ -0,0 +1,82 @@
+use ethers::signers::LocalWallet;
+use log::info;
+
+use hyperliquid_rust_sdk::{
+    BaseUrl, Error, ExchangeClient, ExchangeDataStatus, ExchangeResponseStatus, MarketCloseParams,
+    MarketOrderParams,
+};
+use std::{thread::sleep, time::Duration};
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    // Key was randomly generated for testing and shouldn't be used with any real funds
+    let wallet: LocalWallet = "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e"
+        .parse()
+        .unwrap();
+
+    let exchange_client = ExchangeClient::new(wallet, Some(BaseUrl::Testnet), None, None)
+        .await
+        .unwrap();
+
+    // Priced from the current book: the limit is the worst level the order needs, and the
+    // order isn't sent if the levels within 1% of the mid can't fill it
+    let market_open_params = MarketOrderParams {
+        asset: "ETH",
+        is_buy: true,
+        sz: "0.01".parse().unwrap(),
+        px: None,
+        slippage: Some(0.01), // 1% slippage
+        cloid: None,
+        wallet: None,
+    };
+
+    let (response, estimate) = match exchange_client
+        .market_open_with_book(market_open_params, None)
+        .await
+    {
+        Ok(res) => res,
+        Err(Error::InsufficientLiquidity {
+            requested,
+            available,
+        }) => {
+            info!("Only {available} of {requested} available within slippage, not trading");
+            return;
+        }
+        Err(e) => panic!("Error placing order: {e}"),
+    };
+    info!(
+        "Expected average fill {} (worst {}), {:.2} bps price impact",
+        estimate.avg_px,
+        estimate.worst_px,
+        estimate.price_impact_bps()
+    );
+
+    let response = match response {
+        ExchangeResponseStatus::Ok(exchange_response) => exchange_response,
+        ExchangeResponseStatus::Err(e) => panic!("Error with exchange response: {e}"),
+    };
+    let status = response.data.unwrap().statuses[0].clone();
+    match status {
+        ExchangeDataStatus::Filled(order) => info!("Order filled at {}: {order:?}", order.avg_px),
+        _ => panic!("Unexpected status: {status:?}"),
+    };
+
+    // Wait for a while before closing the position
+    sleep(Duration::from_secs(10));
+
+    let market_close_params = MarketCloseParams {
+        asset: "ETH",
+        sz: None, // Close entire position
+        px: None,
+        slippage: Some(0.01), // 1% slippage
+        cloid: None,
+        wallet: None,
+    };
+
+    let (response, estimate) = exchange_client
+        .market_close_with_book(market_close_params, None)
+        .await
+        .unwrap();
+    info!("Market close order placed: {response:?}, expected {estimate:?}");
+}
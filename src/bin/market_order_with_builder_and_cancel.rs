@@ -23,7 +23,7 @@ async fn main() {
     let market_open_params = MarketOrderParams {
         asset: "ETH",
         is_buy: true,
-        sz: 0.01,
+        sz: "0.01".parse().unwrap(),
         px: None,
         slippage: Some(0.01), // 1% slippage
         cloid: None,
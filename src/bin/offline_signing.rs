@@ -0,0 +1,44 @@
+use ethers::signers::LocalWallet;
+use hyperliquid_rust_sdk::{BaseUrl, ExchangeClient, SignedAction};
+use log::info;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+
+    // On the offline machine: sign a withdrawal and export it as JSON
+    let json = {
+        // Key was randomly generated for testing and shouldn't be used with any real funds
+        let wallet: LocalWallet =
+            "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e"
+                .parse()
+                .unwrap();
+        let nonce = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+
+        let signed = SignedAction::withdraw_from_bridge(
+            "5",
+            "0x0D1d9635D0640821d15e323ac8AdADfA9c111414",
+            nonce,
+            false,
+            &wallet,
+        )
+        .await
+        .unwrap();
+        signed.to_json().unwrap()
+    };
+    info!("Signed withdrawal: {json}");
+
+    // On the online machine: submit it without access to the signing key
+    let submitter = LocalWallet::new(&mut rand::thread_rng());
+    let exchange_client = ExchangeClient::new(submitter, Some(BaseUrl::Testnet), None, None)
+        .await
+        .unwrap();
+
+    let signed = SignedAction::from_json(&json).unwrap();
+    let res = exchange_client.submit_signed(&signed).await.unwrap();
+    info!("Withdrawal result: {res:?}");
+}
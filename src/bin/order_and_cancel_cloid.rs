@@ -2,7 +2,8 @@ use ethers::signers::LocalWallet;
 use log::info;
 
 use hyperliquid_rust_sdk::{
-    BaseUrl, ClientCancelRequestCloid, ClientLimit, ClientOrder, ClientOrderRequest, ExchangeClient,
+    BaseUrl, ClientCancelRequestCloid, ClientLimit, ClientOrder, ClientOrderRequest,
+    ExchangeClient, RetryPolicy,
 };
 use std::{thread::sleep, time::Duration};
 use uuid::Uuid;
@@ -15,9 +16,12 @@ async fn main() {
         .parse()
         .unwrap();
 
+    // Requests that time out or fail with a 5xx are retried, and since the order carries a
+    // cloid, a retry first checks whether the failed attempt placed it
     let exchange_client = ExchangeClient::new(wallet, Some(BaseUrl::Testnet), None, None)
         .await
-        .unwrap();
+        .unwrap()
+        .with_retry_policy(Some(RetryPolicy::default()));
 
     // Order and Cancel with cloid
     let cloid = Uuid::new_v4();
@@ -25,8 +29,8 @@ async fn main() {
         asset: "ETH".to_string(),
         is_buy: true,
         reduce_only: false,
-        limit_px: 1800.0,
-        sz: 0.01,
+        limit_px: "1800".parse().unwrap(),
+        sz: "0.01".parse().unwrap(),
         cloid: Some(cloid),
         order_type: ClientOrder::Limit(ClientLimit {
             tif: "Gtc".to_string(),
@@ -29,8 +29,8 @@ async fn main() {
         asset: "ETH".to_string(),
         is_buy: true,
         reduce_only: false,
-        limit_px: 1800.0,
-        sz: 0.01,
+        limit_px: "1800".parse().unwrap(),
+        sz: "0.01".parse().unwrap(),
         cloid: Some(cloid),
         order_type: ClientOrder::Limit(ClientLimit {
             tif: "Gtc".to_string(),
@@ -48,8 +48,8 @@ async fn main() {
         asset: "ETH".to_string(),
         is_buy: true,
         reduce_only: false,
-        limit_px: 1750.0, // Lower price
-        sz: 0.01,
+        limit_px: "1750".parse().unwrap(), // Lower price
+        sz: "0.01".parse().unwrap(),
         cloid: Some(cloid), // Same CLOID
         order_type: ClientOrder::Limit(ClientLimit {
             tif: "Gtc".to_string(),
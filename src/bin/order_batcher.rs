@@ -0,0 +1,53 @@
+use ethers::signers::LocalWallet;
+use log::info;
+use std::time::Duration;
+
+use hyperliquid_rust_sdk::{
+    BaseUrl, ClientLimit, ClientOrder, ClientOrderRequest, ExchangeClient, ExchangeDataStatus,
+    OrderBatcher,
+};
+
+fn bid(limit_px: &str) -> ClientOrderRequest {
+    ClientOrderRequest {
+        asset: "ETH".to_string(),
+        is_buy: true,
+        reduce_only: false,
+        limit_px: limit_px.parse().unwrap(),
+        sz: "0.01".parse().unwrap(),
+        cloid: None,
+        order_type: ClientOrder::Limit(ClientLimit {
+            tif: "Gtc".to_string(),
+        }),
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    // Key was randomly generated for testing and shouldn't be used with any real funds
+    let wallet: LocalWallet = "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e"
+        .parse()
+        .unwrap();
+
+    let exchange_client = ExchangeClient::new(wallet, Some(BaseUrl::Testnet), None, None)
+        .await
+        .unwrap();
+
+    // Orders placed by independent tasks within 20ms of each other share one action
+    let batcher = OrderBatcher::new(exchange_client, Duration::from_millis(20));
+    let tasks: Vec<_> = ["1500", "1490", "1480"]
+        .into_iter()
+        .map(|limit_px| {
+            let batcher = batcher.clone();
+            tokio::spawn(async move { batcher.order(bid(limit_px)).await })
+        })
+        .collect();
+
+    for task in tasks {
+        match task.await.unwrap() {
+            Ok(ExchangeDataStatus::Resting(order)) => info!("Resting with oid {}", order.oid),
+            Ok(status) => info!("Order status: {status:?}"),
+            Err(e) => info!("Order failed: {e}"),
+        }
+    }
+}
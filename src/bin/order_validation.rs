@@ -0,0 +1,49 @@
+use ethers::signers::LocalWallet;
+use log::info;
+
+use hyperliquid_rust_sdk::{
+    BaseUrl, ClientLimit, ClientOrder, ClientOrderRequest, Error, ExchangeClient, Px,
+    ValidationMode,
+};
+
+fn order(limit_px: f64) -> ClientOrderRequest {
+    ClientOrderRequest {
+        asset: "ETH".to_string(),
+        is_buy: true,
+        reduce_only: false,
+        limit_px: Px::try_from(limit_px).unwrap(),
+        sz: "0.01".parse().unwrap(),
+        cloid: None,
+        order_type: ClientOrder::Limit(ClientLimit {
+            tif: "Gtc".to_string(),
+        }),
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    // Key was randomly generated for testing and shouldn't be used with any real funds
+    let wallet: LocalWallet = "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e"
+        .parse()
+        .unwrap();
+
+    let exchange_client = ExchangeClient::new(wallet, Some(BaseUrl::Testnet), None, None)
+        .await
+        .unwrap();
+
+    // A price computed in floating point usually has too many significant figures
+    let limit_px = 1800.0 * 0.987654;
+
+    let strict_client = exchange_client.with_order_validation(Some(ValidationMode::Strict));
+    match strict_client.order(order(limit_px), None).await {
+        Err(Error::OrderValidation { errors, .. }) => {
+            info!("Order rejected before signing: {errors:?}")
+        }
+        response => info!("Unexpected response: {response:?}"),
+    }
+
+    let auto_fix_client = exchange_client.with_order_validation(Some(ValidationMode::AutoFix));
+    let response = auto_fix_client.order(order(limit_px), None).await.unwrap();
+    info!("Order placed after rounding: {response:?}");
+}
@@ -0,0 +1,81 @@
+use log::info;
+
+use hyperliquid_rust_sdk::{
+    BaseUrl, ClientLimit, ClientOrder, ClientOrderRequest, InfoClient, MarketOrderParams, Message,
+    MetaCache, PaperExchangeClient, Subscription, UserData,
+};
+use tokio::{
+    sync::mpsc::unbounded_channel,
+    time::{sleep, Duration},
+};
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+
+    // Real market data, simulated account: nothing here needs a key or funds
+    let meta_cache = MetaCache::new(BaseUrl::Mainnet).await.unwrap();
+    let (fills_sender, mut fills) = unbounded_channel();
+    let paper = PaperExchangeClient::new(meta_cache.assets()).with_fills(fills_sender);
+
+    let mut info_client = InfoClient::new(Some(BaseUrl::Mainnet)).await.unwrap();
+    let (sender, receiver) = unbounded_channel();
+    for subscription in [
+        Subscription::L2Book {
+            coin: "ETH".to_string(),
+        },
+        Subscription::Trades {
+            coin: "ETH".to_string(),
+        },
+    ] {
+        info_client
+            .subscribe(subscription, sender.clone())
+            .await
+            .unwrap();
+    }
+    let _feed = paper.spawn_feed(receiver);
+
+    // Wait for the first book before trading against it
+    while paper.book("ETH").unwrap().is_none() {
+        sleep(Duration::from_millis(100)).await;
+    }
+
+    let market_open = MarketOrderParams {
+        asset: "ETH",
+        is_buy: true,
+        sz: "0.01".parse().unwrap(),
+        px: None,
+        slippage: Some(0.01),
+        cloid: None,
+        wallet: None,
+    };
+    info!(
+        "Market buy: {:?}",
+        paper.market_open(market_open).await.unwrap()
+    );
+
+    // Bid just below the mid and wait for the market to trade through it
+    let mid = paper.book("ETH").unwrap().unwrap().mid_px().unwrap();
+    let bid = ClientOrderRequest {
+        asset: "ETH".to_string(),
+        is_buy: true,
+        reduce_only: false,
+        limit_px: format!("{:.1}", mid * 0.9995).parse().unwrap(),
+        sz: "0.01".parse().unwrap(),
+        cloid: None,
+        order_type: ClientOrder::Limit(ClientLimit {
+            tif: "Gtc".to_string(),
+        }),
+    };
+    info!("Bid: {:?}", paper.order(bid, None).await.unwrap());
+
+    while let Some(Message::User(user)) = fills.recv().await {
+        if let UserData::Fills(fills) = user.data {
+            info!("Filled: {fills:?}");
+            info!("ETH position: {}", paper.position("ETH").unwrap());
+        }
+        if paper.open_orders().unwrap().is_empty() {
+            break;
+        }
+    }
+}
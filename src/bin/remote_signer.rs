@@ -0,0 +1,26 @@
+use ethers::types::H160;
+use hyperliquid_rust_sdk::{BaseUrl, ExchangeClient, RemoteSigner};
+use log::info;
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    // Address of the key held by the signing service
+    let address: H160 = "0x0D1d9635D0640821d15e323ac8AdADfA9c111414"
+        .parse()
+        .unwrap();
+
+    // The private key never enters this process, every signature is requested from the
+    // signing service listening on the socket
+    let signer = RemoteSigner::unix_socket(address, "/tmp/hyperliquid-signer.sock");
+
+    let exchange_client = ExchangeClient::new(signer, Some(BaseUrl::Testnet), None, None)
+        .await
+        .unwrap();
+
+    let res = exchange_client
+        .usdc_transfer("1", "0x1719884eb866cb12b2287399b15f7db5e7d775ea", None)
+        .await
+        .unwrap();
+    info!("Usdc transfer result: {res:?}");
+}
@@ -0,0 +1,42 @@
+use ethers::signers::LocalWallet;
+use log::info;
+
+use hyperliquid_rust_sdk::{BaseUrl, ExchangeClient, ScaleOrder, ScaleWeighting};
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    // Key was randomly generated for testing and shouldn't be used with any real funds
+    let wallet: LocalWallet = "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e"
+        .parse()
+        .unwrap();
+
+    let exchange_client = ExchangeClient::new(wallet, Some(BaseUrl::Testnet), None, None)
+        .await
+        .unwrap();
+
+    // 20 bids from 1800 down to 1700, each 10% larger than the one above it
+    let scale = ScaleOrder::new(
+        "ETH",
+        true,
+        "1".parse().unwrap(),
+        "1800".parse().unwrap(),
+        "1700".parse().unwrap(),
+        20,
+    )
+    .with_weighting(ScaleWeighting::Geometric { ratio: 1.1 })
+    .with_tif("Alo");
+
+    let status = exchange_client.scale_order(scale, None).await.unwrap();
+    info!(
+        "{} levels rested, {} filled",
+        status.rested().len(),
+        status.filled().len()
+    );
+    for level in status.failed() {
+        info!(
+            "Level {} at {} failed: {:?}",
+            level.sz, level.limit_px, level.status
+        );
+    }
+}
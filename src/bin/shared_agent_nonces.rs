@@ -0,0 +1,60 @@
+use ethers::signers::LocalWallet;
+use log::info;
+
+use hyperliquid_rust_sdk::{
+    BaseUrl, ClientCancelRequest, ClientLimit, ClientOrder, ClientOrderRequest, ExchangeClient,
+    ExchangeDataStatus, ExchangeResponseStatus, InfoClient, NonceManager,
+};
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    // Key was randomly generated for testing and shouldn't be used with any real funds
+    let wallet: LocalWallet = "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e"
+        .parse()
+        .unwrap();
+
+    // Every process signing with this key points at the same directory, so none of them
+    // hands out a nonce another one already used
+    let nonces = NonceManager::file(std::env::temp_dir().join("hyperliquid-nonces")).unwrap();
+
+    let info_client = InfoClient::new(Some(BaseUrl::Testnet)).await.unwrap();
+    let offset_ms = nonces.sync_clock(&info_client).await.unwrap();
+    info!("Server clock is {offset_ms}ms ahead of the local one");
+
+    let exchange_client = ExchangeClient::new(wallet, Some(BaseUrl::Testnet), None, None)
+        .await
+        .unwrap()
+        .with_nonce_manager(nonces);
+
+    let order = ClientOrderRequest {
+        asset: "ETH".to_string(),
+        is_buy: true,
+        reduce_only: false,
+        limit_px: "1800".parse().unwrap(),
+        sz: "0.01".parse().unwrap(),
+        cloid: None,
+        order_type: ClientOrder::Limit(ClientLimit {
+            tif: "Gtc".to_string(),
+        }),
+    };
+
+    let response = exchange_client.order(order, None).await.unwrap();
+    info!("Order placed: {response:?}");
+
+    let ExchangeResponseStatus::Ok(response) = response else {
+        return;
+    };
+    let Some(ExchangeDataStatus::Resting(order)) =
+        response.data.and_then(|d| d.statuses.first().cloned())
+    else {
+        return;
+    };
+
+    let cancel = ClientCancelRequest {
+        asset: "ETH".to_string(),
+        oid: order.oid,
+    };
+    let response = exchange_client.cancel(cancel, None).await.unwrap();
+    info!("Order cancelled: {response:?}");
+}
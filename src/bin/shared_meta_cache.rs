@@ -0,0 +1,32 @@
+use ethers::signers::LocalWallet;
+use log::info;
+use std::time::Duration;
+
+use hyperliquid_rust_sdk::{BaseUrl, ExchangeClient, MetaCache};
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    // Keys were randomly generated for testing and shouldn't be used with any real funds
+    let wallet: LocalWallet = "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e"
+        .parse()
+        .unwrap();
+    let other_wallet = LocalWallet::new(&mut rand::thread_rng());
+
+    // One copy of the metadata for both clients, refreshed every minute so assets listed
+    // while the bot runs can be traded
+    let meta_cache = MetaCache::new(BaseUrl::Testnet).await.unwrap();
+    let _refresh = meta_cache.spawn_refresh(Duration::from_secs(60));
+
+    let exchange_client = ExchangeClient::from_meta_cache(wallet, meta_cache.clone(), None);
+    let other_exchange_client = ExchangeClient::from_meta_cache(other_wallet, meta_cache, None);
+
+    let eth = exchange_client.assets().asset_id("ETH").unwrap();
+    info!("ETH is {eth:?}");
+
+    let res = other_exchange_client
+        .update_leverage(5, "ETH", false, None)
+        .await
+        .unwrap();
+    info!("Update leverage response: {res:?}");
+}
@@ -23,8 +23,8 @@ async fn main() {
         asset: "XYZTWO/USDC".to_string(),
         is_buy: true,
         reduce_only: false,
-        limit_px: 0.00002378,
-        sz: 1000000.0,
+        limit_px: "0.00002378".parse().unwrap(),
+        sz: "1000000".parse().unwrap(),
         cloid: None,
         order_type: ClientOrder::Limit(ClientLimit {
             tif: "Gtc".to_string(),
@@ -0,0 +1,30 @@
+use ethers::signers::LocalWallet;
+use hyperliquid_rust_sdk::{BaseUrl, ExchangeClient};
+use log::info;
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    // Key was randomly generated for testing and shouldn't be used with any real funds
+    let wallet: LocalWallet = "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e"
+        .parse()
+        .unwrap();
+
+    let exchange_client = ExchangeClient::new(wallet, Some(BaseUrl::Testnet), None, None)
+        .await
+        .unwrap();
+
+    let wei = 10_000_000; // 0.1 HYPE
+    let validator = "0x5ac99df645f3414876c816caa18b2d234024b487"
+        .parse()
+        .unwrap();
+
+    let res = exchange_client.c_deposit(wei, None).await.unwrap();
+    info!("Staking deposit result: {res:?}");
+
+    let res = exchange_client
+        .token_delegate(validator, wei, false, None)
+        .await
+        .unwrap();
+    info!("Delegation result: {res:?}");
+}
@@ -0,0 +1,53 @@
+use ethers::signers::LocalWallet;
+use log::info;
+
+use hyperliquid_rust_sdk::{
+    BaseUrl, ClientLimit, ClientOrder, ClientOrderRequest, CreateSubAccountResponseStatus,
+    ExchangeClient,
+};
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    // Key was randomly generated for testing and shouldn't be used with any real funds
+    let wallet: LocalWallet = "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e"
+        .parse()
+        .unwrap();
+
+    let exchange_client = ExchangeClient::new(wallet, Some(BaseUrl::Testnet), None, None)
+        .await
+        .unwrap();
+
+    let response = exchange_client
+        .create_sub_account("example".to_string(), None)
+        .await
+        .unwrap();
+    info!("Sub-account created: {response:?}");
+
+    let sub_account_user = match response {
+        CreateSubAccountResponseStatus::Ok(response) => response.data,
+        CreateSubAccountResponseStatus::Err(e) => panic!("error with exchange response: {e}"),
+    };
+
+    let usd = 1_000_000; // 1 USD
+    let response = exchange_client
+        .sub_account_transfer(sub_account_user, true, usd, None)
+        .await
+        .unwrap();
+    info!("Sub-account transfer result: {response:?}");
+
+    let sub_account_client = exchange_client.for_sub_account(sub_account_user);
+    let order = ClientOrderRequest {
+        asset: "ETH".to_string(),
+        is_buy: true,
+        reduce_only: false,
+        limit_px: "1800".parse().unwrap(),
+        sz: "0.01".parse().unwrap(),
+        cloid: None,
+        order_type: ClientOrder::Limit(ClientLimit {
+            tif: "Gtc".to_string(),
+        }),
+    };
+    let response = sub_account_client.order(order, None).await.unwrap();
+    info!("Sub-account order placed: {response:?}");
+}
@@ -0,0 +1,41 @@
+use ethers::signers::LocalWallet;
+use log::info;
+
+use hyperliquid_rust_sdk::{BaseUrl, ClientTwapRequest, ExchangeClient};
+use std::{thread::sleep, time::Duration};
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+    // Key was randomly generated for testing and shouldn't be used with any real funds
+    let wallet: LocalWallet = "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e"
+        .parse()
+        .unwrap();
+
+    let exchange_client = ExchangeClient::new(wallet, Some(BaseUrl::Testnet), None, None)
+        .await
+        .unwrap();
+
+    let twap = ClientTwapRequest {
+        asset: "ETH".to_string(),
+        is_buy: true,
+        sz: "0.1".parse().unwrap(),
+        reduce_only: false,
+        minutes: 10,
+        randomize: false,
+    };
+
+    let response = exchange_client.twap_order(twap, None).await.unwrap();
+    info!("TWAP placed: {response:?}");
+
+    let twap_id = response.twap_id().unwrap();
+
+    // So you can see the TWAP running before it's cancelled
+    sleep(Duration::from_secs(10));
+
+    let response = exchange_client
+        .twap_cancel("ETH", twap_id, None)
+        .await
+        .unwrap();
+    info!("TWAP cancelled: {response:?}");
+}
@@ -0,0 +1,331 @@
+use crate::{
+    helpers::{float_to_string_for_hashing, WIRE_DECIMALS},
+    prelude::*,
+    Error,
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{fmt, str::FromStr};
+
+const UNIT_SCALE: u128 = 10u128.pow(WIRE_DECIMALS as u32);
+
+fn parse_units(s: &str) -> Result<u128> {
+    let invalid = || Error::DecimalParse(s.to_string());
+
+    let (int_part, frac_part) = s.split_once('.').unwrap_or((s, ""));
+    if (int_part.is_empty() && frac_part.is_empty())
+        || frac_part.len() > WIRE_DECIMALS as usize
+        || !int_part.bytes().all(|b| b.is_ascii_digit())
+        || !frac_part.bytes().all(|b| b.is_ascii_digit())
+    {
+        return Err(invalid());
+    }
+
+    let int_units = if int_part.is_empty() {
+        0
+    } else {
+        int_part
+            .parse::<u128>()
+            .ok()
+            .and_then(|int| int.checked_mul(UNIT_SCALE))
+            .ok_or_else(invalid)?
+    };
+    let frac_units = if frac_part.is_empty() {
+        0
+    } else {
+        frac_part.parse::<u128>().map_err(|_| invalid())?
+            * 10u128.pow(WIRE_DECIMALS as u32 - frac_part.len() as u32)
+    };
+    int_units.checked_add(frac_units).ok_or_else(invalid)
+}
+
+fn format_units(units: u128, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let int = units / UNIT_SCALE;
+    let frac = units % UNIT_SCALE;
+    if frac == 0 {
+        return write!(f, "{int}");
+    }
+    let frac = format!("{:0width$}", frac, width = WIRE_DECIMALS as usize);
+    write!(f, "{int}.{}", frac.trim_end_matches('0'))
+}
+
+fn round_units(units: u128, factor: u128) -> u128 {
+    (units + factor / 2) / factor * factor
+}
+
+fn truncate_units(units: u128, factor: u128) -> u128 {
+    units / factor * factor
+}
+
+fn ceil_units(units: u128, factor: u128) -> u128 {
+    units.div_ceil(factor) * factor
+}
+
+fn digit_count(units: u128) -> u32 {
+    units.checked_ilog10().map_or(0, |log| log + 1)
+}
+
+macro_rules! wire_decimal {
+    ($(#[$doc:meta])* $name:ident) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name {
+            units: u128,
+        }
+
+        impl $name {
+            pub const ZERO: $name = $name { units: 0 };
+
+            pub fn to_f64(&self) -> f64 {
+                self.units as f64 / UNIT_SCALE as f64
+            }
+
+            pub fn is_zero(&self) -> bool {
+                self.units == 0
+            }
+
+            pub fn is_integer(&self) -> bool {
+                self.units % UNIT_SCALE == 0
+            }
+
+            /// Number of digits after the decimal point, ignoring trailing zeros.
+            pub fn decimals(&self) -> u32 {
+                let frac = self.units % UNIT_SCALE;
+                if frac == 0 {
+                    return 0;
+                }
+                let mut trailing_zeros = 0;
+                let mut frac = frac;
+                while frac % 10 == 0 {
+                    frac /= 10;
+                    trailing_zeros += 1;
+                }
+                WIRE_DECIMALS as u32 - trailing_zeros
+            }
+
+            /// Number of digits between the first and last non-zero digit.
+            pub fn significant_figures(&self) -> u32 {
+                if self.units == 0 {
+                    return 0;
+                }
+                let mut units = self.units;
+                while units % 10 == 0 {
+                    units /= 10;
+                }
+                digit_count(units)
+            }
+
+            /// Rounds half up to at most `decimals` digits after the decimal point.
+            pub fn round_to_decimals(&self, decimals: u32) -> $name {
+                self.round_decimals_with(decimals, round_units)
+            }
+
+            /// Truncates toward zero to at most `decimals` digits after the decimal point.
+            pub fn truncate_to_decimals(&self, decimals: u32) -> $name {
+                self.round_decimals_with(decimals, truncate_units)
+            }
+
+            /// Rounds up to at most `decimals` digits after the decimal point.
+            pub fn ceil_to_decimals(&self, decimals: u32) -> $name {
+                self.round_decimals_with(decimals, ceil_units)
+            }
+
+            /// Rounds half up to at most `sig_figs` significant figures and then to at most
+            /// `max_decimals` digits after the decimal point.
+            pub fn round_to_significant_and_decimal(&self, sig_figs: u32, max_decimals: u32) -> $name {
+                self.round_significant_and_decimal_with(sig_figs, max_decimals, round_units)
+            }
+
+            /// Like [`round_to_significant_and_decimal`](Self::round_to_significant_and_decimal),
+            /// truncating toward zero instead.
+            pub fn truncate_to_significant_and_decimal(&self, sig_figs: u32, max_decimals: u32) -> $name {
+                self.round_significant_and_decimal_with(sig_figs, max_decimals, truncate_units)
+            }
+
+            /// Like [`round_to_significant_and_decimal`](Self::round_to_significant_and_decimal),
+            /// rounding up instead.
+            pub fn ceil_to_significant_and_decimal(&self, sig_figs: u32, max_decimals: u32) -> $name {
+                self.round_significant_and_decimal_with(sig_figs, max_decimals, ceil_units)
+            }
+
+            fn round_decimals_with(&self, decimals: u32, round: fn(u128, u128) -> u128) -> $name {
+                if decimals >= WIRE_DECIMALS as u32 {
+                    return *self;
+                }
+                let factor = 10u128.pow(WIRE_DECIMALS as u32 - decimals);
+                $name {
+                    units: round(self.units, factor),
+                }
+            }
+
+            fn round_significant_and_decimal_with(
+                &self,
+                sig_figs: u32,
+                max_decimals: u32,
+                round: fn(u128, u128) -> u128,
+            ) -> $name {
+                let digits = digit_count(self.units);
+                let rounded = if digits > sig_figs {
+                    $name {
+                        units: round(self.units, 10u128.pow(digits - sig_figs)),
+                    }
+                } else {
+                    *self
+                };
+                rounded.round_decimals_with(max_decimals, round)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = Error;
+
+            fn from_str(s: &str) -> Result<$name> {
+                parse_units(s).map(|units| $name { units })
+            }
+        }
+
+        impl TryFrom<f64> for $name {
+            type Error = Error;
+
+            /// Converts through the 8 decimal wire representation, so `0.1` becomes exactly
+            /// `0.1` rather than the nearest binary fraction.
+            fn try_from(value: f64) -> Result<$name> {
+                float_to_string_for_hashing(value).parse()
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                format_units(self.units, f)
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<$name, D::Error> {
+                let s = String::deserialize(deserializer)?;
+                s.parse().map_err(serde::de::Error::custom)
+            }
+        }
+    };
+}
+
+wire_decimal!(
+    /// Exact non-negative price with up to 8 decimals, formatted the way the exchange expects
+    /// it on the wire.
+    Px
+);
+
+wire_decimal!(
+    /// Exact non-negative size with up to 8 decimals, formatted the way the exchange expects
+    /// it on the wire.
+    Sz
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_formats_wire_representation() -> Result<()> {
+        for (input, wire) in [
+            ("0", "0"),
+            ("0.0", "0"),
+            ("2000.0", "2000"),
+            ("3.5", "3.5"),
+            ("0.00076000", "0.00076"),
+            ("0.00000001", "0.00000001"),
+            ("87654321.12345678", "87654321.12345678"),
+            (".5", "0.5"),
+        ] {
+            assert_eq!(input.parse::<Px>()?.to_string(), wire);
+        }
+
+        for invalid in ["", ".", "-1", "1e5", "0.123456789", "1.2.3", "abc"] {
+            assert!(invalid.parse::<Sz>().is_err(), "{invalid} should not parse");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn converts_from_f64_exactly() -> Result<()> {
+        assert_eq!(Px::try_from(0.1)?.to_string(), "0.1");
+        assert_eq!(Px::try_from(-0.0)?.to_string(), "0");
+        assert_eq!(Px::try_from(1800.0)?, "1800".parse()?);
+        assert_eq!(Sz::try_from(0.00076000)?.to_string(), "0.00076");
+        assert!(Px::try_from(-1.0).is_err());
+        assert!(Px::try_from(f64::NAN).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn rounds_without_float_error() -> Result<()> {
+        // 1.005 is 1.00499999999999989... as a float, so float rounding gives 1.0
+        assert_eq!(
+            "1.005".parse::<Px>()?.round_to_decimals(2).to_string(),
+            "1.01"
+        );
+        assert_eq!(
+            "0.129".parse::<Sz>()?.truncate_to_decimals(2).to_string(),
+            "0.12"
+        );
+        assert_eq!(
+            "0.121".parse::<Sz>()?.ceil_to_decimals(2).to_string(),
+            "0.13"
+        );
+        assert_eq!(
+            "2000.15"
+                .parse::<Px>()?
+                .truncate_to_significant_and_decimal(5, 2)
+                .to_string(),
+            "2000.1"
+        );
+        assert_eq!(
+            "2000.11"
+                .parse::<Px>()?
+                .ceil_to_significant_and_decimal(5, 2)
+                .to_string(),
+            "2000.2"
+        );
+        assert_eq!(
+            "2047.123456"
+                .parse::<Px>()?
+                .round_to_significant_and_decimal(5, 2)
+                .to_string(),
+            "2047.1"
+        );
+        assert_eq!(
+            "123456"
+                .parse::<Px>()?
+                .round_to_significant_and_decimal(5, 2)
+                .to_string(),
+            "123460"
+        );
+        assert_eq!(
+            "0.00123456"
+                .parse::<Px>()?
+                .round_to_significant_and_decimal(5, 8)
+                .to_string(),
+            "0.0012346"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn counts_decimals_and_significant_figures() -> Result<()> {
+        let px: Px = "1234.50".parse()?;
+        assert_eq!(px.decimals(), 1);
+        assert_eq!(px.significant_figures(), 5);
+        assert!(!px.is_integer());
+
+        let px: Px = "1200".parse()?;
+        assert_eq!(px.decimals(), 0);
+        assert_eq!(px.significant_figures(), 2);
+        assert!(px.is_integer());
+        Ok(())
+    }
+}
@@ -1,3 +1,5 @@
+use crate::OrderValidationError;
+use ethers::types::H160;
 use thiserror::Error;
 
 #[derive(Error, Debug, Clone)]
@@ -21,6 +23,10 @@ pub enum Error {
     ChainNotAllowed,
     #[error("Asset not found")]
     AssetNotFound,
+    #[error("Asset {0:?} is delisted")]
+    AssetDelisted(String),
+    #[error("Book depth within slippage covers {available} of the requested size {requested}")]
+    InsufficientLiquidity { requested: f64, available: f64 },
     #[error("Error from Eip712 struct: {0:?}")]
     Eip712(String),
     #[error("Json parse error: {0:?}")]
@@ -55,10 +61,25 @@ pub enum Error {
     RmpParse(String),
     #[error("Invalid input number")]
     FloatStringParse,
+    #[error("Invalid decimal, expected a non-negative number with at most 8 decimals: {0:?}")]
+    DecimalParse(String),
     #[error("No cloid found in order request when expected")]
     NoCloid,
     #[error("ECDSA signature failed: {0:?}")]
     SignatureFailure(String),
     #[error("Vault address not found")]
     VaultAddressNotFound,
+    #[error("Order {index} failed validation: {errors:?}")]
+    OrderValidation {
+        index: usize,
+        errors: Vec<OrderValidationError>,
+    },
+    #[error("Nonce store error: {0:?}")]
+    NonceStore(String),
+    #[error("Nonce {nonce} was already used by {address:?}")]
+    NonceReused { address: H160, nonce: u64 },
+    #[error("Action expired before the exchange received it: {0:?}")]
+    ActionExpired(String),
+    #[error("Rate limited: {0}")]
+    RateLimited(String),
 }
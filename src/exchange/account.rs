@@ -0,0 +1,422 @@
+use crate::{
+    prelude::*, AssetResolver, BasicOrderInfo, ClientCancelRequest, ClientLimit, ClientOrder,
+    ClientOrderRequest, ExchangeClient, ExchangeDataStatus, Px, Sz,
+};
+use log::debug;
+
+/// Orders or positions [`ExchangeClient::cancel_all`] and
+/// [`ExchangeClient::close_all_positions`] act on. The default matches everything.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountFilter {
+    /// Only these assets, by any name [`AssetResolver`] accepts, or all if `None`
+    pub assets: Option<Vec<String>>,
+    pub perp: bool,
+    pub spot: bool,
+    /// Whether trigger orders still waiting for their trigger price are included
+    pub triggers: bool,
+}
+
+impl Default for AccountFilter {
+    fn default() -> Self {
+        AccountFilter {
+            assets: None,
+            perp: true,
+            spot: true,
+            triggers: true,
+        }
+    }
+}
+
+impl AccountFilter {
+    /// Whether `coin` matches, or the error resolving it if that's needed to tell.
+    fn matches(&self, assets: &AssetResolver, coin: &str) -> Result<bool> {
+        if let Some(names) = &self.assets {
+            if !names
+                .iter()
+                .any(|name| assets.resolve(name).is_ok_and(|info| info.coin == coin))
+            {
+                return Ok(false);
+            }
+        }
+        let is_spot = assets.resolve(coin)?.id.is_spot();
+        Ok(if is_spot { self.spot } else { self.perp })
+    }
+}
+
+/// Result of cancelling one order in [`ExchangeClient::cancel_all`].
+#[derive(Debug, Clone)]
+pub struct CancelOutcome {
+    pub order: BasicOrderInfo,
+    pub status: Result<ExchangeDataStatus>,
+}
+
+/// Result of closing one position in [`ExchangeClient::close_all_positions`].
+#[derive(Debug, Clone)]
+pub struct CloseOutcome {
+    pub coin: String,
+    /// Signed size of the position before closing it
+    pub szi: String,
+    pub status: Result<ExchangeDataStatus>,
+}
+
+impl ExchangeClient {
+    /// Cancels every open order of the client's account matching `filter`, perp and spot,
+    /// in batches of at most [`MAX_BULK_ACTION_SIZE`](crate::MAX_BULK_ACTION_SIZE).
+    ///
+    /// An order whose asset can't be resolved, such as one that was delisted, or a batch
+    /// failing doesn't stop the remaining ones; those orders report the error instead.
+    pub async fn cancel_all(&self, filter: &AccountFilter) -> Result<Vec<CancelOutcome>> {
+        let info_client = self.meta_cache().info_client();
+        let open_orders = info_client
+            .frontend_open_orders(self.account_address())
+            .await?;
+        let assets = self
+            .meta_cache()
+            .assets_for(open_orders.iter().map(|order| order.coin.as_str()))
+            .await?;
+
+        let mut outcomes = Vec::new();
+        let mut orders = Vec::new();
+        for order in open_orders {
+            if !filter.triggers && order.is_trigger {
+                continue;
+            }
+            match filter.matches(&assets, &order.coin) {
+                Ok(true) => orders.push(order),
+                Ok(false) => {}
+                Err(e) => outcomes.push(CancelOutcome {
+                    order,
+                    status: Err(e),
+                }),
+            }
+        }
+        debug!("Cancelling {} open orders", orders.len());
+
+        let cancels = orders
+            .iter()
+            .map(|order| ClientCancelRequest {
+                asset: order.coin.clone(),
+                oid: order.oid,
+            })
+            .collect();
+        let statuses = self.bulk_cancel_chunked(cancels, None).await;
+        outcomes.extend(
+            orders
+                .into_iter()
+                .zip(statuses)
+                .map(|(order, status)| CancelOutcome { order, status }),
+        );
+        Ok(outcomes)
+    }
+
+    /// Closes every perp position of the client's account matching `filter` with reduce-only
+    /// IOC orders priced `slippage` (5% if `None`) past the mid, in batches of at most
+    /// [`MAX_BULK_ACTION_SIZE`](crate::MAX_BULK_ACTION_SIZE). Spot balances aren't positions
+    /// and are left untouched.
+    ///
+    /// A position that can't be resolved or priced or a batch failing doesn't stop the
+    /// remaining ones; those positions report the error instead.
+    pub async fn close_all_positions(
+        &self,
+        filter: &AccountFilter,
+        slippage: Option<f64>,
+    ) -> Result<Vec<CloseOutcome>> {
+        let slippage = slippage.unwrap_or(0.05);
+        let info_client = self.meta_cache().info_client();
+        let user_state = info_client.user_state(self.account_address()).await?;
+        let positions: Vec<_> = user_state
+            .asset_positions
+            .into_iter()
+            .map(|asset_position| asset_position.position)
+            .filter(|position| position.szi.parse::<f64>().is_ok_and(|szi| szi != 0.0))
+            .collect();
+        if positions.is_empty() {
+            return Ok(Vec::new());
+        }
+        let assets = self
+            .meta_cache()
+            .assets_for(positions.iter().map(|position| position.coin.as_str()))
+            .await?;
+        let all_mids = info_client.all_mids().await?;
+
+        let mut outcomes = Vec::new();
+        let mut orders = Vec::new();
+        for position in positions {
+            match filter.matches(&assets, &position.coin) {
+                Ok(true) => {}
+                Ok(false) => continue,
+                Err(e) => {
+                    outcomes.push(CloseOutcome {
+                        coin: position.coin,
+                        szi: position.szi,
+                        status: Err(e),
+                    });
+                    continue;
+                }
+            }
+            let is_buy = position.szi.starts_with('-');
+            let mid = all_mids
+                .get(&position.coin)
+                .and_then(|mid| mid.parse::<Px>().ok());
+            let order = async {
+                let (limit_px, sz_decimals) = self
+                    .calculate_slippage_price(&position.coin, is_buy, slippage, mid)
+                    .await?;
+                let sz: Sz = position.szi.trim_start_matches('-').parse()?;
+                Ok(ClientOrderRequest {
+                    asset: position.coin.clone(),
+                    is_buy,
+                    reduce_only: true,
+                    limit_px,
+                    sz: sz.round_to_decimals(sz_decimals),
+                    cloid: None,
+                    order_type: ClientOrder::Limit(ClientLimit {
+                        tif: "Ioc".to_string(),
+                    }),
+                })
+            };
+            match order.await {
+                Ok(order) => orders.push((position.coin, position.szi, order)),
+                Err(e) => outcomes.push(CloseOutcome {
+                    coin: position.coin,
+                    szi: position.szi,
+                    status: Err(e),
+                }),
+            }
+        }
+        debug!("Closing {} positions", orders.len());
+
+        let (closed, requests): (Vec<_>, Vec<_>) = orders
+            .into_iter()
+            .map(|(coin, szi, order)| ((coin, szi), order))
+            .unzip();
+        let statuses = self.bulk_order_chunked(requests, None).await;
+        outcomes.extend(
+            closed
+                .into_iter()
+                .zip(statuses)
+                .map(|((coin, szi), status)| CloseOutcome { coin, szi, status }),
+        );
+        Ok(outcomes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_server, BaseUrl, Error, MarketCloseParams};
+    use ethers::types::H160;
+    use std::sync::{Arc, Mutex};
+
+    const META: &str =
+        r#"{"universe":[{"name":"BTC","szDecimals":5},{"name":"ETH","szDecimals":4}]}"#;
+    const SPOT_META: &str = r#"{
+        "universe":[{"tokens":[1,0],"name":"PURR/USDC","index":0,"isCanonical":true}],
+        "tokens":[
+            {"name":"USDC","szDecimals":8,"weiDecimals":8,"index":0,"tokenId":"0x6d1e7cde53ba9467b783cb7c530ce054","isCanonical":true},
+            {"name":"PURR","szDecimals":0,"weiDecimals":5,"index":1,"tokenId":"0xc1fb593aeffbeb02f85e0308e9956a90","isCanonical":true}
+        ]
+    }"#;
+
+    fn open_order(coin: &str, oid: u64, is_trigger: bool) -> String {
+        let tif = if is_trigger { "null" } else { r#""Gtc""# };
+        format!(
+            r#"{{"coin":"{coin}","side":"B","limitPx":"1800","sz":"1","oid":{oid},"timestamp":1,"triggerCondition":"N/A","isTrigger":{is_trigger},"triggerPx":"0","isPositionTpsl":false,"reduceOnly":false,"orderType":"Limit","origSz":"1","tif":{tif},"cloid":null}}"#
+        )
+    }
+
+    fn position(coin: &str, szi: &str) -> String {
+        format!(
+            r#"{{"type":"oneWay","position":{{"coin":"{coin}","entryPx":"2000","leverage":{{"type":"cross","value":20}},"liquidationPx":null,"marginUsed":"50","positionValue":"1000","returnOnEquity":"0","szi":"{szi}","unrealizedPnl":"0","maxLeverage":50,"cumFunding":{{"allTime":"0","sinceOpen":"0","sinceChange":"0"}}}}}}"#
+        )
+    }
+
+    fn user_state(positions: &[String]) -> String {
+        format!(
+            r#"{{"assetPositions":[{}],"crossMarginSummary":{{"accountValue":"0","totalMarginUsed":"0","totalNtlPos":"0","totalRawUsd":"0"}},"marginSummary":{{"accountValue":"0","totalMarginUsed":"0","totalNtlPos":"0","totalRawUsd":"0"}},"withdrawable":"0"}}"#,
+            positions.join(",")
+        )
+    }
+
+    /// Exchange answering every action with one status per element of its batch.
+    async fn serve_account(
+        info: impl Fn(&str) -> Option<String> + Send + Sync + 'static,
+        requests: Arc<Mutex<Vec<String>>>,
+    ) -> BaseUrl {
+        test_server::serve_exchange(META, SPOT_META, move |path, body| {
+            requests.lock().unwrap().push(body.to_string());
+            if path != "/exchange" {
+                return info(body).unwrap_or_else(|| "{}".to_string());
+            }
+            let action: serde_json::Value = serde_json::from_str(body).unwrap();
+            let (batch, status) = match action["action"]["type"].as_str() {
+                Some("cancel") => ("cancels", r#""success""#),
+                _ => (
+                    "orders",
+                    r#"{"filled":{"totalSz":"0.5","avgPx":"2001","oid":9}}"#,
+                ),
+            };
+            let len = action["action"][batch].as_array().unwrap().len();
+            let statuses = vec![status; len].join(",");
+            format!(
+                r#"{{"status":"ok","response":{{"type":"{}","data":{{"statuses":[{statuses}]}}}}}}"#,
+                action["action"]["type"].as_str().unwrap()
+            )
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_cancel_all_filters_and_chunks() -> Result<()> {
+        let mut orders: Vec<String> = (0..60).map(|oid| open_order("ETH", oid, false)).collect();
+        orders.push(open_order("ETH", 100, true));
+        orders.push(open_order("PURR/USDC", 101, false));
+        orders.push(open_order("BTC", 102, false));
+        orders.push(open_order("GONE", 103, false));
+        let orders = format!("[{}]", orders.join(","));
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let base_url = serve_account(
+            move |body| {
+                body.contains(r#""type":"frontendOpenOrders""#)
+                    .then(|| orders.clone())
+            },
+            requests.clone(),
+        )
+        .await;
+
+        let sub_account = H160::from_low_u64_be(7);
+        let client = test_server::test_client(base_url)
+            .await?
+            .for_sub_account(sub_account);
+        let filter = AccountFilter {
+            assets: Some(vec!["ETH".to_string()]),
+            spot: false,
+            triggers: false,
+            ..AccountFilter::default()
+        };
+        let outcomes = client.cancel_all(&filter).await?;
+
+        assert_eq!(outcomes.len(), 60);
+        assert!(outcomes
+            .iter()
+            .all(|outcome| matches!(outcome.status, Ok(ExchangeDataStatus::Success))));
+        {
+            let requests = requests.lock().unwrap();
+            assert!(requests
+                .iter()
+                .any(|body| body.contains("frontendOpenOrders")
+                    && body.contains(&format!("{sub_account:?}"))));
+            let cancels: Vec<_> = requests
+                .iter()
+                .filter(|body| body.contains(r#""type":"cancel""#))
+                .collect();
+            assert_eq!(cancels.len(), 2);
+            assert!(cancels
+                .iter()
+                .all(|body| body.contains(&format!(r#""vaultAddress":"{sub_account:?}""#))));
+        }
+
+        // Spot orders resolve as well, and an unknown coin fails on its own
+        let all = client.cancel_all(&AccountFilter::default()).await?;
+        assert_eq!(all.len(), 64);
+        let failed: Vec<_> = all
+            .iter()
+            .filter(|outcome| outcome.status.is_err())
+            .collect();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].order.oid, 103);
+        assert!(matches!(failed[0].status, Err(Error::AssetNotFound)));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_market_close_sub_account_position() -> Result<()> {
+        let sub_account = H160::from_low_u64_be(7);
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let base_url = serve_account(
+            move |body| {
+                if body.contains(r#""type":"clearinghouseState""#) {
+                    // Only the sub-account holds a position
+                    let positions = if body.contains(&format!("{sub_account:?}")) {
+                        vec![position("ETH", "-0.5")]
+                    } else {
+                        Vec::new()
+                    };
+                    Some(user_state(&positions))
+                } else if body.contains(r#""type":"allMids""#) {
+                    Some(r#"{"ETH":"2000"}"#.to_string())
+                } else {
+                    None
+                }
+            },
+            requests.clone(),
+        )
+        .await;
+
+        let client = test_server::test_client(base_url)
+            .await?
+            .for_sub_account(sub_account);
+        let params = MarketCloseParams {
+            asset: "ETH",
+            sz: None,
+            px: None,
+            slippage: Some(0.01),
+            cloid: None,
+            wallet: None,
+        };
+        client.market_close(params).await?;
+
+        let requests = requests.lock().unwrap();
+        let order = requests
+            .iter()
+            .find(|body| body.contains(r#""type":"order""#))
+            .unwrap();
+        assert!(order.contains(r#""b":true,"p":"2020","s":"0.5","r":true"#));
+        assert!(order.contains(&format!(r#""vaultAddress":"{sub_account:?}""#)));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_close_all_positions() -> Result<()> {
+        let state = user_state(&[
+            position("ETH", "-0.5"),
+            position("BTC", "0.0"),
+            position("GONE", "1"),
+        ]);
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let base_url = serve_account(
+            move |body| {
+                if body.contains(r#""type":"clearinghouseState""#) {
+                    Some(state.clone())
+                } else if body.contains(r#""type":"allMids""#) {
+                    Some(r#"{"ETH":"2000","BTC":"60000"}"#.to_string())
+                } else {
+                    None
+                }
+            },
+            requests.clone(),
+        )
+        .await;
+
+        let client = test_server::test_client(base_url).await?;
+        let outcomes = client
+            .close_all_positions(&AccountFilter::default(), Some(0.01))
+            .await?;
+
+        assert_eq!(outcomes.len(), 2);
+        let (gone, eth) = (&outcomes[0], &outcomes[1]);
+        assert_eq!(gone.coin, "GONE");
+        assert!(matches!(gone.status, Err(Error::AssetNotFound)));
+        assert_eq!((eth.coin.as_str(), eth.szi.as_str()), ("ETH", "-0.5"));
+        assert!(matches!(eth.status, Ok(ExchangeDataStatus::Filled(_))));
+        let requests = requests.lock().unwrap();
+        let order = requests
+            .iter()
+            .find(|body| body.contains(r#""type":"order""#))
+            .unwrap();
+        // Buys back the short, reduce-only at 1% above the mid
+        assert!(order.contains(r#""b":true,"p":"2020","s":"0.5","r":true"#));
+        assert!(order.contains(r#""tif":"Ioc""#));
+        Ok(())
+    }
+}
@@ -0,0 +1,256 @@
+use crate::{consts::EPSILON, prelude::*, Error, L2BookData, L2SnapshotResponse, Px, Sz};
+
+/// Resting liquidity of one asset, best price first on each side.
+///
+/// Built from an [`InfoClient::l2_snapshot`](crate::InfoClient::l2_snapshot) response or from
+/// the `L2Book` messages of a live subscription.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BookDepth {
+    pub bids: Vec<(Px, Sz)>,
+    pub asks: Vec<(Px, Sz)>,
+}
+
+/// Expected execution of a market order against a [`BookDepth`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FillEstimate {
+    /// Price slippage is measured from: the mid, or the price passed with the order
+    pub reference_px: f64,
+    /// Size weighted average price of the levels the order consumes
+    pub avg_px: f64,
+    /// Price of the last level the order reaches, used as its limit price
+    pub worst_px: Px,
+    /// Number of levels the order consumes
+    pub levels: usize,
+    /// Relative distance of `avg_px` from `reference_px`, positive when the fill is worse
+    pub price_impact: f64,
+}
+
+impl FillEstimate {
+    pub fn price_impact_bps(&self) -> f64 {
+        self.price_impact * 10_000.0
+    }
+}
+
+fn parse_side<'a>(levels: impl Iterator<Item = (&'a str, &'a str)>) -> Result<Vec<(Px, Sz)>> {
+    levels
+        .map(|(px, sz)| Ok((px.parse()?, sz.parse()?)))
+        .collect()
+}
+
+impl TryFrom<&L2SnapshotResponse> for BookDepth {
+    type Error = Error;
+
+    fn try_from(snapshot: &L2SnapshotResponse) -> Result<BookDepth> {
+        BookDepth::from_levels(&snapshot.levels, |level| (&level.px, &level.sz))
+    }
+}
+
+impl TryFrom<&L2BookData> for BookDepth {
+    type Error = Error;
+
+    fn try_from(book: &L2BookData) -> Result<BookDepth> {
+        BookDepth::from_levels(&book.levels, |level| (&level.px, &level.sz))
+    }
+}
+
+impl BookDepth {
+    /// Parses the `[bids, asks]` levels of a snapshot or subscription message, whose level
+    /// types differ only in name.
+    fn from_levels<L>(
+        levels: &[Vec<L>],
+        px_sz: impl Fn(&L) -> (&String, &String),
+    ) -> Result<BookDepth> {
+        let side = |index: usize| {
+            parse_side(levels.get(index).into_iter().flatten().map(|level| {
+                let (px, sz) = px_sz(level);
+                (px.as_str(), sz.as_str())
+            }))
+        };
+        Ok(BookDepth {
+            bids: side(0)?,
+            asks: side(1)?,
+        })
+    }
+
+    pub fn mid_px(&self) -> Option<f64> {
+        let (best_bid, _) = self.bids.first()?;
+        let (best_ask, _) = self.asks.first()?;
+        Some((best_bid.to_f64() + best_ask.to_f64()) / 2.0)
+    }
+
+    /// Walks the opposite side of the book for an order of `sz`, consuming only levels
+    /// within `slippage` of `reference_px`, or of the mid if `None`. Fails with
+    /// [`Error::InsufficientLiquidity`] if those levels can't fill the whole size.
+    pub fn estimate_fill(
+        &self,
+        is_buy: bool,
+        sz: Sz,
+        reference_px: Option<f64>,
+        slippage: f64,
+    ) -> Result<FillEstimate> {
+        let requested = sz.to_f64();
+        let thin = |available: f64| Error::InsufficientLiquidity {
+            requested,
+            available,
+        };
+
+        let reference_px = reference_px.or_else(|| self.mid_px()).ok_or(thin(0.0))?;
+        let (side, limit_px) = if is_buy {
+            (&self.asks, reference_px * (1.0 + slippage))
+        } else {
+            (&self.bids, reference_px * (1.0 - slippage))
+        };
+
+        let mut remaining = requested;
+        let mut notional = 0.0;
+        let mut worst_px = None;
+        let mut levels = 0;
+        for (px, level_sz) in side {
+            if remaining <= EPSILON {
+                break;
+            }
+            let level_px = px.to_f64();
+            let within_slippage = if is_buy {
+                level_px <= limit_px
+            } else {
+                level_px >= limit_px
+            };
+            if !within_slippage {
+                break;
+            }
+
+            let take = remaining.min(level_sz.to_f64());
+            notional += take * level_px;
+            remaining -= take;
+            worst_px = Some(*px);
+            levels += 1;
+        }
+
+        let worst_px = match worst_px {
+            Some(worst_px) if remaining <= EPSILON => worst_px,
+            _ => return Err(thin(requested - remaining)),
+        };
+        let avg_px = notional / requested;
+        let price_impact = if is_buy {
+            avg_px / reference_px - 1.0
+        } else {
+            1.0 - avg_px / reference_px
+        };
+
+        Ok(FillEstimate {
+            reference_px,
+            avg_px,
+            worst_px,
+            levels,
+            price_impact,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_server, MarketCloseParams};
+    use ethers::types::H160;
+    use std::sync::{Arc, Mutex};
+
+    fn book() -> BookDepth {
+        let snapshot: L2SnapshotResponse = serde_json::from_str(
+            r#"{
+                "coin": "ETH",
+                "time": 1583838,
+                "levels": [
+                    [{"px":"1999","sz":"1","n":1},{"px":"1998","sz":"2","n":2},{"px":"1990","sz":"10","n":3}],
+                    [{"px":"2001","sz":"1","n":1},{"px":"2002","sz":"2","n":1},{"px":"2010","sz":"10","n":4}]
+                ]
+            }"#,
+        )
+        .unwrap();
+        BookDepth::try_from(&snapshot).unwrap()
+    }
+
+    #[test]
+    fn test_estimate_walks_levels() -> Result<()> {
+        let book = book();
+        assert_eq!(book.mid_px(), Some(2000.0));
+
+        let buy = book.estimate_fill(true, "2".parse()?, None, 0.01)?;
+        assert_eq!(buy.worst_px, "2002".parse()?);
+        assert_eq!(buy.levels, 2);
+        assert!((buy.avg_px - 2001.5).abs() < 1e-9);
+        assert!((buy.price_impact_bps() - 7.5).abs() < 1e-6);
+
+        let sell = book.estimate_fill(false, "0.5".parse()?, None, 0.01)?;
+        assert_eq!(sell.worst_px, "1999".parse()?);
+        assert!((sell.price_impact_bps() - 5.0).abs() < 1e-6);
+        Ok(())
+    }
+
+    #[test]
+    fn test_estimate_fails_on_thin_book() -> Result<()> {
+        let book = book();
+
+        // The 2010 level is beyond 0.15% slippage, leaving only 3 within reach
+        match book.estimate_fill(true, "5".parse()?, None, 0.0015) {
+            Err(Error::InsufficientLiquidity {
+                requested,
+                available,
+            }) => {
+                assert_eq!(requested, 5.0);
+                assert_eq!(available, 3.0);
+            }
+            other => panic!("expected insufficient liquidity, got {other:?}"),
+        }
+        assert!(book.estimate_fill(true, "5".parse()?, None, 0.01).is_ok());
+        assert!(BookDepth::default()
+            .estimate_fill(false, "1".parse()?, None, 0.01)
+            .is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_close_with_book_uses_vault_position() -> Result<()> {
+        let vault = H160::from_low_u64_be(9);
+        let orders = Arc::new(Mutex::new(Vec::new()));
+        let posted = orders.clone();
+        let meta = r#"{"universe":[{"name":"ETH","szDecimals":4}]}"#;
+        let base_url =
+            test_server::serve_exchange(meta, test_server::EMPTY_SPOT_META, move |path, body| {
+                if path == "/exchange" {
+                    posted.lock().unwrap().push(body.to_string());
+                    return r#"{"status":"ok","response":{"type":"order","data":{"statuses":[{"filled":{"totalSz":"1.5","avgPx":"1998.67","oid":1}}]}}}"#.to_string();
+                }
+                // Only the vault holds a position
+                let positions = if body.contains(&format!("{vault:?}")) {
+                    r#"{"type":"oneWay","position":{"coin":"ETH","entryPx":"2000","leverage":{"type":"cross","value":20},"liquidationPx":null,"marginUsed":"150","positionValue":"3000","returnOnEquity":"0","szi":"1.5","unrealizedPnl":"0","maxLeverage":50,"cumFunding":{"allTime":"0","sinceOpen":"0","sinceChange":"0"}}}"#
+                } else {
+                    ""
+                };
+                format!(
+                    r#"{{"assetPositions":[{positions}],"crossMarginSummary":{{"accountValue":"0","totalMarginUsed":"0","totalNtlPos":"0","totalRawUsd":"0"}},"marginSummary":{{"accountValue":"0","totalMarginUsed":"0","totalNtlPos":"0","totalRawUsd":"0"}},"withdrawable":"0"}}"#
+                )
+            })
+            .await;
+        let client = test_server::test_client(base_url)
+            .await?
+            .with_vault_address(Some(vault));
+
+        let params = MarketCloseParams {
+            asset: "ETH",
+            sz: None,
+            px: None,
+            slippage: Some(0.01),
+            cloid: None,
+            wallet: None,
+        };
+        let (_, estimate) = client.market_close_with_book(params, Some(&book())).await?;
+
+        // Selling the vault's 1.5 reaches the second bid
+        assert_eq!(estimate.worst_px, "1998".parse()?);
+        let orders = orders.lock().unwrap();
+        assert_eq!(orders.len(), 1);
+        assert!(orders[0].contains(r#""b":false,"p":"1998","s":"1.5","r":true"#));
+        assert!(orders[0].contains(&format!(r#""vaultAddress":"{vault:?}""#)));
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+use crate::{
+    prelude::*, ClientCancelRequest, ClientOrderRequest, Error, ExchangeClient, ExchangeDataStatus,
+    ExchangeResponseStatus, HyperliquidSigner,
+};
+
+/// Most orders or cancels sent in one action when a call splits a longer list into several
+pub const MAX_BULK_ACTION_SIZE: usize = 50;
+
+/// Status at each of `len` positions of an action answered with `response`.
+pub(crate) fn statuses(
+    response: Result<ExchangeResponseStatus>,
+    len: usize,
+) -> Vec<Result<ExchangeDataStatus>> {
+    match response {
+        Ok(ExchangeResponseStatus::Ok(response)) => {
+            let mut statuses = response
+                .data
+                .map(|data| data.statuses)
+                .unwrap_or_default()
+                .into_iter();
+            (0..len)
+                .map(|_| {
+                    statuses.next().ok_or_else(|| {
+                        Error::GenericRequest("No status returned for request".to_string())
+                    })
+                })
+                .collect()
+        }
+        Ok(ExchangeResponseStatus::Err(e)) => vec![Ok(ExchangeDataStatus::Error(e)); len],
+        Err(e) => vec![Err(e); len],
+    }
+}
+
+impl ExchangeClient {
+    /// Places `orders` with [`bulk_order`](ExchangeClient::bulk_order) in actions of at most
+    /// [`MAX_BULK_ACTION_SIZE`], returning the status of each order in the same order.
+    ///
+    /// An action failing doesn't stop the remaining ones; its orders report the error instead.
+    pub(crate) async fn bulk_order_chunked(
+        &self,
+        mut orders: Vec<ClientOrderRequest>,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Vec<Result<ExchangeDataStatus>> {
+        let mut results = Vec::with_capacity(orders.len());
+        while !orders.is_empty() {
+            let chunk: Vec<_> = orders
+                .drain(..orders.len().min(MAX_BULK_ACTION_SIZE))
+                .collect();
+            let len = chunk.len();
+            results.extend(statuses(self.bulk_order(chunk, wallet).await, len));
+        }
+        results
+    }
+
+    /// Like [`bulk_order_chunked`](ExchangeClient::bulk_order_chunked), for
+    /// [`bulk_cancel`](ExchangeClient::bulk_cancel).
+    pub(crate) async fn bulk_cancel_chunked(
+        &self,
+        mut cancels: Vec<ClientCancelRequest>,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Vec<Result<ExchangeDataStatus>> {
+        let mut results = Vec::with_capacity(cancels.len());
+        while !cancels.is_empty() {
+            let chunk: Vec<_> = cancels
+                .drain(..cancels.len().min(MAX_BULK_ACTION_SIZE))
+                .collect();
+            let len = chunk.len();
+            results.extend(statuses(self.bulk_cancel(chunk, wallet).await, len));
+        }
+        results
+    }
+}
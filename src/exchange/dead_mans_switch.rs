@@ -0,0 +1,160 @@
+use crate::{helpers::now_timestamp_ms, prelude::*, Error, ExchangeClient, ExchangeResponseStatus};
+use log::{debug, error, info};
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::task::JoinHandle;
+
+/// Minimum distance into the future the exchange accepts for a scheduled cancel.
+const MIN_SCHEDULE_DELAY: Duration = Duration::from_secs(5);
+
+/// Keeps a `scheduleCancel` pushed into the future from a background task.
+///
+/// The strategy calls [`DeadMansSwitch::feed`] as it makes progress. Every `renew_interval`
+/// the scheduled cancel time is pushed to `now + timeout`, but only while the last feed is
+/// at most `timeout` old. If the strategy stops feeding, the process hangs or is killed, the
+/// handle is dropped or [`DeadMansSwitch::stall`] is called, renewals stop and the exchange
+/// cancels all open orders once the last scheduled time passes.
+#[derive(Debug)]
+pub struct DeadMansSwitch {
+    exchange_client: ExchangeClient,
+    last_feed: Arc<AtomicU64>,
+    task: JoinHandle<()>,
+}
+
+impl DeadMansSwitch {
+    /// Starts renewing on the current tokio runtime, counting the start as the first feed.
+    pub fn start(
+        exchange_client: ExchangeClient,
+        timeout: Duration,
+        renew_interval: Duration,
+    ) -> Result<DeadMansSwitch> {
+        if timeout < MIN_SCHEDULE_DELAY {
+            return Err(Error::GenericRequest(format!(
+                "Dead man's switch timeout must be at least {MIN_SCHEDULE_DELAY:?}"
+            )));
+        }
+        if renew_interval >= timeout {
+            return Err(Error::GenericRequest(
+                "Dead man's switch renew interval must be shorter than the timeout".to_string(),
+            ));
+        }
+
+        let last_feed = Arc::new(AtomicU64::new(now_timestamp_ms()));
+        let client = exchange_client.clone();
+        let fed = last_feed.clone();
+        let task = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(renew_interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            let mut stalled = false;
+            loop {
+                interval.tick().await;
+                let now = now_timestamp_ms();
+                let since_feed = now.saturating_sub(fed.load(Ordering::SeqCst));
+                if since_feed > timeout.as_millis() as u64 {
+                    if stalled {
+                        debug!("No heartbeat for {since_feed}ms");
+                    } else {
+                        error!(
+                            "No heartbeat for {since_feed}ms, letting the scheduled cancel fire"
+                        );
+                        stalled = true;
+                    }
+                    continue;
+                }
+                if stalled {
+                    info!("Heartbeat resumed, renewing the scheduled cancel");
+                    stalled = false;
+                }
+                let time = now + timeout.as_millis() as u64;
+                match client.schedule_cancel(Some(time), None).await {
+                    Ok(ExchangeResponseStatus::Ok(_)) => {
+                        debug!("Scheduled cancel pushed to {time}")
+                    }
+                    Ok(ExchangeResponseStatus::Err(e)) => {
+                        error!("Error renewing scheduled cancel: {e}")
+                    }
+                    Err(e) => error!("Error renewing scheduled cancel: {e}"),
+                }
+            }
+        });
+
+        Ok(DeadMansSwitch {
+            exchange_client,
+            last_feed,
+            task,
+        })
+    }
+
+    /// Records that the strategy is still making progress, keeping renewals going for
+    /// another `timeout`.
+    pub fn feed(&self) {
+        self.last_feed.store(now_timestamp_ms(), Ordering::SeqCst);
+    }
+
+    /// Stops renewing the scheduled cancel, letting it fire at the last scheduled time.
+    pub fn stall(&self) {
+        self.task.abort();
+    }
+
+    pub fn is_armed(&self) -> bool {
+        !self.task.is_finished()
+    }
+
+    /// Stops renewing and removes the scheduled cancel, for a clean shutdown that should
+    /// leave open orders in place.
+    pub async fn disarm(self) -> Result<ExchangeResponseStatus> {
+        self.task.abort();
+        self.exchange_client.schedule_cancel(None, None).await
+    }
+}
+
+impl Drop for DeadMansSwitch {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_server;
+
+    #[tokio::test]
+    async fn test_renews_only_while_fed() -> Result<()> {
+        let renewals = Arc::new(AtomicU64::new(0));
+        let counted = renewals.clone();
+        let meta = r#"{"universe":[{"name":"ETH","szDecimals":4}]}"#;
+        let base_url =
+            test_server::serve_exchange(meta, test_server::EMPTY_SPOT_META, move |_, body| {
+                assert!(body.contains(r#""type":"scheduleCancel""#));
+                counted.fetch_add(1, Ordering::SeqCst);
+                r#"{"status":"ok","response":{"type":"default"}}"#.to_string()
+            })
+            .await;
+        let client = test_server::test_client(base_url).await?;
+        let switch = DeadMansSwitch::start(client, MIN_SCHEDULE_DELAY, Duration::from_millis(10))?;
+        let settle = Duration::from_millis(100);
+
+        tokio::time::sleep(settle).await;
+        assert!(renewals.load(Ordering::SeqCst) > 0);
+
+        // Last fed longer than the timeout ago, as if the strategy hung
+        let stale = now_timestamp_ms() - MIN_SCHEDULE_DELAY.as_millis() as u64 - 1;
+        switch.last_feed.store(stale, Ordering::SeqCst);
+        tokio::time::sleep(settle).await;
+        let stalled = renewals.load(Ordering::SeqCst);
+        tokio::time::sleep(settle).await;
+        assert_eq!(renewals.load(Ordering::SeqCst), stalled);
+        assert!(switch.is_armed());
+
+        switch.feed();
+        tokio::time::sleep(settle).await;
+        assert!(renewals.load(Ordering::SeqCst) > stalled);
+        Ok(())
+    }
+}
@@ -3,32 +3,42 @@ use crate::{
     exchange::{
         actions::{
             ApproveAgent, ApproveBuilderFee, BulkCancel, BulkModify, BulkModifyCloid, BulkOrder,
-            SetReferrer, UpdateIsolatedMargin, UpdateLeverage, UsdSend,
+            CreateSubAccount, CreateVault, ScheduleCancel, SetReferrer, SubAccountSpotTransfer,
+            SubAccountUsdTransfer, TwapCancel, TwapOrder, UpdateIsolatedMargin, UpdateLeverage,
+            UsdSend, VaultDistribute, VaultModify,
         },
         cancel::{CancelRequest, CancelRequestCloid},
         modify::{
             ClientModifyRequest, ClientModifyRequestCloid, ModifyRequest, ModifyRequestCloid,
         },
-        ClientCancelRequest, ClientOrderRequest,
+        order::OrderRequest,
+        retry::placed_order_response,
+        ActionExpiry, BookDepth, ClientBracketOrder, ClientCancelRequest, ClientOrderRequest,
+        ClientTwapRequest, FillEstimate, OrderGrouping, OrderRejectReason, OrderValidator,
+        RetryPolicy, ValidationMode,
     },
-    helpers::{generate_random_key, next_nonce, uuid_to_hex_string},
+    helpers::{generate_random_key, uuid_to_hex_string},
     info::info_client::InfoClient,
     meta::Meta,
     prelude::*,
     req::HttpClient,
     signature::sign_l1_action,
-    BaseUrl, BulkCancelCloid, Error, ExchangeResponseStatus,
+    AssetResolver, BaseUrl, BracketOrderStatus, BulkCancelCloid, CreateSubAccountResponseStatus,
+    CreateVaultResponseStatus, Error, ExchangeResponseStatus, HyperliquidSigner, MetaCache,
+    NonceManager, Px, RateLimiter, SignedAction, Sz, TwapResponseStatus,
+};
+use crate::{
+    CDeposit, CWithdraw, ClassTransfer, SpotSend, SpotUser, TokenDelegate, VaultTransfer, Withdraw3,
 };
-use crate::{ClassTransfer, SpotSend, SpotUser, VaultTransfer, Withdraw3};
 use ethers::{
     abi::AbiEncode,
     signers::{LocalWallet, Signer},
     types::{Signature, H160, H256},
 };
-use log::debug;
+use log::{debug, warn};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, sync::Arc};
+use std::sync::Arc;
 
 use super::cancel::ClientCancelRequestCloid;
 use super::order::{MarketCloseParams, MarketOrderParams};
@@ -37,25 +47,18 @@ use super::{BuilderInfo, ClientLimit, ClientOrder};
 #[derive(Debug)]
 struct ExchangeClientInner {
     http_client: HttpClient<'static>,
-    wallet: LocalWallet,
-    #[allow(dead_code)]
-    meta: Meta,
-    vault_address: Option<H160>,
-    coin_to_asset: HashMap<String, u32>,
+    signer: Arc<dyn HyperliquidSigner>,
 }
 
 #[derive(Debug, Clone)]
 pub struct ExchangeClient {
     inner: Arc<ExchangeClientInner>,
-}
-
-#[derive(Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct ExchangePayload {
-    action: serde_json::Value,
-    signature: Signature,
-    nonce: u64,
+    meta: MetaCache,
     vault_address: Option<H160>,
+    order_validation: Option<ValidationMode>,
+    nonces: NonceManager,
+    expiry: Option<ActionExpiry>,
+    retry: Option<RetryPolicy>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -77,10 +80,29 @@ pub enum Actions {
     SpotSend(SpotSend),
     SetReferrer(SetReferrer),
     ApproveBuilderFee(ApproveBuilderFee),
+    TwapOrder(TwapOrder),
+    TwapCancel(TwapCancel),
+    ScheduleCancel(ScheduleCancel),
+    CreateSubAccount(CreateSubAccount),
+    SubAccountTransfer(SubAccountUsdTransfer),
+    SubAccountSpotTransfer(SubAccountSpotTransfer),
+    CreateVault(CreateVault),
+    VaultModify(VaultModify),
+    VaultDistribute(VaultDistribute),
+    CDeposit(CDeposit),
+    CWithdraw(CWithdraw),
+    TokenDelegate(TokenDelegate),
 }
 
 impl Actions {
-    fn hash(&self, timestamp: u64, vault_address: Option<H160>) -> Result<H256> {
+    /// Connection id an L1 action is signed under: the keccak hash of the msgpack encoded
+    /// action followed by the nonce, the vault address and the `expiresAfter` time.
+    pub fn hash(
+        &self,
+        timestamp: u64,
+        vault_address: Option<H160>,
+        expires_after: Option<u64>,
+    ) -> Result<H256> {
         let mut bytes =
             rmp_serde::to_vec_named(self).map_err(|e| Error::RmpParse(e.to_string()))?;
         bytes.extend(timestamp.to_be_bytes());
@@ -90,159 +112,365 @@ impl Actions {
         } else {
             bytes.push(0);
         }
+        if let Some(expires_after) = expires_after {
+            bytes.push(0);
+            bytes.extend(expires_after.to_be_bytes());
+        }
         Ok(H256(ethers::utils::keccak256(bytes)))
     }
+
+    /// Whether the action is signed as EIP-712 typed data rather than under its
+    /// [`hash`](Actions::hash). Such actions carry their own nonce and can't expire.
+    pub(crate) fn is_user_signed(&self) -> bool {
+        matches!(
+            self,
+            Actions::UsdSend(_)
+                | Actions::Withdraw3(_)
+                | Actions::SpotSend(_)
+                | Actions::ApproveAgent(_)
+                | Actions::CDeposit(_)
+                | Actions::CWithdraw(_)
+                | Actions::TokenDelegate(_)
+        )
+    }
 }
 
 impl ExchangeClient {
-    pub async fn new(
-        wallet: LocalWallet,
+    pub async fn new<S: HyperliquidSigner + 'static>(
+        wallet: S,
         base_url: Option<BaseUrl>,
         meta: Option<Meta>,
         vault_address: Option<H160>,
     ) -> Result<ExchangeClient> {
         let base_url = base_url.unwrap_or(BaseUrl::Mainnet);
-
         let info = InfoClient::new(Some(base_url)).await?;
-        let meta = if let Some(meta) = meta {
-            meta
-        } else {
-            info.meta().await?
-        };
+        let meta_cache = MetaCache::from_info_client(info, meta).await?;
 
-        let mut coin_to_asset = HashMap::new();
-        for (asset_ind, asset) in meta.universe.iter().enumerate() {
-            coin_to_asset.insert(asset.name.clone(), asset_ind as u32);
-        }
-
-        coin_to_asset = info
-            .spot_meta()
-            .await?
-            .add_pair_and_name_to_index_map(coin_to_asset);
-
-        Ok(ExchangeClient {
-            inner: Arc::new(ExchangeClientInner {
-                wallet,
-                meta,
-                vault_address,
-                http_client: HttpClient::new(base_url.get_url()),
-                coin_to_asset,
-            }),
-        })
+        Ok(ExchangeClient::from_meta_cache(
+            wallet,
+            meta_cache,
+            vault_address,
+        ))
     }
 
-    pub async fn with_client(
+    pub async fn with_client<S: HyperliquidSigner + 'static>(
         client: &'static Client,
-        wallet: LocalWallet,
+        wallet: S,
         base_url: Option<BaseUrl>,
         meta: Option<Meta>,
         vault_address: Option<H160>,
     ) -> Result<ExchangeClient> {
         let base_url = base_url.unwrap_or(BaseUrl::Mainnet);
+        let info = InfoClient::with_client(client, Some(base_url)).await?;
+        let meta_cache = MetaCache::from_info_client(info, meta).await?;
 
-        let info = InfoClient::new(Some(base_url)).await?;
-        let meta = if let Some(meta) = meta {
-            meta
-        } else {
-            info.meta().await?
+        Ok(ExchangeClient::from_meta_cache(
+            wallet,
+            meta_cache,
+            vault_address,
+        ))
+    }
+
+    /// Client using an existing [`MetaCache`], and its connection and base URL, so that
+    /// several clients share one copy of the metadata and its refreshes.
+    pub fn from_meta_cache<S: HyperliquidSigner + 'static>(
+        wallet: S,
+        meta_cache: MetaCache,
+        vault_address: Option<H160>,
+    ) -> ExchangeClient {
+        let http_client = HttpClient {
+            client: meta_cache.info_client().http_client.client,
+            base_url: meta_cache.base_url().clone(),
+            rate_limiter: meta_cache.info_client().http_client.rate_limiter.clone(),
         };
 
-        let mut coin_to_asset = HashMap::new();
-        for (asset_ind, asset) in meta.universe.iter().enumerate() {
-            coin_to_asset.insert(asset.name.clone(), asset_ind as u32);
+        ExchangeClient {
+            inner: Arc::new(ExchangeClientInner {
+                signer: Arc::new(wallet),
+                http_client,
+            }),
+            meta: meta_cache,
+            vault_address,
+            order_validation: None,
+            nonces: NonceManager::shared(),
+            expiry: None,
+            retry: None,
         }
+    }
 
-        coin_to_asset = info
-            .spot_meta()
-            .await?
-            .add_pair_and_name_to_index_map(coin_to_asset);
+    pub fn base_url(&self) -> &BaseUrl {
+        &self.inner.http_client.base_url
+    }
+
+    /// Latest metadata snapshot, see [`MetaCache::assets`].
+    pub fn assets(&self) -> Arc<AssetResolver> {
+        self.meta.assets()
+    }
+
+    pub fn meta_cache(&self) -> &MetaCache {
+        &self.meta
+    }
+
+    /// Signer used when a method is called without an explicit one
+    pub fn signer(&self) -> &dyn HyperliquidSigner {
+        self.inner.signer.as_ref()
+    }
+
+    /// Validator over the latest metadata snapshot.
+    pub fn order_validator(&self) -> OrderValidator {
+        OrderValidator::from_assets(self.assets())
+    }
+
+    /// Validation applied to orders before signing, see [`with_order_validation`].
+    ///
+    /// [`with_order_validation`]: ExchangeClient::with_order_validation
+    pub fn order_validation(&self) -> Option<ValidationMode> {
+        self.order_validation
+    }
+
+    /// Returns a client sharing this one's connection, wallet and metadata that acts on
+    /// behalf of `vault_address` instead, or on the wallet's own account if `None`.
+    pub fn with_vault_address(&self, vault_address: Option<H160>) -> ExchangeClient {
+        ExchangeClient {
+            vault_address,
+            ..self.clone()
+        }
+    }
+
+    /// Returns a client that checks orders and modifies against the asset's tick size, lot
+    /// size and minimum notional rules before signing them, or doesn't if `None`.
+    pub fn with_order_validation(&self, mode: Option<ValidationMode>) -> ExchangeClient {
+        ExchangeClient {
+            order_validation: mode,
+            ..self.clone()
+        }
+    }
+
+    /// Returns a client that takes its nonces from `nonces`. By default all clients in the
+    /// process share [`NonceManager::shared`], use a file-backed manager to share nonces
+    /// with other processes signing with the same key.
+    pub fn with_nonce_manager(&self, nonces: NonceManager) -> ExchangeClient {
+        ExchangeClient {
+            nonces,
+            ..self.clone()
+        }
+    }
+
+    pub fn nonce_manager(&self) -> &NonceManager {
+        &self.nonces
+    }
+
+    /// Returns a client whose L1 actions carry an `expiresAfter` time, so the exchange
+    /// rejects them with [`Error::ActionExpired`] instead of executing them late. Set it on a
+    /// long-lived client for every action, or on a temporary one for a single call.
+    pub fn with_expiry(&self, expiry: Option<ActionExpiry>) -> ExchangeClient {
+        ExchangeClient {
+            expiry,
+            ..self.clone()
+        }
+    }
 
-        Ok(ExchangeClient {
+    /// Returns a client whose requests are checked against `rate_limiter` first, or aren't if
+    /// `None`. Clients built from a [`MetaCache`] inherit the limiter of its info client.
+    pub fn with_rate_limiter(&self, rate_limiter: Option<RateLimiter>) -> ExchangeClient {
+        ExchangeClient {
             inner: Arc::new(ExchangeClientInner {
-                wallet,
-                meta,
-                vault_address,
                 http_client: HttpClient {
-                    client,
-                    base_url: base_url.get_url(),
+                    rate_limiter,
+                    ..self.inner.http_client.clone()
                 },
-                coin_to_asset,
+                signer: self.inner.signer.clone(),
             }),
-        })
+            ..self.clone()
+        }
     }
 
-    async fn post(
+    pub fn rate_limiter(&self) -> Option<&RateLimiter> {
+        self.inner.http_client.rate_limiter.as_ref()
+    }
+
+    /// Returns a client that resends actions after network failures and `5xx` responses as
+    /// `retry` allows, or doesn't if `None`. See [`RetryPolicy`] for how this avoids placing
+    /// an order twice.
+    pub fn with_retry_policy(&self, retry: Option<RetryPolicy>) -> ExchangeClient {
+        ExchangeClient {
+            retry,
+            ..self.clone()
+        }
+    }
+
+    fn expires_after(&self, nonce: u64) -> Option<u64> {
+        self.expiry.map(|expiry| expiry.expires_after(nonce))
+    }
+
+    async fn next_nonce(&self, signer: &dyn HyperliquidSigner) -> Result<u64> {
+        self.nonces.next_nonce(signer.address()).await
+    }
+
+    async fn convert_orders(&self, orders: Vec<ClientOrderRequest>) -> Result<Vec<OrderRequest>> {
+        let assets = self
+            .meta
+            .assets_for(orders.iter().map(|order| order.asset.as_str()))
+            .await?;
+        let orders = match self.order_validation {
+            Some(mode) => OrderValidator::from_assets(assets.clone()).apply(orders, mode)?,
+            None => orders,
+        };
+        orders
+            .into_iter()
+            .map(|order| order.convert(&assets))
+            .collect()
+    }
+
+    /// Returns a client that trades on behalf of the given sub-account of the wallet.
+    pub fn for_sub_account(&self, sub_account_user: H160) -> ExchangeClient {
+        self.with_vault_address(Some(sub_account_user))
+    }
+
+    /// Vault or sub-account the client acts on behalf of, if any.
+    pub fn vault_address(&self) -> Option<H160> {
+        self.vault_address
+    }
+
+    /// Account the client's actions apply to: the vault or sub-account if set, otherwise
+    /// the wallet's own.
+    pub fn account_address(&self) -> H160 {
+        self.vault_address
+            .unwrap_or_else(|| self.inner.signer.address())
+    }
+
+    /// Posts an action signed earlier, possibly on another machine, see [`SignedAction`].
+    ///
+    /// The nonce is registered with the client's [`NonceManager`] under the recovered
+    /// signer first, so submitting the same action twice fails with [`Error::NonceReused`].
+    pub async fn submit_signed(&self, signed: &SignedAction) -> Result<ExchangeResponseStatus> {
+        let signer = signed.recover_signer(self.inner.http_client.is_mainnet())?;
+        self.nonces.register(signer, signed.nonce).await?;
+        self.post_signed(signed).await
+    }
+
+    async fn post<T: for<'a> Deserialize<'a>>(
         &self,
-        action: serde_json::Value,
+        action: Actions,
         signature: Signature,
         nonce: u64,
-    ) -> Result<ExchangeResponseStatus> {
-        let exchange_payload = ExchangePayload {
+    ) -> Result<T> {
+        self.post_with_vault_address(action, signature, nonce, self.vault_address)
+            .await
+    }
+
+    async fn post_with_vault_address<T: for<'a> Deserialize<'a>>(
+        &self,
+        action: Actions,
+        signature: Signature,
+        nonce: u64,
+        vault_address: Option<H160>,
+    ) -> Result<T> {
+        let expires_after = if action.is_user_signed() {
+            None
+        } else {
+            self.expires_after(nonce)
+        };
+        self.post_signed(&SignedAction {
             action,
             signature,
             nonce,
-            vault_address: self.inner.vault_address,
-        };
-        let res = serde_json::to_string(&exchange_payload)
-            .map_err(|e| Error::JsonParse(e.to_string()))?;
+            vault_address,
+            expires_after,
+        })
+        .await
+    }
+
+    async fn post_signed<T: for<'a> Deserialize<'a>>(&self, signed: &SignedAction) -> Result<T> {
+        let res = signed.to_json()?;
         debug!("Sending request {res:?}");
 
-        let output = &self
-            .inner
-            .http_client
-            .post("/exchange", res)
-            .await
-            .map_err(|e| Error::JsonParse(e.to_string()))?;
+        let output = &self.send_signed(signed, res).await?;
+        if let Ok(ExchangeResponseStatus::Err(message)) = serde_json::from_str(output) {
+            if OrderRejectReason::parse(&message) == OrderRejectReason::Expired {
+                return Err(Error::ActionExpired(message));
+            }
+        }
         serde_json::from_str(output).map_err(|e| Error::JsonParse(e.to_string()))
     }
 
+    /// Posts `body`, the JSON of `signed`, resending it as the retry policy allows.
+    async fn send_signed(&self, signed: &SignedAction, body: String) -> Result<String> {
+        let http_client = &self.inner.http_client;
+        let Some(retry) = self.retry else {
+            return http_client.post("/exchange", body).await;
+        };
+
+        let mut attempt = 1;
+        loop {
+            let sent = http_client.post("/exchange", body.clone());
+            let result = match retry.timeout {
+                Some(timeout) => tokio::time::timeout(timeout, sent)
+                    .await
+                    .unwrap_or_else(|_| {
+                        Err(Error::GenericRequest(format!(
+                            "No response after {timeout:?}"
+                        )))
+                    }),
+                None => sent.await,
+            };
+            let error = match result {
+                Err(e) if attempt < retry.max_attempts && RetryPolicy::is_retryable(&e) => e,
+                result => return result,
+            };
+
+            let backoff = retry.backoff(attempt);
+            warn!("Exchange request failed, retrying in {backoff:?}: {error}");
+            tokio::time::sleep(backoff).await;
+            // Resending is safe either way since the nonce can only be used once, but orders
+            // placed by the failed attempt would only come back as a nonce error
+            let is_mainnet = http_client.is_mainnet();
+            match placed_order_response(self.meta.info_client(), signed, is_mainnet).await {
+                Ok(Some(output)) => return Ok(output),
+                Ok(None) => {}
+                Err(e) => warn!("Failed to look up orders by cloid, resending anyway: {e}"),
+            }
+            attempt += 1;
+        }
+    }
+
     pub async fn usdc_transfer(
         &self,
         amount: &str,
         destination: &str,
-        wallet: Option<&LocalWallet>,
+        wallet: Option<&dyn HyperliquidSigner>,
     ) -> Result<ExchangeResponseStatus> {
-        let wallet = wallet.unwrap_or(&self.inner.wallet);
-        let hyperliquid_chain = if self.inner.http_client.is_mainnet() {
-            "Mainnet".to_string()
-        } else {
-            "Testnet".to_string()
-        };
-
-        let timestamp = next_nonce();
-        let usd_send = UsdSend {
-            signature_chain_id: 421614.into(),
-            hyperliquid_chain,
-            destination: destination.to_string(),
-            amount: amount.to_string(),
-            time: timestamp,
-        };
-        let signature = sign_typed_data(&usd_send, wallet)?;
-        let action = serde_json::to_value(Actions::UsdSend(usd_send))
-            .map_err(|e| Error::JsonParse(e.to_string()))?;
-
-        self.post(action, signature, timestamp).await
+        let wallet = wallet.unwrap_or(self.inner.signer.as_ref());
+        let signed = SignedAction::usdc_transfer(
+            amount,
+            destination,
+            self.next_nonce(wallet).await?,
+            self.inner.http_client.is_mainnet(),
+            wallet,
+        )
+        .await?;
+        self.post_signed(&signed).await
     }
 
     pub async fn class_transfer(
         &self,
         usdc: f64,
         to_perp: bool,
-        wallet: Option<&LocalWallet>,
+        wallet: Option<&dyn HyperliquidSigner>,
     ) -> Result<ExchangeResponseStatus> {
         // payload expects usdc without decimals
         let usdc = (usdc * 1e6).round() as u64;
-        let wallet = wallet.unwrap_or(&self.inner.wallet);
+        let wallet = wallet.unwrap_or(self.inner.signer.as_ref());
 
-        let timestamp = next_nonce();
+        let timestamp = self.next_nonce(wallet).await?;
 
         let action = Actions::SpotUser(SpotUser {
             class_transfer: ClassTransfer { usdc, to_perp },
         });
-        let connection_id = action.hash(timestamp, self.inner.vault_address)?;
-        let action = serde_json::to_value(&action).map_err(|e| Error::JsonParse(e.to_string()))?;
+        let connection_id =
+            action.hash(timestamp, self.vault_address, self.expires_after(timestamp))?;
         let is_mainnet = self.inner.http_client.is_mainnet();
-        let signature = sign_l1_action(wallet, connection_id, is_mainnet)?;
+        let signature = sign_l1_action(wallet, connection_id, is_mainnet).await?;
 
         self.post(action, signature, timestamp).await
     }
@@ -252,30 +480,97 @@ impl ExchangeClient {
         is_deposit: bool,
         usd: u64,
         vault_address: Option<H160>,
-        wallet: Option<&LocalWallet>,
+        wallet: Option<&dyn HyperliquidSigner>,
     ) -> Result<ExchangeResponseStatus> {
         let vault_address = self
-            .inner
             .vault_address
             .or(vault_address)
             .ok_or(Error::VaultAddressNotFound)?;
-        let wallet = wallet.unwrap_or(&self.inner.wallet);
+        let wallet = wallet.unwrap_or(self.inner.signer.as_ref());
 
-        let timestamp = next_nonce();
+        let timestamp = self.next_nonce(wallet).await?;
 
         let action = Actions::VaultTransfer(VaultTransfer {
             vault_address,
             is_deposit,
             usd,
         });
-        let connection_id = action.hash(timestamp, self.inner.vault_address)?;
-        let action = serde_json::to_value(&action).map_err(|e| Error::JsonParse(e.to_string()))?;
+        let connection_id =
+            action.hash(timestamp, self.vault_address, self.expires_after(timestamp))?;
         let is_mainnet = self.inner.http_client.is_mainnet();
-        let signature = sign_l1_action(wallet, connection_id, is_mainnet)?;
+        let signature = sign_l1_action(wallet, connection_id, is_mainnet).await?;
 
         self.post(action, signature, timestamp).await
     }
 
+    /// Creates a vault led by the wallet, seeded with `initial_usd` (with 6 decimals).
+    pub async fn create_vault(
+        &self,
+        name: String,
+        description: String,
+        initial_usd: u64,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<CreateVaultResponseStatus> {
+        let wallet = wallet.unwrap_or(self.inner.signer.as_ref());
+        let timestamp = self.next_nonce(wallet).await?;
+
+        let action = Actions::CreateVault(CreateVault {
+            name,
+            description,
+            initial_usd,
+            nonce: timestamp,
+        });
+        let connection_id = action.hash(timestamp, None, self.expires_after(timestamp))?;
+
+        let is_mainnet = self.inner.http_client.is_mainnet();
+        let signature = sign_l1_action(wallet, connection_id, is_mainnet).await?;
+        self.post_with_vault_address(action, signature, timestamp, None)
+            .await
+    }
+
+    /// Updates vault settings as its leader. Settings passed as `None` are left unchanged.
+    pub async fn vault_modify(
+        &self,
+        vault_address: H160,
+        allow_deposits: Option<bool>,
+        always_close_on_withdraw: Option<bool>,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        let wallet = wallet.unwrap_or(self.inner.signer.as_ref());
+        let timestamp = self.next_nonce(wallet).await?;
+
+        let action = Actions::VaultModify(VaultModify {
+            vault_address,
+            allow_deposits,
+            always_close_on_withdraw,
+        });
+        let connection_id = action.hash(timestamp, None, self.expires_after(timestamp))?;
+
+        let is_mainnet = self.inner.http_client.is_mainnet();
+        let signature = sign_l1_action(wallet, connection_id, is_mainnet).await?;
+        self.post_with_vault_address(action, signature, timestamp, None)
+            .await
+    }
+
+    /// Distributes `usd` (with 6 decimals) from the vault to its followers pro rata.
+    pub async fn vault_distribute(
+        &self,
+        vault_address: H160,
+        usd: u64,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        let wallet = wallet.unwrap_or(self.inner.signer.as_ref());
+        let timestamp = self.next_nonce(wallet).await?;
+
+        let action = Actions::VaultDistribute(VaultDistribute { vault_address, usd });
+        let connection_id = action.hash(timestamp, None, self.expires_after(timestamp))?;
+
+        let is_mainnet = self.inner.http_client.is_mainnet();
+        let signature = sign_l1_action(wallet, connection_id, is_mainnet).await?;
+        self.post_with_vault_address(action, signature, timestamp, None)
+            .await
+    }
+
     pub async fn market_open(
         &self,
         params: MarketOrderParams<'_>,
@@ -290,7 +585,7 @@ impl ExchangeClient {
             is_buy: params.is_buy,
             reduce_only: false,
             limit_px: px,
-            sz: round_to_decimals(params.sz, sz_decimals),
+            sz: params.sz.round_to_decimals(sz_decimals),
             cloid: params.cloid,
             order_type: ClientOrder::Limit(ClientLimit {
                 tif: "Ioc".to_string(),
@@ -315,7 +610,7 @@ impl ExchangeClient {
             is_buy: params.is_buy,
             reduce_only: false,
             limit_px: px,
-            sz: round_to_decimals(params.sz, sz_decimals),
+            sz: params.sz.round_to_decimals(sz_decimals),
             cloid: params.cloid,
             order_type: ClientOrder::Limit(ClientLimit {
                 tif: "Ioc".to_string(),
@@ -330,37 +625,22 @@ impl ExchangeClient {
         params: MarketCloseParams<'_>,
     ) -> Result<ExchangeResponseStatus> {
         let slippage = params.slippage.unwrap_or(0.05); // Default 5% slippage
-        let wallet = params.wallet.unwrap_or(&self.inner.wallet);
-
-        let base_url = match self.inner.http_client.base_url.as_str() {
-            "https://api.hyperliquid.xyz" => BaseUrl::Mainnet,
-            "https://api.hyperliquid-testnet.xyz" => BaseUrl::Testnet,
-            _ => return Err(Error::GenericRequest("Invalid base URL".to_string())),
-        };
-        let info_client = InfoClient::new(Some(base_url)).await?;
-        let user_state = info_client.user_state(wallet.address()).await?;
-
-        let position = user_state
-            .asset_positions
-            .iter()
-            .find(|p| p.position.coin == params.asset)
-            .ok_or(Error::AssetNotFound)?;
+        let wallet = params.wallet.unwrap_or(self.inner.signer.as_ref());
 
-        let szi = position
-            .position
-            .szi
-            .parse::<f64>()
-            .map_err(|_| Error::FloatStringParse)?;
+        let (is_short, position_sz) = self.open_position(params.asset).await?;
 
         let (px, sz_decimals) = self
-            .calculate_slippage_price(params.asset, szi < 0.0, slippage, params.px)
+            .calculate_slippage_price(params.asset, is_short, slippage, params.px)
             .await?;
 
-        let sz = round_to_decimals(params.sz.unwrap_or_else(|| szi.abs()), sz_decimals);
+        let sz = params
+            .sz
+            .unwrap_or(position_sz)
+            .round_to_decimals(sz_decimals);
 
         let order = ClientOrderRequest {
             asset: params.asset.to_string(),
-            is_buy: szi < 0.0,
+            is_buy: is_short,
             reduce_only: true,
             limit_px: px,
             sz,
@@ -373,41 +653,150 @@ impl ExchangeClient {
         self.order(order, Some(wallet)).await
     }
 
-    async fn calculate_slippage_price(
+    /// Like [`market_open`](ExchangeClient::market_open), but sets the limit price to the
+    /// worst level of `book`, or of a fresh `l2_snapshot` if `None`, the order needs to fill.
+    /// Only levels within the slippage are considered, if they can't fill the size the order
+    /// isn't sent and [`Error::InsufficientLiquidity`] is returned. The estimate is returned
+    /// with the response to compare against the actual fills.
+    pub async fn market_open_with_book(
         &self,
-        asset: &str,
-        is_buy: bool,
-        slippage: f64,
-        px: Option<f64>,
-    ) -> Result<(f64, u32)> {
-        let base_url = match self.inner.http_client.base_url.as_str() {
-            "https://api.hyperliquid.xyz" => BaseUrl::Mainnet,
-            "https://api.hyperliquid-testnet.xyz" => BaseUrl::Testnet,
-            _ => return Err(Error::GenericRequest("Invalid base URL".to_string())),
+        params: MarketOrderParams<'_>,
+        book: Option<&BookDepth>,
+    ) -> Result<(ExchangeResponseStatus, FillEstimate)> {
+        let slippage = params.slippage.unwrap_or(0.05); // Default 5% slippage
+        let (sz, estimate) = self
+            .estimate_market_fill(
+                params.asset,
+                params.is_buy,
+                params.sz,
+                slippage,
+                params.px,
+                book,
+            )
+            .await?;
+
+        let order = ClientOrderRequest {
+            asset: params.asset.to_string(),
+            is_buy: params.is_buy,
+            reduce_only: false,
+            limit_px: estimate.worst_px,
+            sz,
+            cloid: params.cloid,
+            order_type: ClientOrder::Limit(ClientLimit {
+                tif: "Ioc".to_string(),
+            }),
+        };
+
+        Ok((self.order(order, params.wallet).await?, estimate))
+    }
+
+    /// Like [`market_close`](ExchangeClient::market_close), priced from the book as in
+    /// [`market_open_with_book`](ExchangeClient::market_open_with_book).
+    pub async fn market_close_with_book(
+        &self,
+        params: MarketCloseParams<'_>,
+        book: Option<&BookDepth>,
+    ) -> Result<(ExchangeResponseStatus, FillEstimate)> {
+        let slippage = params.slippage.unwrap_or(0.05); // Default 5% slippage
+        let wallet = params.wallet.unwrap_or(self.inner.signer.as_ref());
+
+        let (is_short, position_sz) = self.open_position(params.asset).await?;
+        let (sz, estimate) = self
+            .estimate_market_fill(
+                params.asset,
+                is_short,
+                params.sz.unwrap_or(position_sz),
+                slippage,
+                params.px,
+                book,
+            )
+            .await?;
+
+        let order = ClientOrderRequest {
+            asset: params.asset.to_string(),
+            is_buy: is_short,
+            reduce_only: true,
+            limit_px: estimate.worst_px,
+            sz,
+            cloid: params.cloid,
+            order_type: ClientOrder::Limit(ClientLimit {
+                tif: "Ioc".to_string(),
+            }),
         };
-        let info_client = InfoClient::new(Some(base_url)).await?;
-        let meta = info_client.meta().await?;
 
-        let asset_meta = meta
-            .universe
+        Ok((self.order(order, Some(wallet)).await?, estimate))
+    }
+
+    /// Whether the position of the client's account in `asset` is short, and its size.
+    async fn open_position(&self, asset: &str) -> Result<(bool, Sz)> {
+        let user_state = self
+            .meta
+            .info_client()
+            .user_state(self.account_address())
+            .await?;
+
+        let position = user_state
+            .asset_positions
             .iter()
-            .find(|a| a.name == asset)
+            .find(|p| p.position.coin == asset)
             .ok_or(Error::AssetNotFound)?;
 
-        let sz_decimals = asset_meta.sz_decimals;
-        let max_decimals: u32 = if self.inner.coin_to_asset[asset] < 10000 {
-            6
-        } else {
-            8
+        let szi = position.position.szi.as_str();
+        Ok((szi.starts_with('-'), szi.trim_start_matches('-').parse()?))
+    }
+
+    /// Size rounded to the asset's lot size and its expected fill against the book.
+    async fn estimate_market_fill(
+        &self,
+        asset: &str,
+        is_buy: bool,
+        sz: Sz,
+        slippage: f64,
+        px: Option<Px>,
+        book: Option<&BookDepth>,
+    ) -> Result<(Sz, FillEstimate)> {
+        let assets = self.meta.assets_for([asset]).await?;
+        let asset_info = assets.resolve(asset)?;
+        let sz = sz.round_to_decimals(asset_info.sz_decimals);
+        let reference_px = px.map(|px| px.to_f64());
+
+        let estimate = match book {
+            Some(book) => book.estimate_fill(is_buy, sz, reference_px, slippage)?,
+            None => {
+                let snapshot = self
+                    .meta
+                    .info_client()
+                    .l2_snapshot(asset_info.coin.clone())
+                    .await?;
+                BookDepth::try_from(&snapshot)?.estimate_fill(is_buy, sz, reference_px, slippage)?
+            }
         };
-        let price_decimals = max_decimals.saturating_sub(sz_decimals);
+        debug!("Estimated fill for {sz} {asset}: {estimate:?}");
+        Ok((sz, estimate))
+    }
+
+    pub(crate) async fn calculate_slippage_price(
+        &self,
+        asset: &str,
+        is_buy: bool,
+        slippage: f64,
+        px: Option<Px>,
+    ) -> Result<(Px, u32)> {
+        let assets = self.meta.assets_for([asset]).await?;
+        let asset_info = assets.resolve(asset)?;
+
+        let sz_decimals = asset_info.sz_decimals;
+        let price_decimals = asset_info
+            .id
+            .max_price_decimals()
+            .saturating_sub(sz_decimals);
 
         let px = if let Some(px) = px {
-            px
+            px.to_f64()
         } else {
-            let all_mids = info_client.all_mids().await?;
+            let all_mids = self.meta.info_client().all_mids().await?;
             all_mids
-                .get(asset)
+                .get(&asset_info.coin)
                 .ok_or(Error::AssetNotFound)?
                 .parse::<f64>()
                 .map_err(|_| Error::FloatStringParse)?
@@ -419,10 +808,10 @@ impl ExchangeClient {
         } else {
             1.0 - slippage
         };
-        let px = px * slippage_factor;
+        let px = Px::try_from(px * slippage_factor)?;
 
         // Round to the correct number of decimal places and significant figures
-        let px = round_to_significant_and_decimal(px, 5, price_decimals);
+        let px = px.round_to_significant_and_decimal(5, price_decimals);
 
         debug!("px after slippage: {px:?}");
         Ok((px, sz_decimals))
@@ -431,7 +820,7 @@ impl ExchangeClient {
     pub async fn order(
         &self,
         order: ClientOrderRequest,
-        wallet: Option<&LocalWallet>,
+        wallet: Option<&dyn HyperliquidSigner>,
     ) -> Result<ExchangeResponseStatus> {
         self.bulk_order(vec![order], wallet).await
     }
@@ -439,7 +828,7 @@ impl ExchangeClient {
     pub async fn order_with_builder(
         &self,
         order: ClientOrderRequest,
-        wallet: Option<&LocalWallet>,
+        wallet: Option<&dyn HyperliquidSigner>,
         builder: BuilderInfo,
     ) -> Result<ExchangeResponseStatus> {
         self.bulk_order_with_builder(vec![order], wallet, builder)
@@ -449,64 +838,84 @@ impl ExchangeClient {
     pub async fn bulk_order(
         &self,
         orders: Vec<ClientOrderRequest>,
-        wallet: Option<&LocalWallet>,
+        wallet: Option<&dyn HyperliquidSigner>,
     ) -> Result<ExchangeResponseStatus> {
-        let wallet = wallet.unwrap_or(&self.inner.wallet);
-        let timestamp = next_nonce();
+        self.bulk_order_with_grouping(orders, OrderGrouping::Na, wallet)
+            .await
+    }
 
-        let mut transformed_orders = Vec::new();
+    pub async fn bracket_order(
+        &self,
+        bracket: ClientBracketOrder,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<BracketOrderStatus> {
+        let has_take_profit = bracket.take_profit.is_some();
+        let has_stop_loss = bracket.stop_loss.is_some();
+
+        let response = self
+            .bulk_order_with_grouping(bracket.into_orders(), OrderGrouping::NormalTpsl, wallet)
+            .await?;
+        Ok(BracketOrderStatus::from_response(
+            response,
+            has_take_profit,
+            has_stop_loss,
+        ))
+    }
 
-        for order in orders {
-            transformed_orders.push(order.convert(&self.inner.coin_to_asset)?);
-        }
+    pub async fn bulk_order_with_grouping(
+        &self,
+        orders: Vec<ClientOrderRequest>,
+        grouping: OrderGrouping,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        let wallet = wallet.unwrap_or(self.inner.signer.as_ref());
+        let timestamp = self.next_nonce(wallet).await?;
+
+        let transformed_orders = self.convert_orders(orders).await?;
 
         let action = Actions::Order(BulkOrder {
             orders: transformed_orders,
-            grouping: "na".to_string(),
+            grouping,
             builder: None,
         });
-        let connection_id = action.hash(timestamp, self.inner.vault_address)?;
-        let action = serde_json::to_value(&action).map_err(|e| Error::JsonParse(e.to_string()))?;
+        let connection_id =
+            action.hash(timestamp, self.vault_address, self.expires_after(timestamp))?;
 
         let is_mainnet = self.inner.http_client.is_mainnet();
-        let signature = sign_l1_action(wallet, connection_id, is_mainnet)?;
+        let signature = sign_l1_action(wallet, connection_id, is_mainnet).await?;
         self.post(action, signature, timestamp).await
     }
 
     pub async fn bulk_order_with_builder(
         &self,
         orders: Vec<ClientOrderRequest>,
-        wallet: Option<&LocalWallet>,
+        wallet: Option<&dyn HyperliquidSigner>,
         mut builder: BuilderInfo,
     ) -> Result<ExchangeResponseStatus> {
-        let wallet = wallet.unwrap_or(&self.inner.wallet);
-        let timestamp = next_nonce();
+        let wallet = wallet.unwrap_or(self.inner.signer.as_ref());
+        let timestamp = self.next_nonce(wallet).await?;
 
         builder.builder = builder.builder.to_lowercase();
 
-        let mut transformed_orders = Vec::new();
-
-        for order in orders {
-            transformed_orders.push(order.convert(&self.inner.coin_to_asset)?);
-        }
+        let transformed_orders = self.convert_orders(orders).await?;
 
         let action = Actions::Order(BulkOrder {
             orders: transformed_orders,
-            grouping: "na".to_string(),
+            grouping: OrderGrouping::Na,
             builder: Some(builder),
         });
-        let connection_id = action.hash(timestamp, self.inner.vault_address)?;
-        let action = serde_json::to_value(&action).map_err(|e| Error::JsonParse(e.to_string()))?;
+        let connection_id =
+            action.hash(timestamp, self.vault_address, self.expires_after(timestamp))?;
 
         let is_mainnet = self.inner.http_client.is_mainnet();
-        let signature = sign_l1_action(wallet, connection_id, is_mainnet)?;
+        let signature = sign_l1_action(wallet, connection_id, is_mainnet).await?;
         self.post(action, signature, timestamp).await
     }
 
     pub async fn cancel(
         &self,
         cancel: ClientCancelRequest,
-        wallet: Option<&LocalWallet>,
+        wallet: Option<&dyn HyperliquidSigner>,
     ) -> Result<ExchangeResponseStatus> {
         self.bulk_cancel(vec![cancel], wallet).await
     }
@@ -514,18 +923,18 @@ impl ExchangeClient {
     pub async fn bulk_cancel(
         &self,
         cancels: Vec<ClientCancelRequest>,
-        wallet: Option<&LocalWallet>,
+        wallet: Option<&dyn HyperliquidSigner>,
     ) -> Result<ExchangeResponseStatus> {
-        let wallet = wallet.unwrap_or(&self.inner.wallet);
-        let timestamp = next_nonce();
+        let wallet = wallet.unwrap_or(self.inner.signer.as_ref());
+        let timestamp = self.next_nonce(wallet).await?;
 
+        let assets = self
+            .meta
+            .assets_for(cancels.iter().map(|cancel| cancel.asset.as_str()))
+            .await?;
         let mut transformed_cancels = Vec::new();
         for cancel in cancels.into_iter() {
-            let &asset = self
-                .inner
-                .coin_to_asset
-                .get(&cancel.asset)
-                .ok_or(Error::AssetNotFound)?;
+            let asset = assets.asset_index(&cancel.asset)?;
             transformed_cancels.push(CancelRequest {
                 asset,
                 oid: cancel.oid,
@@ -535,11 +944,11 @@ impl ExchangeClient {
         let action = Actions::Cancel(BulkCancel {
             cancels: transformed_cancels,
         });
-        let connection_id = action.hash(timestamp, self.inner.vault_address)?;
+        let connection_id =
+            action.hash(timestamp, self.vault_address, self.expires_after(timestamp))?;
 
-        let action = serde_json::to_value(&action).map_err(|e| Error::JsonParse(e.to_string()))?;
         let is_mainnet = self.inner.http_client.is_mainnet();
-        let signature = sign_l1_action(wallet, connection_id, is_mainnet)?;
+        let signature = sign_l1_action(wallet, connection_id, is_mainnet).await?;
 
         self.post(action, signature, timestamp).await
     }
@@ -547,7 +956,7 @@ impl ExchangeClient {
     pub async fn modify(
         &self,
         modify: ClientModifyRequest,
-        wallet: Option<&LocalWallet>,
+        wallet: Option<&dyn HyperliquidSigner>,
     ) -> Result<ExchangeResponseStatus> {
         self.bulk_modify(vec![modify], wallet).await
     }
@@ -555,27 +964,29 @@ impl ExchangeClient {
     pub async fn bulk_modify(
         &self,
         modifies: Vec<ClientModifyRequest>,
-        wallet: Option<&LocalWallet>,
+        wallet: Option<&dyn HyperliquidSigner>,
     ) -> Result<ExchangeResponseStatus> {
-        let wallet = wallet.unwrap_or(&self.inner.wallet);
-        let timestamp = next_nonce();
-
-        let mut transformed_modifies = Vec::new();
-        for modify in modifies.into_iter() {
-            transformed_modifies.push(ModifyRequest {
-                oid: modify.oid,
-                order: modify.order.convert(&self.inner.coin_to_asset)?,
-            });
-        }
+        let wallet = wallet.unwrap_or(self.inner.signer.as_ref());
+        let timestamp = self.next_nonce(wallet).await?;
+
+        let (oids, orders): (Vec<_>, Vec<_>) = modifies
+            .into_iter()
+            .map(|modify| (modify.oid, modify.order))
+            .unzip();
+        let transformed_modifies = oids
+            .into_iter()
+            .zip(self.convert_orders(orders).await?)
+            .map(|(oid, order)| ModifyRequest { oid, order })
+            .collect();
 
         let action = Actions::BatchModify(BulkModify {
             modifies: transformed_modifies,
         });
-        let connection_id = action.hash(timestamp, self.inner.vault_address)?;
+        let connection_id =
+            action.hash(timestamp, self.vault_address, self.expires_after(timestamp))?;
 
-        let action = serde_json::to_value(&action).map_err(|e| Error::JsonParse(e.to_string()))?;
         let is_mainnet = self.inner.http_client.is_mainnet();
-        let signature = sign_l1_action(wallet, connection_id, is_mainnet)?;
+        let signature = sign_l1_action(wallet, connection_id, is_mainnet).await?;
 
         self.post(action, signature, timestamp).await
     }
@@ -583,7 +994,7 @@ impl ExchangeClient {
     pub async fn modify_by_cloid(
         &self,
         modify: ClientModifyRequestCloid,
-        wallet: Option<&LocalWallet>,
+        wallet: Option<&dyn HyperliquidSigner>,
     ) -> Result<ExchangeResponseStatus> {
         self.bulk_modify_by_cloid(vec![modify], wallet).await
     }
@@ -591,27 +1002,29 @@ impl ExchangeClient {
     pub async fn bulk_modify_by_cloid(
         &self,
         modifies: Vec<ClientModifyRequestCloid>,
-        wallet: Option<&LocalWallet>,
+        wallet: Option<&dyn HyperliquidSigner>,
     ) -> Result<ExchangeResponseStatus> {
-        let wallet = wallet.unwrap_or(&self.inner.wallet);
-        let timestamp = next_nonce();
-
-        let mut transformed_modifies = Vec::new();
-        for modify in modifies.into_iter() {
-            transformed_modifies.push(ModifyRequestCloid {
-                cloid: uuid_to_hex_string(modify.cloid),
-                order: modify.order.convert(&self.inner.coin_to_asset)?,
-            });
-        }
+        let wallet = wallet.unwrap_or(self.inner.signer.as_ref());
+        let timestamp = self.next_nonce(wallet).await?;
+
+        let (cloids, orders): (Vec<_>, Vec<_>) = modifies
+            .into_iter()
+            .map(|modify| (uuid_to_hex_string(modify.cloid), modify.order))
+            .unzip();
+        let transformed_modifies = cloids
+            .into_iter()
+            .zip(self.convert_orders(orders).await?)
+            .map(|(cloid, order)| ModifyRequestCloid { cloid, order })
+            .collect();
 
         let action = Actions::BatchModifyByCloid(BulkModifyCloid {
             modifies: transformed_modifies,
         });
-        let connection_id = action.hash(timestamp, self.inner.vault_address)?;
+        let connection_id =
+            action.hash(timestamp, self.vault_address, self.expires_after(timestamp))?;
 
-        let action = serde_json::to_value(&action).map_err(|e| Error::JsonParse(e.to_string()))?;
         let is_mainnet = self.inner.http_client.is_mainnet();
-        let signature = sign_l1_action(wallet, connection_id, is_mainnet)?;
+        let signature = sign_l1_action(wallet, connection_id, is_mainnet).await?;
 
         self.post(action, signature, timestamp).await
     }
@@ -619,7 +1032,7 @@ impl ExchangeClient {
     pub async fn cancel_by_cloid(
         &self,
         cancel: ClientCancelRequestCloid,
-        wallet: Option<&LocalWallet>,
+        wallet: Option<&dyn HyperliquidSigner>,
     ) -> Result<ExchangeResponseStatus> {
         self.bulk_cancel_by_cloid(vec![cancel], wallet).await
     }
@@ -627,18 +1040,18 @@ impl ExchangeClient {
     pub async fn bulk_cancel_by_cloid(
         &self,
         cancels: Vec<ClientCancelRequestCloid>,
-        wallet: Option<&LocalWallet>,
+        wallet: Option<&dyn HyperliquidSigner>,
     ) -> Result<ExchangeResponseStatus> {
-        let wallet = wallet.unwrap_or(&self.inner.wallet);
-        let timestamp = next_nonce();
+        let wallet = wallet.unwrap_or(self.inner.signer.as_ref());
+        let timestamp = self.next_nonce(wallet).await?;
 
+        let assets = self
+            .meta
+            .assets_for(cancels.iter().map(|cancel| cancel.asset.as_str()))
+            .await?;
         let mut transformed_cancels: Vec<CancelRequestCloid> = Vec::new();
         for cancel in cancels.into_iter() {
-            let &asset = self
-                .inner
-                .coin_to_asset
-                .get(&cancel.asset)
-                .ok_or(Error::AssetNotFound)?;
+            let asset = assets.asset_index(&cancel.asset)?;
             transformed_cancels.push(CancelRequestCloid {
                 asset,
                 cloid: uuid_to_hex_string(cancel.cloid),
@@ -649,10 +1062,10 @@ impl ExchangeClient {
             cancels: transformed_cancels,
         });
 
-        let connection_id = action.hash(timestamp, self.inner.vault_address)?;
-        let action = serde_json::to_value(&action).map_err(|e| Error::JsonParse(e.to_string()))?;
+        let connection_id =
+            action.hash(timestamp, self.vault_address, self.expires_after(timestamp))?;
         let is_mainnet = self.inner.http_client.is_mainnet();
-        let signature = sign_l1_action(wallet, connection_id, is_mainnet)?;
+        let signature = sign_l1_action(wallet, connection_id, is_mainnet).await?;
 
         self.post(action, signature, timestamp).await
     }
@@ -662,26 +1075,22 @@ impl ExchangeClient {
         leverage: u32,
         coin: &str,
         is_cross: bool,
-        wallet: Option<&LocalWallet>,
+        wallet: Option<&dyn HyperliquidSigner>,
     ) -> Result<ExchangeResponseStatus> {
-        let wallet = wallet.unwrap_or(&self.inner.wallet);
+        let wallet = wallet.unwrap_or(self.inner.signer.as_ref());
 
-        let timestamp = next_nonce();
+        let timestamp = self.next_nonce(wallet).await?;
 
-        let &asset_index = self
-            .inner
-            .coin_to_asset
-            .get(coin)
-            .ok_or(Error::AssetNotFound)?;
+        let asset_index = self.meta.assets_for([coin]).await?.asset_index(coin)?;
         let action = Actions::UpdateLeverage(UpdateLeverage {
             asset: asset_index,
             is_cross,
             leverage,
         });
-        let connection_id = action.hash(timestamp, self.inner.vault_address)?;
-        let action = serde_json::to_value(&action).map_err(|e| Error::JsonParse(e.to_string()))?;
+        let connection_id =
+            action.hash(timestamp, self.vault_address, self.expires_after(timestamp))?;
         let is_mainnet = self.inner.http_client.is_mainnet();
-        let signature = sign_l1_action(wallet, connection_id, is_mainnet)?;
+        let signature = sign_l1_action(wallet, connection_id, is_mainnet).await?;
 
         self.post(action, signature, timestamp).await
     }
@@ -690,42 +1099,38 @@ impl ExchangeClient {
         &self,
         amount: f64,
         coin: &str,
-        wallet: Option<&LocalWallet>,
+        wallet: Option<&dyn HyperliquidSigner>,
     ) -> Result<ExchangeResponseStatus> {
-        let wallet = wallet.unwrap_or(&self.inner.wallet);
+        let wallet = wallet.unwrap_or(self.inner.signer.as_ref());
 
         let amount = (amount * 1_000_000.0).round() as i64;
-        let timestamp = next_nonce();
+        let timestamp = self.next_nonce(wallet).await?;
 
-        let &asset_index = self
-            .inner
-            .coin_to_asset
-            .get(coin)
-            .ok_or(Error::AssetNotFound)?;
+        let asset_index = self.meta.assets_for([coin]).await?.asset_index(coin)?;
         let action = Actions::UpdateIsolatedMargin(UpdateIsolatedMargin {
             asset: asset_index,
             is_buy: true,
             ntli: amount,
         });
-        let connection_id = action.hash(timestamp, self.inner.vault_address)?;
-        let action = serde_json::to_value(&action).map_err(|e| Error::JsonParse(e.to_string()))?;
+        let connection_id =
+            action.hash(timestamp, self.vault_address, self.expires_after(timestamp))?;
         let is_mainnet = self.inner.http_client.is_mainnet();
-        let signature = sign_l1_action(wallet, connection_id, is_mainnet)?;
+        let signature = sign_l1_action(wallet, connection_id, is_mainnet).await?;
 
         self.post(action, signature, timestamp).await
     }
 
     pub async fn approve_agent(
         &self,
-        wallet: Option<&LocalWallet>,
+        wallet: Option<&dyn HyperliquidSigner>,
     ) -> Result<(String, ExchangeResponseStatus)> {
-        let wallet = wallet.unwrap_or(&self.inner.wallet);
+        let wallet = wallet.unwrap_or(self.inner.signer.as_ref());
         let key = H256::from(generate_random_key()?).encode_hex()[2..].to_string();
 
-        let address = key
+        let agent = key
             .parse::<LocalWallet>()
-            .map_err(|e| Error::PrivateKeyParse(e.to_string()))?
-            .address();
+            .map_err(|e| Error::PrivateKeyParse(e.to_string()))?;
+        let address = Signer::address(&agent);
 
         let hyperliquid_chain = if self.inner.http_client.is_mainnet() {
             "Mainnet".to_string()
@@ -733,7 +1138,7 @@ impl ExchangeClient {
             "Testnet".to_string()
         };
 
-        let nonce = next_nonce();
+        let nonce = self.next_nonce(wallet).await?;
         let approve_agent = ApproveAgent {
             signature_chain_id: 421614.into(),
             hyperliquid_chain,
@@ -741,9 +1146,8 @@ impl ExchangeClient {
             agent_name: None,
             nonce,
         };
-        let signature = sign_typed_data(&approve_agent, wallet)?;
-        let action = serde_json::to_value(Actions::ApproveAgent(approve_agent))
-            .map_err(|e| Error::JsonParse(e.to_string()))?;
+        let signature = sign_typed_data(&approve_agent, wallet).await?;
+        let action = Actions::ApproveAgent(approve_agent);
         Ok((key, self.post(action, signature, nonce).await?))
     }
 
@@ -751,56 +1155,119 @@ impl ExchangeClient {
         &self,
         amount: &str,
         destination: &str,
-        wallet: Option<&LocalWallet>,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        let wallet = wallet.unwrap_or(self.inner.signer.as_ref());
+        let signed = SignedAction::withdraw_from_bridge(
+            amount,
+            destination,
+            self.next_nonce(wallet).await?,
+            self.inner.http_client.is_mainnet(),
+            wallet,
+        )
+        .await?;
+        self.post_signed(&signed).await
+    }
+
+    pub async fn spot_transfer(
+        &self,
+        amount: &str,
+        destination: &str,
+        token: &str,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        let wallet = wallet.unwrap_or(self.inner.signer.as_ref());
+        let signed = SignedAction::spot_transfer(
+            amount,
+            destination,
+            token,
+            self.next_nonce(wallet).await?,
+            self.inner.http_client.is_mainnet(),
+            wallet,
+        )
+        .await?;
+        self.post_signed(&signed).await
+    }
+
+    /// Moves `wei` of HYPE (with 8 decimals) from the spot balance into staking.
+    pub async fn c_deposit(
+        &self,
+        wei: u64,
+        wallet: Option<&dyn HyperliquidSigner>,
     ) -> Result<ExchangeResponseStatus> {
-        let wallet = wallet.unwrap_or(&self.inner.wallet);
+        let wallet = wallet.unwrap_or(self.inner.signer.as_ref());
         let hyperliquid_chain = if self.inner.http_client.is_mainnet() {
             "Mainnet".to_string()
         } else {
             "Testnet".to_string()
         };
 
-        let timestamp = next_nonce();
-        let withdraw = Withdraw3 {
+        let timestamp = self.next_nonce(wallet).await?;
+        let c_deposit = CDeposit {
             signature_chain_id: 421614.into(),
             hyperliquid_chain,
-            destination: destination.to_string(),
-            amount: amount.to_string(),
-            time: timestamp,
+            wei,
+            nonce: timestamp,
         };
-        let signature = sign_typed_data(&withdraw, wallet)?;
-        let action = serde_json::to_value(Actions::Withdraw3(withdraw))
-            .map_err(|e| Error::JsonParse(e.to_string()))?;
+        let signature = sign_typed_data(&c_deposit, wallet).await?;
+        let action = Actions::CDeposit(c_deposit);
 
         self.post(action, signature, timestamp).await
     }
 
-    pub async fn spot_transfer(
+    /// Moves `wei` of undelegated HYPE (with 8 decimals) from staking back to spot.
+    pub async fn c_withdraw(
         &self,
-        amount: &str,
-        destination: &str,
-        token: &str,
-        wallet: Option<&LocalWallet>,
+        wei: u64,
+        wallet: Option<&dyn HyperliquidSigner>,
     ) -> Result<ExchangeResponseStatus> {
-        let wallet = wallet.unwrap_or(&self.inner.wallet);
+        let wallet = wallet.unwrap_or(self.inner.signer.as_ref());
         let hyperliquid_chain = if self.inner.http_client.is_mainnet() {
             "Mainnet".to_string()
         } else {
             "Testnet".to_string()
         };
 
-        let timestamp = next_nonce();
-        let spot_send = SpotSend {
+        let timestamp = self.next_nonce(wallet).await?;
+        let c_withdraw = CWithdraw {
             signature_chain_id: 421614.into(),
             hyperliquid_chain,
-            destination: destination.to_string(),
-            amount: amount.to_string(),
-            time: timestamp,
-            token: token.to_string(),
+            wei,
+            nonce: timestamp,
+        };
+        let signature = sign_typed_data(&c_withdraw, wallet).await?;
+        let action = Actions::CWithdraw(c_withdraw);
+
+        self.post(action, signature, timestamp).await
+    }
+
+    /// Delegates `wei` of staked HYPE (with 8 decimals) to a validator, or undelegates it
+    /// when `is_undelegate` is set.
+    pub async fn token_delegate(
+        &self,
+        validator: H160,
+        wei: u64,
+        is_undelegate: bool,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        let wallet = wallet.unwrap_or(self.inner.signer.as_ref());
+        let hyperliquid_chain = if self.inner.http_client.is_mainnet() {
+            "Mainnet".to_string()
+        } else {
+            "Testnet".to_string()
+        };
+
+        let timestamp = self.next_nonce(wallet).await?;
+        let token_delegate = TokenDelegate {
+            signature_chain_id: 421614.into(),
+            hyperliquid_chain,
+            validator,
+            wei,
+            is_undelegate,
+            nonce: timestamp,
         };
-        let signature = sign_typed_data(&spot_send, wallet)?;
-        let action = serde_json::to_value(Actions::SpotSend(spot_send))
-            .map_err(|e| Error::JsonParse(e.to_string()))?;
+        let signature = sign_typed_data(&token_delegate, wallet).await?;
+        let action = Actions::TokenDelegate(token_delegate);
 
         self.post(action, signature, timestamp).await
     }
@@ -808,18 +1275,18 @@ impl ExchangeClient {
     pub async fn set_referrer(
         &self,
         code: String,
-        wallet: Option<&LocalWallet>,
+        wallet: Option<&dyn HyperliquidSigner>,
     ) -> Result<ExchangeResponseStatus> {
-        let wallet = wallet.unwrap_or(&self.inner.wallet);
-        let timestamp = next_nonce();
+        let wallet = wallet.unwrap_or(self.inner.signer.as_ref());
+        let timestamp = self.next_nonce(wallet).await?;
 
         let action = Actions::SetReferrer(SetReferrer { code });
 
-        let connection_id = action.hash(timestamp, self.inner.vault_address)?;
-        let action = serde_json::to_value(&action).map_err(|e| Error::JsonParse(e.to_string()))?;
+        let connection_id =
+            action.hash(timestamp, self.vault_address, self.expires_after(timestamp))?;
 
         let is_mainnet = self.inner.http_client.is_mainnet();
-        let signature = sign_l1_action(wallet, connection_id, is_mainnet)?;
+        let signature = sign_l1_action(wallet, connection_id, is_mainnet).await?;
         self.post(action, signature, timestamp).await
     }
 
@@ -827,10 +1294,10 @@ impl ExchangeClient {
         &self,
         builder: String,
         max_fee_rate: String,
-        wallet: Option<&LocalWallet>,
+        wallet: Option<&dyn HyperliquidSigner>,
     ) -> Result<ExchangeResponseStatus> {
-        let wallet = wallet.unwrap_or(&self.inner.wallet);
-        let timestamp = next_nonce();
+        let wallet = wallet.unwrap_or(self.inner.signer.as_ref());
+        let timestamp = self.next_nonce(wallet).await?;
 
         let hyperliquid_chain = if self.inner.http_client.is_mainnet() {
             "Mainnet".to_string()
@@ -846,36 +1313,149 @@ impl ExchangeClient {
             nonce: timestamp,
         });
 
-        let connection_id = action.hash(timestamp, self.inner.vault_address)?;
-        let action = serde_json::to_value(&action).map_err(|e| Error::JsonParse(e.to_string()))?;
+        let connection_id =
+            action.hash(timestamp, self.vault_address, self.expires_after(timestamp))?;
 
         let is_mainnet = self.inner.http_client.is_mainnet();
-        let signature = sign_l1_action(wallet, connection_id, is_mainnet)?;
+        let signature = sign_l1_action(wallet, connection_id, is_mainnet).await?;
         self.post(action, signature, timestamp).await
     }
-}
 
-fn round_to_decimals(value: f64, decimals: u32) -> f64 {
-    let factor = 10f64.powi(decimals as i32);
-    (value * factor).round() / factor
-}
+    /// Schedules a cancel of all open orders at `time` (ms since epoch), which must be at
+    /// least 5 seconds in the future. Passing `None` removes the scheduled cancel.
+    pub async fn schedule_cancel(
+        &self,
+        time: Option<u64>,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        let wallet = wallet.unwrap_or(self.inner.signer.as_ref());
+        let timestamp = self.next_nonce(wallet).await?;
+
+        let action = Actions::ScheduleCancel(ScheduleCancel { time });
+        let connection_id =
+            action.hash(timestamp, self.vault_address, self.expires_after(timestamp))?;
+
+        let is_mainnet = self.inner.http_client.is_mainnet();
+        let signature = sign_l1_action(wallet, connection_id, is_mainnet).await?;
+        self.post(action, signature, timestamp).await
+    }
+
+    pub async fn create_sub_account(
+        &self,
+        name: String,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<CreateSubAccountResponseStatus> {
+        let wallet = wallet.unwrap_or(self.inner.signer.as_ref());
+        let timestamp = self.next_nonce(wallet).await?;
+
+        // Sub-accounts are managed by the master account, never on behalf of a vault
+        let action = Actions::CreateSubAccount(CreateSubAccount { name });
+        let connection_id = action.hash(timestamp, None, self.expires_after(timestamp))?;
+
+        let is_mainnet = self.inner.http_client.is_mainnet();
+        let signature = sign_l1_action(wallet, connection_id, is_mainnet).await?;
+        self.post_with_vault_address(action, signature, timestamp, None)
+            .await
+    }
+
+    /// Moves `usd` (with 6 decimals, so 1_000_000 is 1 USDC) between the master account's
+    /// perp balance and a sub-account.
+    pub async fn sub_account_transfer(
+        &self,
+        sub_account_user: H160,
+        is_deposit: bool,
+        usd: u64,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        let wallet = wallet.unwrap_or(self.inner.signer.as_ref());
+        let timestamp = self.next_nonce(wallet).await?;
+
+        let action = Actions::SubAccountTransfer(SubAccountUsdTransfer {
+            sub_account_user,
+            is_deposit,
+            usd,
+        });
+        let connection_id = action.hash(timestamp, None, self.expires_after(timestamp))?;
 
-fn round_to_significant_and_decimal(value: f64, sig_figs: u32, max_decimals: u32) -> f64 {
-    let abs_value = value.abs();
-    let magnitude = abs_value.log10().floor() as i32;
-    let scale = 10f64.powi(sig_figs as i32 - magnitude - 1);
-    let rounded = (abs_value * scale).round() / scale;
-    round_to_decimals(rounded.copysign(value), max_decimals)
+        let is_mainnet = self.inner.http_client.is_mainnet();
+        let signature = sign_l1_action(wallet, connection_id, is_mainnet).await?;
+        self.post_with_vault_address(action, signature, timestamp, None)
+            .await
+    }
+
+    pub async fn sub_account_spot_transfer(
+        &self,
+        sub_account_user: H160,
+        is_deposit: bool,
+        token: &str,
+        amount: &str,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        let wallet = wallet.unwrap_or(self.inner.signer.as_ref());
+        let timestamp = self.next_nonce(wallet).await?;
+
+        let action = Actions::SubAccountSpotTransfer(SubAccountSpotTransfer {
+            sub_account_user,
+            is_deposit,
+            token: token.to_string(),
+            amount: amount.to_string(),
+        });
+        let connection_id = action.hash(timestamp, None, self.expires_after(timestamp))?;
+
+        let is_mainnet = self.inner.http_client.is_mainnet();
+        let signature = sign_l1_action(wallet, connection_id, is_mainnet).await?;
+        self.post_with_vault_address(action, signature, timestamp, None)
+            .await
+    }
+
+    pub async fn twap_order(
+        &self,
+        twap: ClientTwapRequest,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<TwapResponseStatus> {
+        let wallet = wallet.unwrap_or(self.inner.signer.as_ref());
+        let timestamp = self.next_nonce(wallet).await?;
+
+        let assets = self.meta.assets_for([twap.asset.as_str()]).await?;
+        let action = Actions::TwapOrder(TwapOrder {
+            twap: twap.convert(&assets)?,
+        });
+        let connection_id =
+            action.hash(timestamp, self.vault_address, self.expires_after(timestamp))?;
+
+        let is_mainnet = self.inner.http_client.is_mainnet();
+        let signature = sign_l1_action(wallet, connection_id, is_mainnet).await?;
+        self.post(action, signature, timestamp).await
+    }
+
+    pub async fn twap_cancel(
+        &self,
+        asset: &str,
+        twap_id: u64,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<TwapResponseStatus> {
+        let wallet = wallet.unwrap_or(self.inner.signer.as_ref());
+        let timestamp = self.next_nonce(wallet).await?;
+
+        let asset = self.meta.assets_for([asset]).await?.asset_index(asset)?;
+        let action = Actions::TwapCancel(TwapCancel { asset, twap_id });
+        let connection_id =
+            action.hash(timestamp, self.vault_address, self.expires_after(timestamp))?;
+
+        let is_mainnet = self.inner.http_client.is_mainnet();
+        let signature = sign_l1_action(wallet, connection_id, is_mainnet).await?;
+        self.post(action, signature, timestamp).await
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::str::FromStr;
+    use std::{str::FromStr, time::Duration};
 
     use super::*;
     use crate::{
         exchange::order::{Limit, OrderRequest, Trigger},
-        Order,
+        ExchangeDataStatus, Order, TwapData, TwapDataStatus, TwapRequest, TwapResponse,
     };
 
     fn get_wallet() -> Result<LocalWallet> {
@@ -885,8 +1465,8 @@ mod tests {
             .map_err(|e| Error::Wallet(e.to_string()))
     }
 
-    #[test]
-    fn test_limit_order_action_hashing() -> Result<()> {
+    #[tokio::test]
+    async fn test_limit_order_action_hashing() -> Result<()> {
         let wallet = get_wallet()?;
         let action = Actions::Order(BulkOrder {
             orders: vec![OrderRequest {
@@ -900,22 +1480,22 @@ mod tests {
                 }),
                 cloid: None,
             }],
-            grouping: "na".to_string(),
+            grouping: OrderGrouping::Na,
             builder: None,
         });
-        let connection_id = action.hash(1583838, None)?;
+        let connection_id = action.hash(1583838, None, None)?;
 
-        let signature = sign_l1_action(&wallet, connection_id, true)?;
+        let signature = sign_l1_action(&wallet, connection_id, true).await?;
         assert_eq!(signature.to_string(), "77957e58e70f43b6b68581f2dc42011fc384538a2e5b7bf42d5b936f19fbb67360721a8598727230f67080efee48c812a6a4442013fd3b0eed509171bef9f23f1c");
 
-        let signature = sign_l1_action(&wallet, connection_id, false)?;
+        let signature = sign_l1_action(&wallet, connection_id, false).await?;
         assert_eq!(signature.to_string(), "cd0925372ff1ed499e54883e9a6205ecfadec748f80ec463fe2f84f1209648776377961965cb7b12414186b1ea291e95fd512722427efcbcfb3b0b2bcd4d79d01c");
 
         Ok(())
     }
 
-    #[test]
-    fn test_limit_order_action_hashing_with_cloid() -> Result<()> {
+    #[tokio::test]
+    async fn test_limit_order_action_hashing_with_cloid() -> Result<()> {
         let cloid = uuid::Uuid::from_str("1e60610f-0b3d-4205-97c8-8c1fed2ad5ee")
             .map_err(|_e| uuid::Uuid::new_v4());
         let wallet = get_wallet()?;
@@ -931,22 +1511,22 @@ mod tests {
                 }),
                 cloid: Some(uuid_to_hex_string(cloid.unwrap())),
             }],
-            grouping: "na".to_string(),
+            grouping: OrderGrouping::Na,
             builder: None,
         });
-        let connection_id = action.hash(1583838, None)?;
+        let connection_id = action.hash(1583838, None, None)?;
 
-        let signature = sign_l1_action(&wallet, connection_id, true)?;
+        let signature = sign_l1_action(&wallet, connection_id, true).await?;
         assert_eq!(signature.to_string(), "d3e894092eb27098077145714630a77bbe3836120ee29df7d935d8510b03a08f456de5ec1be82aa65fc6ecda9ef928b0445e212517a98858cfaa251c4cd7552b1c");
 
-        let signature = sign_l1_action(&wallet, connection_id, false)?;
+        let signature = sign_l1_action(&wallet, connection_id, false).await?;
         assert_eq!(signature.to_string(), "3768349dbb22a7fd770fc9fc50c7b5124a7da342ea579b309f58002ceae49b4357badc7909770919c45d850aabb08474ff2b7b3204ae5b66d9f7375582981f111c");
 
         Ok(())
     }
 
-    #[test]
-    fn test_tpsl_order_action_hashing() -> Result<()> {
+    #[tokio::test]
+    async fn test_tpsl_order_action_hashing() -> Result<()> {
         for (tpsl, mainnet_signature, testnet_signature) in [
             (
                 "tp",
@@ -976,22 +1556,107 @@ mod tests {
                         cloid: None,
                     }
                 ],
-                grouping: "na".to_string(),
+                grouping: OrderGrouping::Na,
                 builder: None,
             });
-            let connection_id = action.hash(1583838, None)?;
+            let connection_id = action.hash(1583838, None, None)?;
 
-            let signature = sign_l1_action(&wallet, connection_id, true)?;
+            let signature = sign_l1_action(&wallet, connection_id, true).await?;
             assert_eq!(signature.to_string(), mainnet_signature);
 
-            let signature = sign_l1_action(&wallet, connection_id, false)?;
+            let signature = sign_l1_action(&wallet, connection_id, false).await?;
             assert_eq!(signature.to_string(), testnet_signature);
         }
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_normal_tpsl_order_action_hashing() -> Result<()> {
+        let wallet = get_wallet()?;
+        let action = Actions::Order(BulkOrder {
+            orders: vec![
+                OrderRequest {
+                    asset: 1,
+                    is_buy: true,
+                    limit_px: "2000.0".to_string(),
+                    sz: "3.5".to_string(),
+                    reduce_only: false,
+                    order_type: Order::Limit(Limit {
+                        tif: "Gtc".to_string(),
+                    }),
+                    cloid: None,
+                },
+                OrderRequest {
+                    asset: 1,
+                    is_buy: false,
+                    limit_px: "2100.0".to_string(),
+                    sz: "3.5".to_string(),
+                    reduce_only: true,
+                    order_type: Order::Trigger(Trigger {
+                        trigger_px: "2100.0".to_string(),
+                        is_market: true,
+                        tpsl: "tp".to_string(),
+                    }),
+                    cloid: None,
+                },
+                OrderRequest {
+                    asset: 1,
+                    is_buy: false,
+                    limit_px: "1900.0".to_string(),
+                    sz: "3.5".to_string(),
+                    reduce_only: true,
+                    order_type: Order::Trigger(Trigger {
+                        trigger_px: "1900.0".to_string(),
+                        is_market: true,
+                        tpsl: "sl".to_string(),
+                    }),
+                    cloid: None,
+                },
+            ],
+            grouping: OrderGrouping::NormalTpsl,
+            builder: None,
+        });
+        let connection_id = action.hash(1583838, None, None)?;
+
+        let signature = sign_l1_action(&wallet, connection_id, true).await?;
+        assert_eq!(signature.to_string(), "a62559f2b81df81dd6710d6aa383c054b35efbe6d229d1f036ac0b37e2fafabe0d25002630787dfdf4239797e4a5d806184ba9a3726b3a71c1a8929878a608c71b");
+
+        let signature = sign_l1_action(&wallet, connection_id, false).await?;
+        assert_eq!(signature.to_string(), "d7bb7e0f44d46f2671efb1b39988d103e9a3759b39f89f102fd904902c9b452849e556a5fe93c6af15e00aa370b9af2e8f2fcb26019593fc04098eff566edbe91c");
+
+        Ok(())
+    }
+
     #[test]
-    fn test_cancel_action_hashing() -> Result<()> {
+    fn test_bracket_order_status_mapping() -> Result<()> {
+        let response: ExchangeResponseStatus = serde_json::from_str(
+            r#"{"status":"ok","response":{"type":"order","data":{"statuses":[{"resting":{"oid":77738308}},"waitingForFill",{"error":"Order has invalid price."}]}}}"#,
+        )
+        .map_err(|e| Error::JsonParse(e.to_string()))?;
+
+        let BracketOrderStatus::Ok(legs) = BracketOrderStatus::from_response(response, false, true)
+        else {
+            panic!("expected leg statuses");
+        };
+        assert!(matches!(legs.entry, ExchangeDataStatus::Resting(_)));
+        assert!(legs.take_profit.is_none());
+        assert!(matches!(
+            legs.stop_loss,
+            Some(ExchangeDataStatus::WaitingForFill)
+        ));
+
+        let status = BracketOrderStatus::from_response(
+            ExchangeResponseStatus::Err("User or API Wallet does not exist.".to_string()),
+            true,
+            true,
+        );
+        assert!(matches!(status, BracketOrderStatus::Err(_)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_cancel_action_hashing() -> Result<()> {
         let wallet = get_wallet()?;
         let action = Actions::Cancel(BulkCancel {
             cancels: vec![CancelRequest {
@@ -999,14 +1664,340 @@ mod tests {
                 oid: 82382,
             }],
         });
-        let connection_id = action.hash(1583838, None)?;
+        let connection_id = action.hash(1583838, None, None)?;
 
-        let signature = sign_l1_action(&wallet, connection_id, true)?;
+        let signature = sign_l1_action(&wallet, connection_id, true).await?;
         assert_eq!(signature.to_string(), "02f76cc5b16e0810152fa0e14e7b219f49c361e3325f771544c6f54e157bf9fa17ed0afc11a98596be85d5cd9f86600aad515337318f7ab346e5ccc1b03425d51b");
 
-        let signature = sign_l1_action(&wallet, connection_id, false)?;
+        let signature = sign_l1_action(&wallet, connection_id, false).await?;
         assert_eq!(signature.to_string(), "6ffebadfd48067663390962539fbde76cfa36f53be65abe2ab72c9db6d0db44457720db9d7c4860f142a484f070c84eb4b9694c3a617c83f0d698a27e55fd5e01c");
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_expires_after_action_hashing() -> Result<()> {
+        let wallet = get_wallet()?;
+        let action = Actions::Cancel(BulkCancel {
+            cancels: vec![CancelRequest {
+                asset: 1,
+                oid: 82382,
+            }],
+        });
+        let expires_after = ActionExpiry::After(Duration::from_secs(60)).expires_after(1583838);
+        assert_eq!(expires_after, 1643838);
+        let connection_id = action.hash(1583838, None, Some(expires_after))?;
+        assert_ne!(connection_id, action.hash(1583838, None, None)?);
+
+        let signature = sign_l1_action(&wallet, connection_id, true).await?;
+        assert_eq!(signature.to_string(), "f150ea1394d203c9bbe3dcceb7609d9aa1d31170915fb83c9275e8b5718e15a704fc8b34cd832eb46e7f8374865217faeddad3411e5b483251da87766df270521b");
+
+        let signature = sign_l1_action(&wallet, connection_id, false).await?;
+        assert_eq!(signature.to_string(), "7eff447e2077f5c72d2beeca81820a24e820dbd6f33594dcabf78ff3e29826d4327094616aa099e743afea6c58074ec2646d46c8a0308a8bdae8ac4005e5db721b");
+
+        let vault_address = H160::from_str("0x1719884eb866cb12b2287399b15f7db5e7d775ea").unwrap();
+        let connection_id = action.hash(1583838, Some(vault_address), Some(expires_after))?;
+
+        let signature = sign_l1_action(&wallet, connection_id, true).await?;
+        assert_eq!(signature.to_string(), "4e806271faad29d1acfddc33f3e2a75a9a0b17bd503a513527a15911cf37fd1673f95030548e6c5310025cfeefa7f753c1e9465c78587a2acb093251a2072ec31b");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_schedule_cancel_action_hashing() -> Result<()> {
+        for (time, mainnet_signature, testnet_signature) in [
+            (Some(1583838 + 60_000), "2096d6b8b2f3e7e038c1ee45e25210aaf031015ca1f3a0164b973213e39af66e1cee0546b01e44f8e0699037620b8a0289266fe8d3c2e12c57733d230dbb65681b", "7a29b24404e997b3882c1552700eb91ea61650924c866696473fabaf341675f2541e5b1bc3613045678701330cc13015d19f90798c2d19700b921e01c45ef6921b"),
+            (None, "d32e5413a14b9598a41e294fb2c401a2d7d17e76c21445f5cc335b1c9927a6d41af9b36c758ca1f0bf813678b01695625785bca6ddf09f17163879abea8f71751c", "a86de8e44f8dcbe6480b4b96c48cebfde96a5993749772beb8758b0e3979d2733b07009f08fec92e839f3f1c6dcb2f69f9b6b33fed594260d80f9fcd5081515b1b"),
+        ] {
+            let wallet = get_wallet()?;
+            let action = Actions::ScheduleCancel(ScheduleCancel { time });
+            let connection_id = action.hash(1583838, None, None)?;
+
+            let signature = sign_l1_action(&wallet, connection_id, true).await?;
+            assert_eq!(signature.to_string(), mainnet_signature);
+
+            let signature = sign_l1_action(&wallet, connection_id, false).await?;
+            assert_eq!(signature.to_string(), testnet_signature);
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_sub_account_action_hashing() -> Result<()> {
+        let wallet = get_wallet()?;
+        let sub_account_user: H160 = "0x1d9470d4b963f552e6f671a81619d395877bf409"
+            .parse()
+            .map_err(|_| Error::GenericParse("bad address".to_string()))?;
+
+        for (action, mainnet_signature, testnet_signature) in [
+            (
+                Actions::CreateSubAccount(CreateSubAccount {
+                    name: "example".to_string(),
+                }),
+                "641ae838866f45167ac83ff50922925254bcd6e7df305cd9237a85b546a37d6a53bd2e567c6fdce4417272162303511731b77f0bf1db11d5b594133d969153d91b",
+                "37c421eabcad25b7e8188ce2228aed96c0eaef2b59be180d0e0b30ccf68cde2d597d35c84da5ed512766c8131c1a63e45de8389e0790c9d7fa445ba610d7d1c51c",
+            ),
+            (
+                Actions::SubAccountTransfer(SubAccountUsdTransfer {
+                    sub_account_user,
+                    is_deposit: true,
+                    usd: 1_000_000,
+                }),
+                "d766ff2eb59b1ab6d33c474fa22830e302c5c116aadb9b7c3cdc08ea3e4f08ba0ba874106de56d86501e1eaefce781413880775c3a632940149788e688ba00201c",
+                "a839b60fb8d30ff896b9e7c2b249afedc073ccc81984add5d4b964ada7d3e4c53ba2846c9355abb210c0ec90680e364320efa679e80972b5262150908137a72f1b",
+            ),
+            (
+                Actions::SubAccountSpotTransfer(SubAccountSpotTransfer {
+                    sub_account_user,
+                    is_deposit: false,
+                    token: "PURR:0xc4bf3f870c0e9465323c0b6ed28096c2".to_string(),
+                    amount: "12.5".to_string(),
+                }),
+                "7b3a6faba58ffd2343f4cf9ce2927df9d4344253302948dd47da368ac57098094dd82bad8f3d29bb67531536cfce096c891ed0173ef92be176b4f0365b7b24eb1c",
+                "9fe8c11f746da472c083a93ee9d2960af5a9f2a4745f3e6f1b73b56a1dfdeaed025999713cdfb42b2f7711645be5ec1a11bc37be642a5af4f6ca4f2eb9cca2a71b",
+            ),
+        ] {
+            let connection_id = action.hash(1583838, None, None)?;
+
+            let signature = sign_l1_action(&wallet, connection_id, true).await?;
+            assert_eq!(signature.to_string(), mainnet_signature);
+
+            let signature = sign_l1_action(&wallet, connection_id, false).await?;
+            assert_eq!(signature.to_string(), testnet_signature);
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_vault_action_hashing() -> Result<()> {
+        let wallet = get_wallet()?;
+        let vault_address: H160 = "0x1962905b0a2d0ce7907ae1a0d17f3e4a1f63dfb7"
+            .parse()
+            .map_err(|_| Error::GenericParse("bad address".to_string()))?;
+
+        for (action, mainnet_signature, testnet_signature) in [
+            (
+                Actions::CreateVault(CreateVault {
+                    name: "example".to_string(),
+                    description: "example vault".to_string(),
+                    initial_usd: 100_000_000,
+                    nonce: 1583838,
+                }),
+                "7108019caddffc28408b30232e590b9fe5e15c5716fa4548027752025710826f202795894d6b4db46a5d458d660f6cc6f2ead7f32718e4f92f1e91bf9c7fd3cc1c",
+                "fcb679995f02941e829d06a71eab60ad49ab540f1fbd30be594e49050981902c40406def7a9dca90e7743e3bc3dd5450789cc4ff9660ad92805b2ff64078d3ba1c",
+            ),
+            (
+                Actions::VaultModify(VaultModify {
+                    vault_address,
+                    allow_deposits: Some(false),
+                    always_close_on_withdraw: None,
+                }),
+                "a03683282f69a96598434bce8fbd7fbc80bffe511a33ce09f0187077ab422067338e8547353db02002b6092ad0bc6fe337d8069e0a2dee608fea6a8f11a3f12d1b",
+                "0d419a44aa1483a90bbdaab4c1052c17bd0880e725b2b57ffc0af474c174479d0a6bde78d000072cde88f5a77832408ded14c422ea8ac017e0d65ca3a159dcce1b",
+            ),
+            (
+                Actions::VaultDistribute(VaultDistribute {
+                    vault_address,
+                    usd: 5_000_000,
+                }),
+                "db94460ca38ca0668619c28553b3c45a434fc32191dc60377a0d9e6731ee10892a3b8869cd9609c0b4668d8dbc016ab83507ae05155b14e81bcf5320a781263c1b",
+                "1293b6cd84f06ad6a40c24c0aba873aea72877482e0472bfcfddafe90f9eb78d2e1feeba217c03588d9f0adb28c336f9ff75b841a732c65b7cea2c07bc82015c1c",
+            ),
+        ] {
+            let connection_id = action.hash(1583838, None, None)?;
+
+            let signature = sign_l1_action(&wallet, connection_id, true).await?;
+            assert_eq!(signature.to_string(), mainnet_signature);
+
+            let signature = sign_l1_action(&wallet, connection_id, false).await?;
+            assert_eq!(signature.to_string(), testnet_signature);
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_twap_order_action_hashing() -> Result<()> {
+        let wallet = get_wallet()?;
+        let action = Actions::TwapOrder(TwapOrder {
+            twap: TwapRequest {
+                asset: 1,
+                is_buy: true,
+                sz: "3.5".to_string(),
+                reduce_only: false,
+                minutes: 30,
+                randomize: true,
+            },
+        });
+        let connection_id = action.hash(1583838, None, None)?;
+
+        let signature = sign_l1_action(&wallet, connection_id, true).await?;
+        assert_eq!(signature.to_string(), "110eee2bedf0cc59d99ceea1aace3b605b9b14a499daa7f3ca73e33e6da185f873670abeb047ffe9990d5334b855cba68c47855eb6cdde52579caf934d01322b1b");
+
+        let signature = sign_l1_action(&wallet, connection_id, false).await?;
+        assert_eq!(signature.to_string(), "3d503305ba84f7d47805c8508bac6dbb53e053ddaec6206b14cf1aad7eba1ed76dd11ec1cbdd21ead38405cbe5e755347e894a70dba83b0fcc9c8c1987f715321c");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_twap_cancel_action_hashing() -> Result<()> {
+        let wallet = get_wallet()?;
+        let action = Actions::TwapCancel(TwapCancel {
+            asset: 1,
+            twap_id: 77738308,
+        });
+        let connection_id = action.hash(1583838, None, None)?;
+
+        let signature = sign_l1_action(&wallet, connection_id, true).await?;
+        assert_eq!(signature.to_string(), "a4b49be8eacf2710c9e23b6d5375c8ad62602be78de6170a280f67e283e4f65f0a51794f19eed4970a73ca3c63894010c56958a4559f7ac47f3193572715c3bc1b");
+
+        let signature = sign_l1_action(&wallet, connection_id, false).await?;
+        assert_eq!(signature.to_string(), "38011496a421b3f6af8629ed091fbba0893230500ae82002289c53502a74246c5535974e56777440fdf77f382f9e73ec986be7c72124968679f396dcafa4eb0e1b");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_twap_response_parsing() -> Result<()> {
+        let running: TwapResponseStatus = serde_json::from_str(
+            r#"{"status":"ok","response":{"type":"twapOrder","data":{"status":{"running":{"twapId":77738308}}}}}"#,
+        )
+        .map_err(|e| Error::JsonParse(e.to_string()))?;
+        assert_eq!(running.twap_id(), Some(77738308));
+
+        let cancelled: TwapResponseStatus = serde_json::from_str(
+            r#"{"status":"ok","response":{"type":"twapCancel","data":{"status":"success"}}}"#,
+        )
+        .map_err(|e| Error::JsonParse(e.to_string()))?;
+        assert_eq!(cancelled.twap_id(), None);
+
+        let rejected: TwapResponseStatus = serde_json::from_str(
+            r#"{"status":"ok","response":{"type":"twapOrder","data":{"status":{"error":"Invalid TWAP duration: 1 min(s)"}}}}"#,
+        )
+        .map_err(|e| Error::JsonParse(e.to_string()))?;
+        assert!(matches!(
+            rejected,
+            TwapResponseStatus::Ok(TwapResponse {
+                data: TwapData {
+                    status: TwapDataStatus::Error(_)
+                },
+                ..
+            })
+        ));
+
+        Ok(())
+    }
+
+    /// Exchange failing the first `failures` order requests with a 502 after placing them if
+    /// `places_failed`, resting every order it placed with oid 42.
+    async fn serve_flaky_exchange(
+        failures: usize,
+        places_failed: bool,
+        posts: Arc<std::sync::Mutex<Vec<String>>>,
+    ) -> BaseUrl {
+        let meta = r#"{"universe":[{"name":"ETH","szDecimals":4}]}"#;
+        crate::test_server::serve_exchange_with_status(
+            meta,
+            crate::test_server::EMPTY_SPOT_META,
+            move |path, body| {
+                if path == "/exchange" {
+                    let mut posts = posts.lock().unwrap();
+                    posts.push(body.to_string());
+                    if posts.len() <= failures {
+                        return (502, "Bad Gateway".to_string());
+                    }
+                    let statuses = r#"{"statuses":[{"resting":{"oid":42}}]}"#;
+                    let response = format!(r#"{{"type":"order","data":{statuses}}}"#);
+                    return (200, format!(r#"{{"status":"ok","response":{response}}}"#));
+                }
+                // Only order status lookups reach here
+                let body = if !places_failed {
+                    r#"{"status":"unknownOid"}"#
+                } else {
+                    r#"{"status":"order","order":{"order":{"coin":"ETH","side":"B","limitPx":"1800","sz":"1","oid":42,"timestamp":1,"triggerCondition":"N/A","isTrigger":false,"triggerPx":"0","isPositionTpsl":false,"reduceOnly":false,"orderType":"Limit","origSz":"1","tif":"Gtc","cloid":null},"status":"open","statusTimestamp":1}}"#
+                };
+                (200, body.to_string())
+            },
+        )
+        .await
+    }
+
+    async fn retried_client(base_url: BaseUrl) -> Result<ExchangeClient> {
+        let retry = RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(1),
+            timeout: Some(Duration::from_secs(5)),
+        };
+        Ok(crate::test_server::test_client(base_url)
+            .await?
+            .with_retry_policy(Some(retry)))
+    }
+
+    fn limit_order(cloid: Option<uuid::Uuid>) -> ClientOrderRequest {
+        ClientOrderRequest {
+            asset: "ETH".to_string(),
+            is_buy: true,
+            reduce_only: false,
+            limit_px: "1800".parse().unwrap(),
+            sz: "1".parse().unwrap(),
+            cloid,
+            order_type: ClientOrder::Limit(ClientLimit {
+                tif: "Gtc".to_string(),
+            }),
+        }
+    }
+
+    fn resting_oid(response: ExchangeResponseStatus) -> Option<u64> {
+        match response {
+            ExchangeResponseStatus::Ok(response) => match &response.data?.statuses[..] {
+                [ExchangeDataStatus::Resting(resting)] => Some(resting.oid),
+                _ => None,
+            },
+            ExchangeResponseStatus::Err(_) => None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retry_finds_order_placed_by_failed_attempt() -> Result<()> {
+        let posts = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let client = retried_client(serve_flaky_exchange(1, true, posts.clone()).await).await?;
+
+        let response = client
+            .order(limit_order(Some(uuid::Uuid::new_v4())), None)
+            .await?;
+        assert_eq!(resting_oid(response), Some(42));
+        // Looked up by cloid instead of sending the order again
+        assert_eq!(posts.lock().unwrap().len(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_retry_resends_same_signed_action() -> Result<()> {
+        let posts = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let client = retried_client(serve_flaky_exchange(2, false, posts.clone()).await).await?;
+
+        let response = client
+            .order(limit_order(Some(uuid::Uuid::new_v4())), None)
+            .await?;
+        assert_eq!(resting_oid(response), Some(42));
+        {
+            let posts = posts.lock().unwrap();
+            assert_eq!(posts.len(), 3);
+            assert!(posts.iter().all(|post| *post == posts[0]));
+        }
+
+        // Out of attempts, the last failure is returned
+        let posts = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let client = retried_client(serve_flaky_exchange(3, false, posts.clone()).await).await?;
+        assert!(matches!(
+            client.order(limit_order(None), None).await,
+            Err(Error::ServerRequest {
+                status_code: 502,
+                ..
+            })
+        ));
+        Ok(())
+    }
 }
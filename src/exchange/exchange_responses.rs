@@ -1,3 +1,5 @@
+use crate::OrderRejectReason;
+use ethers::types::H160;
 use serde::Deserialize;
 
 #[derive(Deserialize, Debug, Clone)]
@@ -24,6 +26,16 @@ pub enum ExchangeDataStatus {
     Filled(FilledOrder),
 }
 
+impl ExchangeDataStatus {
+    /// Parsed reason, if the exchange rejected this order
+    pub fn reject_reason(&self) -> Option<OrderRejectReason> {
+        match self {
+            ExchangeDataStatus::Error(e) => Some(OrderRejectReason::parse(e)),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Deserialize, Debug, Clone)]
 pub struct ExchangeDataStatuses {
     pub statuses: Vec<ExchangeDataStatus>,
@@ -43,3 +55,154 @@ pub enum ExchangeResponseStatus {
     Ok(ExchangeResponse),
     Err(String),
 }
+
+impl ExchangeResponseStatus {
+    /// Parsed reason, if the exchange rejected the whole action
+    pub fn reject_reason(&self) -> Option<OrderRejectReason> {
+        match self {
+            ExchangeResponseStatus::Err(e) => Some(OrderRejectReason::parse(e)),
+            ExchangeResponseStatus::Ok(_) => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BracketLegStatuses {
+    pub entry: ExchangeDataStatus,
+    pub take_profit: Option<ExchangeDataStatus>,
+    pub stop_loss: Option<ExchangeDataStatus>,
+}
+
+#[derive(Debug, Clone)]
+pub enum BracketOrderStatus {
+    Ok(BracketLegStatuses),
+    Err(String),
+}
+
+impl BracketOrderStatus {
+    /// Maps the statuses of a grouped order response back onto its legs. The exchange
+    /// returns one status per order, in the order they were submitted: entry, then
+    /// take profit and stop loss when present.
+    pub(crate) fn from_response(
+        response: ExchangeResponseStatus,
+        has_take_profit: bool,
+        has_stop_loss: bool,
+    ) -> Self {
+        let response = match response {
+            ExchangeResponseStatus::Ok(response) => response,
+            ExchangeResponseStatus::Err(e) => return BracketOrderStatus::Err(e),
+        };
+        let Some(data) = response.data else {
+            return BracketOrderStatus::Err("Exchange response data is empty".to_string());
+        };
+
+        let mut statuses = data.statuses.into_iter();
+        let Some(entry) = statuses.next() else {
+            return BracketOrderStatus::Err("Exchange data statuses is empty".to_string());
+        };
+        let take_profit = if has_take_profit {
+            statuses.next()
+        } else {
+            None
+        };
+        let stop_loss = if has_stop_loss { statuses.next() } else { None };
+
+        BracketOrderStatus::Ok(BracketLegStatuses {
+            entry,
+            take_profit,
+            stop_loss,
+        })
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TwapRunning {
+    pub twap_id: u64,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub enum TwapDataStatus {
+    Success,
+    Running(TwapRunning),
+    Error(String),
+}
+
+impl TwapDataStatus {
+    /// Parsed reason, if the exchange rejected this TWAP
+    pub fn reject_reason(&self) -> Option<OrderRejectReason> {
+        match self {
+            TwapDataStatus::Error(e) => Some(OrderRejectReason::parse(e)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TwapData {
+    pub status: TwapDataStatus,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct TwapResponse {
+    #[serde(rename = "type")]
+    pub response_type: String,
+    pub data: TwapData,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[serde(tag = "status", content = "response")]
+pub enum TwapResponseStatus {
+    Ok(TwapResponse),
+    Err(String),
+}
+
+impl TwapResponseStatus {
+    /// Id of the TWAP that was started, if the exchange accepted it
+    pub fn twap_id(&self) -> Option<u64> {
+        match self {
+            TwapResponseStatus::Ok(TwapResponse {
+                data:
+                    TwapData {
+                        status: TwapDataStatus::Running(running),
+                    },
+                ..
+            }) => Some(running.twap_id),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct CreateSubAccountResponse {
+    #[serde(rename = "type")]
+    pub response_type: String,
+    /// Address of the newly created sub-account
+    pub data: H160,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[serde(tag = "status", content = "response")]
+pub enum CreateSubAccountResponseStatus {
+    Ok(CreateSubAccountResponse),
+    Err(String),
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct CreateVaultResponse {
+    #[serde(rename = "type")]
+    pub response_type: String,
+    /// Address of the newly created vault
+    pub data: H160,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[serde(tag = "status", content = "response")]
+pub enum CreateVaultResponseStatus {
+    Ok(CreateVaultResponse),
+    Err(String),
+}
@@ -0,0 +1,21 @@
+use std::time::Duration;
+
+/// When a signed L1 action stops being valid. The exchange rejects actions that arrive
+/// after their `expiresAfter` time instead of executing them late.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActionExpiry {
+    /// Expires this long after the action's nonce
+    After(Duration),
+    /// Expires at a fixed time, in milliseconds since epoch
+    At(u64),
+}
+
+impl ActionExpiry {
+    /// The `expiresAfter` timestamp in milliseconds for an action signed with `nonce`.
+    pub fn expires_after(&self, nonce: u64) -> u64 {
+        match self {
+            ActionExpiry::After(ttl) => nonce.saturating_add(ttl.as_millis() as u64),
+            ActionExpiry::At(time) => *time,
+        }
+    }
+}
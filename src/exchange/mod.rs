@@ -1,20 +1,49 @@
+mod account;
 mod actions;
+mod book_depth;
 mod builder;
 mod cancel;
+mod chunked;
+mod dead_mans_switch;
 mod exchange_client;
 mod exchange_responses;
+mod expiry;
 mod modify;
 mod order;
+mod order_batcher;
+mod paper;
+mod reject_reason;
+mod retry;
+mod scale;
+mod signed_action;
+mod twap;
+mod validation;
 
+pub use account::{AccountFilter, CancelOutcome, CloseOutcome};
 pub use actions::*;
+pub use book_depth::{BookDepth, FillEstimate};
 pub use builder::*;
 pub use cancel::{ClientCancelRequest, ClientCancelRequestCloid};
+pub use chunked::MAX_BULK_ACTION_SIZE;
+pub use dead_mans_switch::DeadMansSwitch;
 pub use exchange_client::*;
 pub use exchange_responses::*;
+pub use expiry::ActionExpiry;
 pub use modify::{
     ClientModifyRequest, ClientModifyRequestCloid, ModifyRequest, ModifyRequestCloid,
 };
 pub use order::{
-    ClientLimit, ClientOrder, ClientOrderRequest, ClientTrigger, MarketCloseParams,
-    MarketOrderParams, Order,
+    ClientBracketOrder, ClientLimit, ClientOrder, ClientOrderRequest, ClientTpslLeg, ClientTrigger,
+    MarketCloseParams, MarketOrderParams, Order, OrderGrouping,
+};
+pub use order_batcher::{OrderBatcher, DEFAULT_MAX_BATCH_SIZE};
+pub use paper::{PaperExchangeClient, PaperOrder, PaperTrigger};
+pub use reject_reason::OrderRejectReason;
+pub use retry::RetryPolicy;
+pub use scale::{ScaleLevelStatus, ScaleOrder, ScaleOrderStatus, ScaleWeighting};
+pub use signed_action::{verify_payload, SignedAction};
+pub use twap::{ClientTwapRequest, TwapRequest};
+pub use validation::{
+    OrderValidationError, OrderValidator, ValidationMode, MAX_PRICE_SIGNIFICANT_FIGURES,
+    MIN_ORDER_NOTIONAL,
 };
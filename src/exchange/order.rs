@@ -1,11 +1,5 @@
-use crate::{
-    errors::Error,
-    helpers::{float_to_string_for_hashing, uuid_to_hex_string},
-    prelude::*,
-};
-use ethers::signers::LocalWallet;
+use crate::{helpers::uuid_to_hex_string, prelude::*, AssetResolver, HyperliquidSigner, Px, Sz};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 use uuid::Uuid;
 
 #[derive(Deserialize, Serialize, Clone, Debug)]
@@ -28,6 +22,18 @@ pub enum Order {
     Trigger(Trigger),
 }
 
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum OrderGrouping {
+    /// Orders are independent of each other
+    #[default]
+    Na,
+    /// Entry order followed by TP/SL legs that only activate once the entry fills
+    NormalTpsl,
+    /// TP/SL legs attached to the existing position, resized as the position changes
+    PositionTpsl,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct OrderRequest {
@@ -55,7 +61,7 @@ pub struct ClientLimit {
 #[derive(Debug)]
 pub struct ClientTrigger {
     pub is_market: bool,
-    pub trigger_px: f64,
+    pub trigger_px: Px,
     pub tpsl: String,
 }
 
@@ -63,21 +69,21 @@ pub struct ClientTrigger {
 pub struct MarketOrderParams<'a> {
     pub asset: &'a str,
     pub is_buy: bool,
-    pub sz: f64,
-    pub px: Option<f64>,
+    pub sz: Sz,
+    pub px: Option<Px>,
     pub slippage: Option<f64>,
     pub cloid: Option<Uuid>,
-    pub wallet: Option<&'a LocalWallet>,
+    pub wallet: Option<&'a dyn HyperliquidSigner>,
 }
 
 #[derive(Debug)]
 pub struct MarketCloseParams<'a> {
     pub asset: &'a str,
-    pub sz: Option<f64>,
-    pub px: Option<f64>,
+    pub sz: Option<Sz>,
+    pub px: Option<Px>,
     pub slippage: Option<f64>,
     pub cloid: Option<Uuid>,
-    pub wallet: Option<&'a LocalWallet>,
+    pub wallet: Option<&'a dyn HyperliquidSigner>,
 }
 
 #[derive(Debug)]
@@ -91,23 +97,72 @@ pub struct ClientOrderRequest {
     pub asset: String,
     pub is_buy: bool,
     pub reduce_only: bool,
-    pub limit_px: f64,
-    pub sz: f64,
+    pub limit_px: Px,
+    pub sz: Sz,
     pub cloid: Option<Uuid>,
     pub order_type: ClientOrder,
 }
 
+#[derive(Debug)]
+pub struct ClientTpslLeg {
+    pub trigger_px: Px,
+    /// Worst acceptable price once triggered, also used as the limit price for limit legs
+    pub limit_px: Px,
+    pub is_market: bool,
+    pub cloid: Option<Uuid>,
+}
+
+#[derive(Debug)]
+pub struct ClientBracketOrder {
+    pub entry: ClientOrderRequest,
+    pub take_profit: Option<ClientTpslLeg>,
+    pub stop_loss: Option<ClientTpslLeg>,
+}
+
+impl ClientBracketOrder {
+    pub(crate) fn into_orders(self) -> Vec<ClientOrderRequest> {
+        let Self {
+            entry,
+            take_profit,
+            stop_loss,
+        } = self;
+
+        let legs: Vec<ClientOrderRequest> = [(take_profit, "tp"), (stop_loss, "sl")]
+            .into_iter()
+            .filter_map(|(leg, tpsl)| {
+                leg.map(|leg| ClientOrderRequest {
+                    asset: entry.asset.clone(),
+                    is_buy: !entry.is_buy,
+                    reduce_only: true,
+                    limit_px: leg.limit_px,
+                    sz: entry.sz,
+                    cloid: leg.cloid,
+                    order_type: ClientOrder::Trigger(ClientTrigger {
+                        is_market: leg.is_market,
+                        trigger_px: leg.trigger_px,
+                        tpsl: tpsl.to_string(),
+                    }),
+                })
+            })
+            .collect();
+
+        let mut orders = vec![entry];
+        orders.extend(legs);
+        orders
+    }
+}
+
 impl ClientOrderRequest {
-    pub(crate) fn convert(self, coin_to_asset: &HashMap<String, u32>) -> Result<OrderRequest> {
+    pub(crate) fn convert(self, assets: &AssetResolver) -> Result<OrderRequest> {
         let order_type = match self.order_type {
             ClientOrder::Limit(limit) => Order::Limit(Limit { tif: limit.tif }),
             ClientOrder::Trigger(trigger) => Order::Trigger(Trigger {
-                trigger_px: float_to_string_for_hashing(trigger.trigger_px),
+                trigger_px: trigger.trigger_px.to_string(),
                 is_market: trigger.is_market,
                 tpsl: trigger.tpsl,
             }),
         };
-        let &asset = coin_to_asset.get(&self.asset).ok_or(Error::AssetNotFound)?;
+        let asset = assets.asset_index(&self.asset)?;
 
         let cloid = self.cloid.map(uuid_to_hex_string);
 
@@ -115,8 +170,8 @@ impl ClientOrderRequest {
             asset,
             is_buy: self.is_buy,
             reduce_only: self.reduce_only,
-            limit_px: float_to_string_for_hashing(self.limit_px),
-            sz: float_to_string_for_hashing(self.sz),
+            limit_px: self.limit_px.to_string(),
+            sz: self.sz.to_string(),
             order_type,
             cloid,
         })
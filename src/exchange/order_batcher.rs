@@ -0,0 +1,289 @@
+use crate::{
+    exchange::chunked::statuses, prelude::*, AssetResolver, ClientCancelRequest,
+    ClientModifyRequest, ClientOrderRequest, Error, ExchangeClient, ExchangeDataStatus,
+    ExchangeResponseStatus, OrderValidator,
+};
+use log::debug;
+use std::{sync::Arc, time::Duration};
+use tokio::{
+    sync::{mpsc, oneshot},
+    time::Instant,
+};
+
+/// Most requests collected into one window unless configured otherwise
+pub const DEFAULT_MAX_BATCH_SIZE: usize = 50;
+
+type Reply = oneshot::Sender<Result<ExchangeDataStatus>>;
+
+#[derive(Debug)]
+enum Request {
+    Order(ClientOrderRequest),
+    Cancel(ClientCancelRequest),
+    Modify(ClientModifyRequest),
+}
+
+/// Collects [`order`], [`cancel`] and [`modify`] calls made within a short window and sends
+/// them as one `bulk_order`, `bulk_cancel` and `bulk_modify` action each, signed by the
+/// client's wallet.
+///
+/// Every caller gets back the status of its own request. Requests naming an unknown asset or
+/// failing the client's [`ValidationMode`](crate::ValidationMode) fail on their own instead of
+/// taking the rest of the batch down with them, while an error rejecting the whole action is
+/// returned to every request in it. Within a window cancels are sent first, then modifies,
+/// then orders.
+///
+/// Clones feed the same batches. Batching stops once every clone is dropped, after sending
+/// what was already collected.
+///
+/// [`order`]: OrderBatcher::order
+/// [`cancel`]: OrderBatcher::cancel
+/// [`modify`]: OrderBatcher::modify
+#[derive(Debug, Clone)]
+pub struct OrderBatcher {
+    sender: mpsc::UnboundedSender<(Request, Reply)>,
+}
+
+impl OrderBatcher {
+    /// Starts batching on the current tokio runtime, sending each batch `window` after its
+    /// first request arrived.
+    pub fn new(client: ExchangeClient, window: Duration) -> OrderBatcher {
+        OrderBatcher::with_max_batch_size(client, window, DEFAULT_MAX_BATCH_SIZE)
+    }
+
+    /// Like [`new`](OrderBatcher::new), but sends a batch early once it holds
+    /// `max_batch_size` requests.
+    pub fn with_max_batch_size(
+        client: ExchangeClient,
+        window: Duration,
+        max_batch_size: usize,
+    ) -> OrderBatcher {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(run(client, window, max_batch_size.max(1), receiver));
+        OrderBatcher { sender }
+    }
+
+    pub async fn order(&self, order: ClientOrderRequest) -> Result<ExchangeDataStatus> {
+        self.submit(Request::Order(order)).await
+    }
+
+    pub async fn cancel(&self, cancel: ClientCancelRequest) -> Result<ExchangeDataStatus> {
+        self.submit(Request::Cancel(cancel)).await
+    }
+
+    pub async fn modify(&self, modify: ClientModifyRequest) -> Result<ExchangeDataStatus> {
+        self.submit(Request::Modify(modify)).await
+    }
+
+    async fn submit(&self, request: Request) -> Result<ExchangeDataStatus> {
+        let (reply, response) = oneshot::channel();
+        self.sender
+            .send((request, reply))
+            .map_err(|_| Error::GenericRequest("Order batcher stopped".to_string()))?;
+        response
+            .await
+            .map_err(|_| Error::GenericRequest("Order batcher dropped the request".to_string()))?
+    }
+}
+
+async fn run(
+    client: ExchangeClient,
+    window: Duration,
+    max_batch_size: usize,
+    mut receiver: mpsc::UnboundedReceiver<(Request, Reply)>,
+) {
+    while let Some(first) = receiver.recv().await {
+        let deadline = Instant::now() + window;
+        let mut batch = vec![first];
+        while batch.len() < max_batch_size {
+            match tokio::time::timeout_at(deadline, receiver.recv()).await {
+                Ok(Some(request)) => batch.push(request),
+                Ok(None) | Err(_) => break,
+            }
+        }
+        debug!("Sending batch of {} requests", batch.len());
+        send_batch(&client, batch).await;
+    }
+}
+
+async fn send_batch(client: &ExchangeClient, batch: Vec<(Request, Reply)>) {
+    let mut orders = Vec::new();
+    let mut cancels = Vec::new();
+    let mut modifies = Vec::new();
+    for (request, reply) in batch {
+        match request {
+            Request::Order(order) => orders.push((order, reply)),
+            Request::Cancel(cancel) => cancels.push((cancel, reply)),
+            Request::Modify(modify) => modifies.push((modify, reply)),
+        }
+    }
+
+    let names = orders
+        .iter()
+        .map(|(order, _)| order.asset.as_str())
+        .chain(cancels.iter().map(|(cancel, _)| cancel.asset.as_str()))
+        .chain(
+            modifies
+                .iter()
+                .map(|(modify, _)| modify.order.asset.as_str()),
+        );
+    let assets = match client.meta_cache().assets_for(names).await {
+        Ok(assets) => assets,
+        Err(e) => {
+            let replies = orders.into_iter().map(|(_, reply)| reply);
+            let replies = replies.chain(cancels.into_iter().map(|(_, reply)| reply));
+            let replies = replies.chain(modifies.into_iter().map(|(_, reply)| reply));
+            for reply in replies {
+                let _ = reply.send(Err(e.clone()));
+            }
+            return;
+        }
+    };
+    let check = |order: ClientOrderRequest| check_order(client, &assets, order);
+
+    let (cancels, replies) = checked(cancels, |cancel| {
+        assets.resolve(&cancel.asset)?;
+        Ok(cancel)
+    });
+    if !cancels.is_empty() {
+        deliver(replies, client.bulk_cancel(cancels, None).await);
+    }
+
+    let (modifies, replies) = checked(modifies, |modify| {
+        Ok(ClientModifyRequest {
+            oid: modify.oid,
+            order: check(modify.order)?,
+        })
+    });
+    if !modifies.is_empty() {
+        deliver(replies, client.bulk_modify(modifies, None).await);
+    }
+
+    let (orders, replies) = checked(orders, check);
+    if !orders.is_empty() {
+        deliver(replies, client.bulk_order(orders, None).await);
+    }
+}
+
+fn check_order(
+    client: &ExchangeClient,
+    assets: &Arc<AssetResolver>,
+    order: ClientOrderRequest,
+) -> Result<ClientOrderRequest> {
+    assets.resolve(&order.asset)?;
+    match client.order_validation() {
+        Some(mode) => Ok(OrderValidator::from_assets(assets.clone())
+            .apply(vec![order], mode)?
+            .remove(0)),
+        None => Ok(order),
+    }
+}
+
+/// Requests passing `check`, failing the others' callers right away.
+fn checked<T>(requests: Vec<(T, Reply)>, check: impl Fn(T) -> Result<T>) -> (Vec<T>, Vec<Reply>) {
+    requests
+        .into_iter()
+        .filter_map(|(request, reply)| match check(request) {
+            Ok(request) => Some((request, reply)),
+            Err(e) => {
+                let _ = reply.send(Err(e));
+                None
+            }
+        })
+        .unzip()
+}
+
+/// Hands each caller the status at its request's position in the action.
+fn deliver(replies: Vec<Reply>, response: Result<ExchangeResponseStatus>) {
+    let statuses = statuses(response, replies.len());
+    for (reply, status) in replies.into_iter().zip(statuses) {
+        let _ = reply.send(status);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_server, BaseUrl, ClientLimit, ClientOrder};
+    use serde_json::Value;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Exchange resting every order with its limit price as oid and accepting every cancel
+    async fn serve_exchange(actions: Arc<AtomicUsize>) -> BaseUrl {
+        let meta = r#"{"universe":[{"name":"ETH","szDecimals":4}]}"#;
+        test_server::serve_exchange(meta, test_server::EMPTY_SPOT_META, move |_, body| {
+            actions.fetch_add(1, Ordering::SeqCst);
+
+            let body: Value = serde_json::from_str(body).unwrap();
+            let action = &body["action"];
+            let statuses: Vec<Value> = match action["type"].as_str().unwrap() {
+                "order" => action["orders"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|order| {
+                        let oid: u64 = order["p"].as_str().unwrap().parse().unwrap();
+                        serde_json::json!({"resting": {"oid": oid}})
+                    })
+                    .collect(),
+                "cancel" => action["cancels"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|_| "success".into())
+                    .collect(),
+                other => panic!("unexpected action {other}"),
+            };
+            serde_json::json!({
+                "status": "ok",
+                "response": {"type": "order", "data": {"statuses": statuses}}
+            })
+            .to_string()
+        })
+        .await
+    }
+
+    fn order(asset: &str, limit_px: &str) -> ClientOrderRequest {
+        ClientOrderRequest {
+            asset: asset.to_string(),
+            is_buy: true,
+            reduce_only: false,
+            limit_px: limit_px.parse().unwrap(),
+            sz: "1".parse().unwrap(),
+            cloid: None,
+            order_type: ClientOrder::Limit(ClientLimit {
+                tif: "Gtc".to_string(),
+            }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_batches_calls_and_routes_statuses() -> Result<()> {
+        let actions = Arc::new(AtomicUsize::new(0));
+        let base_url = serve_exchange(actions.clone()).await;
+        let client = test_server::test_client(base_url).await?;
+        let batcher = OrderBatcher::new(client, Duration::from_millis(50));
+
+        let (first, second, third, unknown, cancel) = tokio::join!(
+            batcher.order(order("ETH", "1801")),
+            batcher.order(order("ETH", "1802")),
+            batcher.order(order("ETH", "1803")),
+            batcher.order(order("TYPO", "1804")),
+            batcher.cancel(ClientCancelRequest {
+                asset: "ETH".to_string(),
+                oid: 7,
+            }),
+        );
+
+        for (status, oid) in [(first, 1801), (second, 1802), (third, 1803)] {
+            match status? {
+                ExchangeDataStatus::Resting(resting) => assert_eq!(resting.oid, oid),
+                other => panic!("expected resting order, got {other:?}"),
+            }
+        }
+        assert!(matches!(unknown, Err(Error::AssetNotFound)));
+        assert!(matches!(cancel?, ExchangeDataStatus::Success));
+        // One cancel and one order action
+        assert_eq!(actions.load(Ordering::SeqCst), 2);
+        Ok(())
+    }
+}
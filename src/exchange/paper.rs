@@ -0,0 +1,1055 @@
+use crate::{
+    consts::EPSILON, exchange::chunked::statuses, helpers::now_timestamp_ms, prelude::*, AssetInfo,
+    AssetResolver, BookDepth, ClientCancelRequest, ClientCancelRequestCloid, ClientLimit,
+    ClientModifyRequest, ClientOrder, ClientOrderRequest, Error, ExchangeDataStatus,
+    ExchangeDataStatuses, ExchangeResponse, ExchangeResponseStatus, FilledOrder, HyperliquidSigner,
+    MarketCloseParams, MarketOrderParams, Message, OrderValidationError, OrderValidator, Px,
+    RestingOrder, ScaleLevelStatus, ScaleOrder, ScaleOrderStatus, Sz, TradeInfo, User, UserData,
+};
+use log::debug;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+use tokio::{
+    sync::mpsc::{UnboundedReceiver, UnboundedSender},
+    task::JoinHandle,
+};
+use uuid::Uuid;
+
+/// Fee rates of the exchange's base tier
+const TAKER_FEE_RATE: f64 = 0.00045;
+const MAKER_FEE_RATE: f64 = 0.00015;
+
+/// Slippage of market orders when none is given, as in [`ExchangeClient::market_open`]
+///
+/// [`ExchangeClient::market_open`]: crate::ExchangeClient::market_open
+const DEFAULT_SLIPPAGE: f64 = 0.05;
+
+const ZERO_HASH: &str = "0x0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Order resting on, or waiting for its trigger in, a [`PaperExchangeClient`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaperOrder {
+    pub oid: u64,
+    pub cloid: Option<Uuid>,
+    pub coin: String,
+    pub is_buy: bool,
+    pub limit_px: Px,
+    /// Size still open
+    pub sz: f64,
+    pub orig_sz: f64,
+    pub reduce_only: bool,
+    pub tif: String,
+    /// Set while the order waits for its trigger price
+    pub trigger: Option<PaperTrigger>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaperTrigger {
+    pub trigger_px: Px,
+    pub is_market: bool,
+    /// Take profit if true, stop loss otherwise
+    pub is_tp: bool,
+}
+
+impl PaperTrigger {
+    fn is_triggered(&self, is_buy: bool, px: f64) -> bool {
+        let trigger_px = self.trigger_px.to_f64();
+        // Take profits trigger when the price moves in the order's favour, stops against it
+        match (is_buy, self.is_tp) {
+            (true, true) | (false, false) => px <= trigger_px,
+            (true, false) | (false, true) => px >= trigger_px,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Position {
+    sz: f64,
+    entry_px: f64,
+}
+
+#[derive(Debug)]
+struct PaperState {
+    books: HashMap<String, BookDepth>,
+    /// Open orders in the order they were placed, which is also their queue priority
+    orders: Vec<PaperOrder>,
+    positions: HashMap<String, Position>,
+    next_oid: u64,
+    next_tid: u64,
+    fills: Vec<TradeInfo>,
+}
+
+/// Simulated exchange with the order API of [`ExchangeClient`](crate::ExchangeClient), for
+/// running strategies without funds or a testnet account.
+///
+/// Orders are checked against the asset rules, then matched against a book kept from the
+/// `L2Book` messages passed to [`apply`](PaperExchangeClient::apply) or
+/// [`spawn_feed`](PaperExchangeClient::spawn_feed). What doesn't fill rests, and fills as the
+/// maker once `Trades` messages trade through its price or the book crosses it. Trigger
+/// orders wait for the traded or mid price to reach their trigger price. Rejections carry
+/// the exchange's error texts, so [`OrderRejectReason`](crate::OrderRejectReason) parses them.
+///
+/// Fills are sent as [`Message::User`] events to the channel given to
+/// [`with_fills`](PaperExchangeClient::with_fills). The `wallet` arguments are ignored.
+///
+/// Implements [`ExchangeApi`](crate::ExchangeApi), so strategies written against it run on
+/// paper unchanged as long as they only trade.
+///
+/// Clones share the same simulated account.
+#[derive(Debug, Clone)]
+pub struct PaperExchangeClient {
+    assets: Arc<AssetResolver>,
+    state: Arc<Mutex<PaperState>>,
+    fills_sender: Option<UnboundedSender<Message>>,
+}
+
+impl PaperExchangeClient {
+    pub fn new(assets: impl Into<Arc<AssetResolver>>) -> PaperExchangeClient {
+        PaperExchangeClient {
+            assets: assets.into(),
+            state: Arc::new(Mutex::new(PaperState {
+                books: HashMap::new(),
+                orders: Vec::new(),
+                positions: HashMap::new(),
+                next_oid: 1,
+                next_tid: 1,
+                fills: Vec::new(),
+            })),
+            fills_sender: None,
+        }
+    }
+
+    /// Returns a client sending every simulated fill to `sender`.
+    pub fn with_fills(&self, sender: UnboundedSender<Message>) -> PaperExchangeClient {
+        PaperExchangeClient {
+            fills_sender: Some(sender),
+            ..self.clone()
+        }
+    }
+
+    /// Updates the simulated market from a `L2Book` or `Trades` message, filling the resting
+    /// and trigger orders it reaches. Other messages are ignored.
+    pub fn apply(&self, message: &Message) -> Result<()> {
+        let fills = {
+            let mut state = self.lock()?;
+            match message {
+                Message::L2Book(book) => {
+                    let coin = book.data.coin.clone();
+                    state
+                        .books
+                        .insert(coin.clone(), BookDepth::try_from(&book.data)?);
+                    state.match_book(&coin);
+                    if let Some(mid) = state.books[&coin].mid_px() {
+                        self.trigger(&mut state, &coin, mid);
+                    }
+                }
+                Message::Trades(trades) => {
+                    for trade in &trades.data {
+                        let px: f64 = parse(&trade.px)?;
+                        let sz: f64 = parse(&trade.sz)?;
+                        state.match_trade(&trade.coin, trade.side == "B", px, sz);
+                        self.trigger(&mut state, &trade.coin, px);
+                    }
+                }
+                _ => {}
+            }
+            std::mem::take(&mut state.fills)
+        };
+        self.send_fills(fills);
+        Ok(())
+    }
+
+    /// Applies every message from `receiver`, e.g. one subscribed to `L2Book` and `Trades`
+    /// with [`InfoClient::subscribe`](crate::InfoClient::subscribe), until it closes.
+    pub fn spawn_feed(&self, mut receiver: UnboundedReceiver<Message>) -> JoinHandle<()> {
+        let client = self.clone();
+        tokio::spawn(async move {
+            while let Some(message) = receiver.recv().await {
+                if let Err(e) = client.apply(&message) {
+                    debug!("Ignoring message the paper exchange can't apply: {e}");
+                }
+            }
+        })
+    }
+
+    pub fn open_orders(&self) -> Result<Vec<PaperOrder>> {
+        Ok(self.lock()?.orders.clone())
+    }
+
+    /// Signed size of the position in `coin`, negative when short.
+    pub fn position(&self, coin: &str) -> Result<f64> {
+        Ok(self.lock()?.positions.get(coin).map_or(0.0, |pos| pos.sz))
+    }
+
+    pub fn book(&self, coin: &str) -> Result<Option<BookDepth>> {
+        Ok(self.lock()?.books.get(coin).cloned())
+    }
+
+    pub async fn order(
+        &self,
+        order: ClientOrderRequest,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        self.bulk_order(vec![order], wallet).await
+    }
+
+    pub async fn bulk_order(
+        &self,
+        orders: Vec<ClientOrderRequest>,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        let infos = self.resolve_all(orders.iter().map(|order| order.asset.as_str()))?;
+        let (statuses, fills) = {
+            let mut state = self.lock()?;
+            let statuses = orders
+                .into_iter()
+                .zip(infos)
+                .map(|(order, info)| self.place(&mut state, order, info, None))
+                .collect();
+            (statuses, std::mem::take(&mut state.fills))
+        };
+        self.send_fills(fills);
+        Ok(response("order", statuses))
+    }
+
+    /// Places the levels of `scale` like [`ExchangeClient::scale_order`].
+    ///
+    /// [`ExchangeClient::scale_order`]: crate::ExchangeClient::scale_order
+    pub async fn scale_order(
+        &self,
+        scale: ScaleOrder,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ScaleOrderStatus> {
+        let orders = scale.orders(&OrderValidator::from_assets(self.assets.clone()))?;
+        let placed: Vec<_> = orders
+            .iter()
+            .map(|order| (order.limit_px, order.sz))
+            .collect();
+        let response = self.bulk_order(orders, wallet).await;
+        let statuses = statuses(response, placed.len());
+        let levels = placed
+            .into_iter()
+            .zip(statuses)
+            .map(|((limit_px, sz), status)| ScaleLevelStatus {
+                limit_px,
+                sz,
+                status,
+            })
+            .collect();
+        Ok(ScaleOrderStatus { levels })
+    }
+
+    pub async fn cancel(
+        &self,
+        cancel: ClientCancelRequest,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        self.bulk_cancel(vec![cancel], wallet).await
+    }
+
+    pub async fn bulk_cancel(
+        &self,
+        cancels: Vec<ClientCancelRequest>,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        let infos = self.resolve_all(cancels.iter().map(|cancel| cancel.asset.as_str()))?;
+        let mut state = self.lock()?;
+        let statuses = cancels
+            .iter()
+            .zip(infos)
+            .map(|(cancel, info)| {
+                let canceled = state.cancel(info, |order| order.oid == cancel.oid);
+                cancel_status(canceled, info)
+            })
+            .collect();
+        Ok(response("cancel", statuses))
+    }
+
+    pub async fn cancel_by_cloid(
+        &self,
+        cancel: ClientCancelRequestCloid,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        self.bulk_cancel_by_cloid(vec![cancel], wallet).await
+    }
+
+    pub async fn bulk_cancel_by_cloid(
+        &self,
+        cancels: Vec<ClientCancelRequestCloid>,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        let infos = self.resolve_all(cancels.iter().map(|cancel| cancel.asset.as_str()))?;
+        let mut state = self.lock()?;
+        let statuses = cancels
+            .iter()
+            .zip(infos)
+            .map(|(cancel, info)| {
+                let canceled = state.cancel(info, |order| order.cloid == Some(cancel.cloid));
+                cancel_status(canceled, info)
+            })
+            .collect();
+        Ok(response("cancel", statuses))
+    }
+
+    pub async fn modify(
+        &self,
+        modify: ClientModifyRequest,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        self.bulk_modify(vec![modify], wallet).await
+    }
+
+    /// Replaces resting orders, keeping their oid. The new order is matched like a new one.
+    pub async fn bulk_modify(
+        &self,
+        modifies: Vec<ClientModifyRequest>,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        let infos = self.resolve_all(modifies.iter().map(|modify| modify.order.asset.as_str()))?;
+        let (statuses, fills) = {
+            let mut state = self.lock()?;
+            let statuses = modifies
+                .into_iter()
+                .zip(infos)
+                .map(|(modify, info)| {
+                    let oid = modify.oid;
+                    let Some(index) = state
+                        .orders
+                        .iter()
+                        .position(|order| order.coin == info.coin && order.oid == oid)
+                    else {
+                        return ExchangeDataStatus::Error(
+                            "Cannot modify canceled or filled order".to_string(),
+                        );
+                    };
+                    let original = state.orders.remove(index);
+                    let status = self.place(&mut state, modify.order, info, Some(oid));
+                    if let ExchangeDataStatus::Error(_) = status {
+                        // The exchange rejects the modify and leaves the order as it was
+                        let index = index.min(state.orders.len());
+                        state.orders.insert(index, original);
+                    }
+                    status
+                })
+                .collect();
+            (statuses, std::mem::take(&mut state.fills))
+        };
+        self.send_fills(fills);
+        Ok(response("order", statuses))
+    }
+
+    /// Immediate or cancel order priced `slippage` away from `px`, or from the mid of the
+    /// simulated book.
+    pub async fn market_open(
+        &self,
+        params: MarketOrderParams<'_>,
+    ) -> Result<ExchangeResponseStatus> {
+        let order = self.market_order(params, false)?;
+        self.order(order, None).await
+    }
+
+    /// Closes the position in the asset, or `sz` of it, with a reduce only market order.
+    pub async fn market_close(
+        &self,
+        params: MarketCloseParams<'_>,
+    ) -> Result<ExchangeResponseStatus> {
+        let info = self.assets.resolve(params.asset)?;
+        let position = self.position(&info.coin)?;
+        if position.abs() <= EPSILON {
+            return Err(Error::AssetNotFound);
+        }
+        let sz = match params.sz {
+            Some(sz) => sz,
+            None => Sz::try_from(position.abs())?,
+        };
+        let params = MarketOrderParams {
+            asset: params.asset,
+            is_buy: position < 0.0,
+            sz,
+            px: params.px,
+            slippage: params.slippage,
+            cloid: params.cloid,
+            wallet: None,
+        };
+        let order = self.market_order(params, true)?;
+        self.order(order, None).await
+    }
+
+    fn market_order(
+        &self,
+        params: MarketOrderParams<'_>,
+        reduce_only: bool,
+    ) -> Result<ClientOrderRequest> {
+        let MarketOrderParams {
+            asset,
+            is_buy,
+            sz,
+            px,
+            slippage,
+            cloid,
+            ..
+        } = params;
+        let info = self.assets.resolve(asset)?;
+        let reference_px = match px {
+            Some(px) => px.to_f64(),
+            None => self
+                .book(&info.coin)?
+                .and_then(|book| book.mid_px())
+                .ok_or(Error::InsufficientLiquidity {
+                    requested: sz.to_f64(),
+                    available: 0.0,
+                })?,
+        };
+        let slippage = slippage.unwrap_or(DEFAULT_SLIPPAGE);
+        let limit_px = if is_buy {
+            reference_px * (1.0 + slippage)
+        } else {
+            reference_px * (1.0 - slippage)
+        };
+
+        let order = ClientOrderRequest {
+            asset: asset.to_string(),
+            is_buy,
+            reduce_only,
+            limit_px: Px::try_from(limit_px)?,
+            sz,
+            cloid,
+            order_type: ClientOrder::Limit(ClientLimit {
+                tif: "Ioc".to_string(),
+            }),
+        };
+        OrderValidator::from_assets(self.assets.clone())
+            .round(order)
+            .map_err(|errors| Error::OrderValidation { index: 0, errors })
+    }
+
+    fn resolve_all<'a>(&self, names: impl Iterator<Item = &'a str>) -> Result<Vec<&AssetInfo>> {
+        names.map(|name| self.assets.resolve(name)).collect()
+    }
+
+    fn place(
+        &self,
+        state: &mut PaperState,
+        order: ClientOrderRequest,
+        info: &AssetInfo,
+        oid: Option<u64>,
+    ) -> ExchangeDataStatus {
+        let validator = OrderValidator::from_assets(self.assets.clone());
+        if let Err(errors) = validator.validate(&order) {
+            return ExchangeDataStatus::Error(rejection(&errors, info));
+        }
+
+        let (tif, trigger) = match order.order_type {
+            ClientOrder::Limit(limit) => (limit.tif, None),
+            ClientOrder::Trigger(trigger) => (
+                if trigger.is_market { "Ioc" } else { "Gtc" }.to_string(),
+                Some(PaperTrigger {
+                    trigger_px: trigger.trigger_px,
+                    is_market: trigger.is_market,
+                    is_tp: trigger.tpsl == "tp",
+                }),
+            ),
+        };
+        let oid = oid.unwrap_or_else(|| {
+            state.next_oid += 1;
+            state.next_oid - 1
+        });
+        let order = PaperOrder {
+            oid,
+            cloid: order.cloid,
+            coin: info.coin.clone(),
+            is_buy: order.is_buy,
+            limit_px: order.limit_px,
+            sz: order.sz.to_f64(),
+            orig_sz: order.sz.to_f64(),
+            reduce_only: order.reduce_only,
+            tif,
+            trigger,
+        };
+
+        if order.trigger.is_some() {
+            state.orders.push(order);
+            return ExchangeDataStatus::WaitingForTrigger;
+        }
+        state.execute(order, info.id.to_wire())
+    }
+
+    /// Turns the trigger orders of `coin` that `px` reaches into regular orders.
+    fn trigger(&self, state: &mut PaperState, coin: &str, px: f64) {
+        let (triggered, waiting) =
+            std::mem::take(&mut state.orders)
+                .into_iter()
+                .partition(|order: &PaperOrder| {
+                    order.coin == coin
+                        && order
+                            .trigger
+                            .as_ref()
+                            .is_some_and(|trigger| trigger.is_triggered(order.is_buy, px))
+                });
+        state.orders = waiting;
+
+        let asset = self
+            .assets
+            .resolve(coin)
+            .map_or(0, |info| info.id.to_wire());
+        for mut order in triggered {
+            debug!("Order {} triggered at {px}", order.oid);
+            order.trigger = None;
+            state.execute(order, asset);
+        }
+    }
+
+    fn send_fills(&self, fills: Vec<TradeInfo>) {
+        if let (Some(sender), false) = (&self.fills_sender, fills.is_empty()) {
+            let _ = sender.send(Message::User(User {
+                data: UserData::Fills(fills),
+            }));
+        }
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, PaperState>> {
+        self.state
+            .lock()
+            .map_err(|e| Error::GenericRequest(e.to_string()))
+    }
+}
+
+impl PaperState {
+    /// Matches a new or triggered order against the book and rests what's left if its time
+    /// in force allows.
+    fn execute(&mut self, mut order: PaperOrder, asset: u32) -> ExchangeDataStatus {
+        if order.reduce_only {
+            let position = self.positions.get(&order.coin).map_or(0.0, |pos| pos.sz);
+            let reduces =
+                (order.is_buy && position < -EPSILON) || (!order.is_buy && position > EPSILON);
+            if !reduces {
+                return ExchangeDataStatus::Error(format!(
+                    "Reduce only order would increase position. asset={asset}"
+                ));
+            }
+            order.sz = order.sz.min(position.abs());
+        }
+
+        let (is_buy, limit_px) = (order.is_buy, order.limit_px.to_f64());
+        let book = self.books.entry(order.coin.clone()).or_default();
+        let crosses = move |px: f64| {
+            if is_buy {
+                px <= limit_px
+            } else {
+                px >= limit_px
+            }
+        };
+        let best = if order.is_buy {
+            book.asks.first()
+        } else {
+            book.bids.first()
+        };
+        let would_match = best.is_some_and(|(px, _)| crosses(px.to_f64()));
+
+        if order.tif == "Alo" && would_match {
+            let bbo = |side: &[(Px, Sz)]| {
+                side.first()
+                    .map_or("None".to_string(), |(px, _)| px.to_string())
+            };
+            return ExchangeDataStatus::Error(format!(
+                "Post only order would have immediately matched, bbo was {}@{}. asset={asset}",
+                bbo(&book.bids),
+                bbo(&book.asks)
+            ));
+        }
+
+        // Takes liquidity from the simulated book, so orders placed before the next update
+        // don't fill against the same levels twice
+        let mut taken = Vec::new();
+        let side = if order.is_buy {
+            &mut book.asks
+        } else {
+            &mut book.bids
+        };
+        while order.sz > EPSILON {
+            let Some((px, level_sz)) = side.first_mut() else {
+                break;
+            };
+            let px = px.to_f64();
+            if !crosses(px) {
+                break;
+            }
+            let take = order.sz.min(level_sz.to_f64());
+            let left = level_sz.to_f64() - take;
+            if left <= EPSILON {
+                side.remove(0);
+            } else if let Ok(left) = Sz::try_from(left) {
+                *level_sz = left;
+            }
+            order.sz -= take;
+            taken.push((px, take));
+        }
+
+        let (mut total_sz, mut notional) = (0.0, 0.0);
+        for (px, sz) in taken {
+            self.fill(&order, px, sz, true);
+            total_sz += sz;
+            notional += px * sz;
+        }
+
+        let filled = (total_sz > EPSILON).then(|| {
+            ExchangeDataStatus::Filled(FilledOrder {
+                total_sz: decimal_string(total_sz),
+                avg_px: decimal_string(notional / total_sz),
+                oid: order.oid,
+            })
+        });
+        if order.sz <= EPSILON || order.tif == "Ioc" {
+            return filled.unwrap_or_else(|| {
+                ExchangeDataStatus::Error(format!(
+                    "Order could not immediately match against any resting orders. asset={asset}"
+                ))
+            });
+        }
+
+        let oid = order.oid;
+        self.orders.push(order);
+        filled.unwrap_or(ExchangeDataStatus::Resting(RestingOrder { oid }))
+    }
+
+    /// Removes the first resting order of `info`'s coin matching `is_target`.
+    fn cancel(
+        &mut self,
+        info: &AssetInfo,
+        is_target: impl Fn(&PaperOrder) -> bool,
+    ) -> Option<PaperOrder> {
+        let index = self
+            .orders
+            .iter()
+            .position(|order| order.coin == info.coin && is_target(order))?;
+        Some(self.orders.remove(index))
+    }
+
+    /// Fills resting orders of `coin` the book crossed, as the maker at their limit price.
+    fn match_book(&mut self, coin: &str) {
+        let Some(book) = self.books.get(coin) else {
+            return;
+        };
+        let best_bid = book.bids.first().map(|(px, _)| px.to_f64());
+        let best_ask = book.asks.first().map(|(px, _)| px.to_f64());
+        let mut fills = Vec::new();
+        for order in self.resting_mut(coin) {
+            let limit_px = order.limit_px.to_f64();
+            let crossed = if order.is_buy {
+                best_ask.is_some_and(|ask| ask <= limit_px)
+            } else {
+                best_bid.is_some_and(|bid| bid >= limit_px)
+            };
+            if crossed {
+                fills.push((order.clone(), order.sz));
+                order.sz = 0.0;
+            }
+        }
+        self.settle(fills);
+    }
+
+    /// Fills resting orders of `coin` a trade of `sz` at `px` traded through, in queue order.
+    fn match_trade(&mut self, coin: &str, is_buy_taker: bool, px: f64, mut sz: f64) {
+        let mut fills = Vec::new();
+        for order in self.resting_mut(coin) {
+            if sz <= EPSILON {
+                break;
+            }
+            let limit_px = order.limit_px.to_f64();
+            let reached = if order.is_buy {
+                !is_buy_taker && px <= limit_px
+            } else {
+                is_buy_taker && px >= limit_px
+            };
+            if reached {
+                let take = order.sz.min(sz);
+                fills.push((order.clone(), take));
+                order.sz -= take;
+                sz -= take;
+            }
+        }
+        self.settle(fills);
+    }
+
+    fn resting_mut<'a>(&'a mut self, coin: &'a str) -> impl Iterator<Item = &'a mut PaperOrder> {
+        self.orders
+            .iter_mut()
+            .filter(move |order| order.coin == coin && order.trigger.is_none())
+    }
+
+    fn settle(&mut self, fills: Vec<(PaperOrder, f64)>) {
+        for (order, sz) in fills {
+            self.fill(&order, order.limit_px.to_f64(), sz, false);
+        }
+        self.orders.retain(|order| order.sz > EPSILON);
+    }
+
+    fn fill(&mut self, order: &PaperOrder, px: f64, sz: f64, crossed: bool) {
+        let position = self.positions.entry(order.coin.clone()).or_default();
+        let start = *position;
+        let signed_sz = if order.is_buy { sz } else { -sz };
+        let is_spot = order.coin.starts_with('@') || order.coin.contains('/');
+
+        let mut closed_pnl = 0.0;
+        if start.sz * signed_sz >= 0.0 {
+            let new_sz = start.sz + signed_sz;
+            position.entry_px = (start.sz.abs() * start.entry_px + sz * px) / new_sz.abs();
+            position.sz = new_sz;
+        } else {
+            let closing = sz.min(start.sz.abs());
+            closed_pnl = closing * (px - start.entry_px) * start.sz.signum();
+            position.sz = start.sz + signed_sz;
+            if position.sz.abs() <= EPSILON {
+                *position = Position::default();
+            } else if sz > closing {
+                position.entry_px = px;
+            }
+        }
+
+        let dir = match (is_spot, start.sz, position.sz) {
+            (true, _, _) if order.is_buy => "Buy",
+            (true, _, _) => "Sell",
+            (false, start, end) if start > EPSILON && end < -EPSILON => "Long > Short",
+            (false, start, end) if start < -EPSILON && end > EPSILON => "Short > Long",
+            (false, start, _) if start > EPSILON && !order.is_buy => "Close Long",
+            (false, start, _) if start < -EPSILON && order.is_buy => "Close Short",
+            _ if order.is_buy => "Open Long",
+            _ => "Open Short",
+        };
+        let fee_rate = if crossed {
+            TAKER_FEE_RATE
+        } else {
+            MAKER_FEE_RATE
+        };
+
+        self.next_tid += 1;
+        self.fills.push(TradeInfo {
+            coin: order.coin.clone(),
+            side: if order.is_buy { "B" } else { "A" }.to_string(),
+            px: decimal_string(px),
+            sz: decimal_string(sz),
+            time: now_timestamp_ms(),
+            hash: ZERO_HASH.to_string(),
+            start_position: decimal_string(start.sz),
+            dir: dir.to_string(),
+            closed_pnl: decimal_string(closed_pnl),
+            oid: order.oid,
+            cloid: order.cloid.map(|cloid| format!("0x{}", cloid.simple())),
+            crossed,
+            fee: decimal_string(px * sz * fee_rate),
+            fee_token: "USDC".to_string(),
+            tid: self.next_tid,
+        });
+    }
+}
+
+/// Text the exchange rejects orders breaking these rules with.
+fn rejection(errors: &[OrderValidationError], info: &AssetInfo) -> String {
+    let asset = info.id.to_wire();
+    match errors.first() {
+        Some(OrderValidationError::PriceDecimals { .. })
+        | Some(OrderValidationError::PriceSignificantFigures { .. }) => {
+            format!("Order has invalid price. asset={asset}")
+        }
+        Some(OrderValidationError::BelowMinNotional { min_notional, .. }) => {
+            format!("Order must have minimum value of ${min_notional}. asset={asset}")
+        }
+        _ => format!("Order has invalid size. asset={asset}"),
+    }
+}
+
+fn cancel_status(canceled: Option<PaperOrder>, info: &AssetInfo) -> ExchangeDataStatus {
+    match canceled {
+        Some(_) => ExchangeDataStatus::Success,
+        None => ExchangeDataStatus::Error(format!(
+            "Order was never placed, already canceled, or filled. asset={}",
+            info.id.to_wire()
+        )),
+    }
+}
+
+fn response(response_type: &str, statuses: Vec<ExchangeDataStatus>) -> ExchangeResponseStatus {
+    ExchangeResponseStatus::Ok(ExchangeResponse {
+        response_type: response_type.to_string(),
+        data: Some(ExchangeDataStatuses { statuses }),
+    })
+}
+
+fn parse(value: &str) -> Result<f64> {
+    value.parse().map_err(|_| Error::FloatStringParse)
+}
+
+/// Shortest decimal form of `value`, without the noise of float arithmetic
+fn decimal_string(value: f64) -> String {
+    let rounded = (value * 1e8).round() / 1e8;
+    if rounded == 0.0 {
+        "0".to_string()
+    } else {
+        rounded.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        meta::SpotMeta, ClientTrigger, ExchangeApi, L2Book, Meta, OrderRejectReason, Trades,
+    };
+    use tokio::sync::mpsc::unbounded_channel;
+
+    fn client() -> PaperExchangeClient {
+        let meta: Meta = serde_json::from_str(
+            r#"{"universe":[{"name":"BTC","szDecimals":5},{"name":"ETH","szDecimals":4}]}"#,
+        )
+        .unwrap();
+        let spot_meta: SpotMeta = serde_json::from_str(r#"{"universe":[],"tokens":[]}"#).unwrap();
+        PaperExchangeClient::new(AssetResolver::new(&meta, &spot_meta))
+    }
+
+    fn book(bids: &str, asks: &str) -> Message {
+        Message::L2Book(
+            serde_json::from_str::<L2Book>(&format!(
+                r#"{{"data":{{"coin":"ETH","time":1,"levels":[[{bids}],[{asks}]]}}}}"#
+            ))
+            .unwrap(),
+        )
+    }
+
+    fn trade(side: &str, px: &str, sz: &str) -> Message {
+        Message::Trades(
+            serde_json::from_str::<Trades>(&format!(
+                r#"{{"data":[{{"coin":"ETH","side":"{side}","px":"{px}","sz":"{sz}","time":1,"hash":"0x","tid":1}}]}}"#
+            ))
+            .unwrap(),
+        )
+    }
+
+    fn limit(is_buy: bool, limit_px: &str, sz: &str, tif: &str) -> ClientOrderRequest {
+        ClientOrderRequest {
+            asset: "ETH".to_string(),
+            is_buy,
+            reduce_only: false,
+            limit_px: limit_px.parse().unwrap(),
+            sz: sz.parse().unwrap(),
+            cloid: None,
+            order_type: ClientOrder::Limit(ClientLimit {
+                tif: tif.to_string(),
+            }),
+        }
+    }
+
+    fn status(response: ExchangeResponseStatus) -> ExchangeDataStatus {
+        match response {
+            ExchangeResponseStatus::Ok(response) => response.data.unwrap().statuses[0].clone(),
+            ExchangeResponseStatus::Err(e) => panic!("unexpected error {e}"),
+        }
+    }
+
+    fn fills(message: Message) -> Vec<TradeInfo> {
+        match message {
+            Message::User(User {
+                data: UserData::Fills(fills),
+            }) => fills,
+            other => panic!("expected fills, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_matches_against_book_and_rests_remainder() -> Result<()> {
+        let (sender, mut receiver) = unbounded_channel();
+        let paper = client().with_fills(sender);
+        paper.apply(&book(
+            r#"{"px":"1999","sz":"1","n":1}"#,
+            r#"{"px":"2001","sz":"1","n":1},{"px":"2002","sz":"1","n":1}"#,
+        ))?;
+
+        // Takes both asks within its limit, the rest rests at 2002
+        match status(paper.order(limit(true, "2002", "3", "Gtc"), None).await?) {
+            ExchangeDataStatus::Filled(filled) => {
+                assert_eq!(filled.total_sz, "2");
+                assert_eq!(filled.avg_px, "2001.5");
+            }
+            other => panic!("expected fill, got {other:?}"),
+        }
+        let taker_fills = fills(receiver.try_recv().unwrap());
+        assert_eq!(taker_fills.len(), 2);
+        assert!(taker_fills
+            .iter()
+            .all(|fill| fill.crossed && fill.dir == "Open Long"));
+        assert_eq!(paper.open_orders()?[0].sz, 1.0);
+        assert_eq!(paper.position("ETH")?, 2.0);
+
+        // A sell trading through 2002 fills the resting remainder as the maker
+        paper.apply(&trade("A", "2002", "5"))?;
+        let maker_fills = fills(receiver.try_recv().unwrap());
+        assert!(!maker_fills[0].crossed);
+        assert!(paper.open_orders()?.is_empty());
+        assert_eq!(paper.position("ETH")?, 3.0);
+
+        // Closing realises the pnl against the average entry
+        paper.apply(&book(
+            r#"{"px":"2010","sz":"10","n":1}"#,
+            r#"{"px":"2011","sz":"10","n":1}"#,
+        ))?;
+        let mut close = limit(false, "2000", "3", "Ioc");
+        close.reduce_only = true;
+        status(paper.order(close, None).await?);
+        let close_fill = &fills(receiver.try_recv().unwrap())[0];
+        assert_eq!(close_fill.dir, "Close Long");
+        assert_eq!(close_fill.closed_pnl, "25");
+        assert_eq!(paper.position("ETH")?, 0.0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_rejections_use_exchange_messages() -> Result<()> {
+        let paper = client();
+        paper.apply(&book(
+            r#"{"px":"1999","sz":"1","n":1}"#,
+            r#"{"px":"2001","sz":"1","n":1}"#,
+        ))?;
+
+        let rejected = [
+            limit(true, "1990", "1", "Ioc"),
+            limit(true, "2001", "1", "Alo"),
+            limit(true, "2000.123", "1", "Gtc"),
+            limit(true, "1990", "0.001", "Gtc"),
+            ClientOrderRequest {
+                reduce_only: true,
+                ..limit(false, "1990", "1", "Ioc")
+            },
+        ];
+        let expected = [
+            OrderRejectReason::IocNoMatch,
+            OrderRejectReason::PostOnlyWouldMatch,
+            OrderRejectReason::InvalidPrice,
+            OrderRejectReason::MinNotional,
+            OrderRejectReason::ReduceOnlyWouldIncrease,
+        ];
+        for (order, expected) in rejected.into_iter().zip(expected) {
+            let status = status(paper.order(order, None).await?);
+            assert_eq!(status.reject_reason(), Some(expected));
+        }
+
+        let missing = ClientCancelRequest {
+            asset: "ETH".to_string(),
+            oid: 99,
+        };
+        let status = status(paper.cancel(missing, None).await?);
+        assert_eq!(
+            status.reject_reason(),
+            Some(OrderRejectReason::OrderNotFound)
+        );
+        assert!(paper
+            .order(
+                ClientOrderRequest {
+                    asset: "TYPO".to_string(),
+                    ..limit(true, "1", "1", "Gtc")
+                },
+                None
+            )
+            .await
+            .is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_modify_cancel_and_trigger() -> Result<()> {
+        let paper = client();
+        paper.apply(&book(
+            r#"{"px":"1999","sz":"5","n":1}"#,
+            r#"{"px":"2001","sz":"5","n":1}"#,
+        ))?;
+
+        let ExchangeDataStatus::Resting(resting) =
+            status(paper.order(limit(true, "1900", "1", "Gtc"), None).await?)
+        else {
+            panic!("expected resting order");
+        };
+        // Off the tick size: rejected, the original keeps resting
+        let invalid = ClientModifyRequest {
+            oid: resting.oid,
+            order: limit(true, "1950.123", "2", "Gtc"),
+        };
+        assert_eq!(
+            status(paper.modify(invalid, None).await?).reject_reason(),
+            Some(OrderRejectReason::InvalidPrice)
+        );
+        assert_eq!(paper.open_orders()?[0].limit_px, "1900".parse()?);
+
+        let modify = ClientModifyRequest {
+            oid: resting.oid,
+            order: limit(true, "1950", "2", "Gtc"),
+        };
+        assert!(matches!(
+            status(paper.modify(modify, None).await?),
+            ExchangeDataStatus::Resting(RestingOrder { oid }) if oid == resting.oid
+        ));
+        assert_eq!(paper.open_orders()?[0].limit_px, "1950".parse()?);
+
+        let cancel = ClientCancelRequest {
+            asset: "ETH".to_string(),
+            oid: resting.oid,
+        };
+        assert!(matches!(
+            status(paper.cancel(cancel, None).await?),
+            ExchangeDataStatus::Success
+        ));
+
+        // Stop buying above 2050, triggered once the book moves there
+        let stop = ClientOrderRequest {
+            order_type: ClientOrder::Trigger(ClientTrigger {
+                is_market: true,
+                trigger_px: "2050".parse()?,
+                tpsl: "sl".to_string(),
+            }),
+            ..limit(true, "2100", "1", "Gtc")
+        };
+        assert!(matches!(
+            status(paper.order(stop, None).await?),
+            ExchangeDataStatus::WaitingForTrigger
+        ));
+        paper.apply(&book(
+            r#"{"px":"2059","sz":"5","n":1}"#,
+            r#"{"px":"2061","sz":"5","n":1}"#,
+        ))?;
+        assert!(paper.open_orders()?.is_empty());
+        assert_eq!(paper.position("ETH")?, 1.0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_trades_through_exchange_api() -> Result<()> {
+        let paper = client();
+        paper.apply(&book(
+            r#"{"px":"1999","sz":"5","n":1}"#,
+            r#"{"px":"2001","sz":"5","n":1}"#,
+        ))?;
+        let exchange: &dyn ExchangeApi = &paper;
+
+        assert!(matches!(
+            status(
+                exchange
+                    .order(limit(true, "1990", "1", "Gtc"), None)
+                    .await?
+            ),
+            ExchangeDataStatus::Resting(_)
+        ));
+        let scale = ScaleOrder::new(
+            "ETH",
+            true,
+            "1".parse()?,
+            "1980".parse()?,
+            "1970".parse()?,
+            2,
+        );
+        assert_eq!(exchange.scale_order(scale, None).await?.rested().len(), 2);
+        assert_eq!(paper.open_orders()?.len(), 3);
+
+        let transfer = exchange.usdc_transfer("1", "0x0", None).await;
+        assert!(matches!(transfer, Err(Error::GenericRequest(e)) if e.contains("usdc_transfer")));
+        Ok(())
+    }
+}
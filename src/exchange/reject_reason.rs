@@ -0,0 +1,174 @@
+/// Reason the exchange gave for rejecting an order or action, parsed from its error text.
+///
+/// Messages the SDK doesn't recognise are kept verbatim in [`OrderRejectReason::Other`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OrderRejectReason {
+    InsufficientMargin,
+    InsufficientSpotBalance,
+    /// Order value is below the exchange minimum
+    MinNotional,
+    /// Price isn't a multiple of the tick size or has too many significant figures
+    InvalidPrice,
+    /// Size isn't a multiple of the lot size or is zero
+    InvalidSize,
+    /// Price is too far from the oracle or mark price
+    PriceTooFarFromReference,
+    PostOnlyWouldMatch,
+    /// Immediate or cancel order found nothing to match against
+    IocNoMatch,
+    ReduceOnlyWouldIncrease,
+    InvalidTpslPrice,
+    OpenInterestCap,
+    TooManyOpenOrders,
+    /// Order to cancel or modify was never placed, already canceled or filled
+    OrderNotFound,
+    RateLimited,
+    InvalidNonce,
+    /// The action arrived after its `expiresAfter` time
+    Expired,
+    /// The signer isn't a known user or agent wallet
+    UnknownSigner,
+    Other(String),
+}
+
+/// Fragments of known rejection messages, matched case-insensitively. More specific
+/// fragments come first.
+const KNOWN_REASONS: &[(&str, OrderRejectReason)] = &[
+    (
+        "insufficient spot balance",
+        OrderRejectReason::InsufficientSpotBalance,
+    ),
+    ("insufficient margin", OrderRejectReason::InsufficientMargin),
+    ("minimum value", OrderRejectReason::MinNotional),
+    (
+        "post only order would have immediately matched",
+        OrderRejectReason::PostOnlyWouldMatch,
+    ),
+    ("could not immediately match", OrderRejectReason::IocNoMatch),
+    (
+        "reduce only order would increase position",
+        OrderRejectReason::ReduceOnlyWouldIncrease,
+    ),
+    ("invalid tp/sl price", OrderRejectReason::InvalidTpslPrice),
+    (
+        "away from the reference price",
+        OrderRejectReason::PriceTooFarFromReference,
+    ),
+    ("divisible by tick size", OrderRejectReason::InvalidPrice),
+    ("invalid price", OrderRejectReason::InvalidPrice),
+    ("invalid size", OrderRejectReason::InvalidSize),
+    ("zero size", OrderRejectReason::InvalidSize),
+    ("open interest cap", OrderRejectReason::OpenInterestCap),
+    ("too many open orders", OrderRejectReason::TooManyOpenOrders),
+    ("maximum open orders", OrderRejectReason::TooManyOpenOrders),
+    (
+        "never placed, already canceled, or filled",
+        OrderRejectReason::OrderNotFound,
+    ),
+    (
+        "cannot modify canceled or filled order",
+        OrderRejectReason::OrderNotFound,
+    ),
+    (
+        "too many cumulative requests",
+        OrderRejectReason::RateLimited,
+    ),
+    ("rate limit", OrderRejectReason::RateLimited),
+    ("expired", OrderRejectReason::Expired),
+    ("expiresafter", OrderRejectReason::Expired),
+    ("nonce", OrderRejectReason::InvalidNonce),
+    ("or api wallet", OrderRejectReason::UnknownSigner),
+];
+
+impl OrderRejectReason {
+    pub fn parse(message: &str) -> OrderRejectReason {
+        let lowercase = message.to_lowercase();
+        KNOWN_REASONS
+            .iter()
+            .find(|(fragment, _)| lowercase.contains(fragment))
+            .map(|(_, reason)| reason.clone())
+            .unwrap_or_else(|| OrderRejectReason::Other(message.to_string()))
+    }
+
+    /// Whether resubmitting the same order unchanged, with a fresh nonce, may succeed.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            OrderRejectReason::RateLimited | OrderRejectReason::InvalidNonce
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ExchangeDataStatuses, ExchangeResponseStatus};
+
+    #[test]
+    fn parses_known_rejections() {
+        for (message, reason) in [
+            (
+                "Insufficient margin to place order. asset=4",
+                OrderRejectReason::InsufficientMargin,
+            ),
+            (
+                "Order must have minimum value of $10. asset=4",
+                OrderRejectReason::MinNotional,
+            ),
+            (
+                "Price must be divisible by tick size. asset=4",
+                OrderRejectReason::InvalidPrice,
+            ),
+            (
+                "Post only order would have immediately matched, bbo was 1797.9@1798.0. asset=4",
+                OrderRejectReason::PostOnlyWouldMatch,
+            ),
+            (
+                "Order could not immediately match against any resting orders. asset=4",
+                OrderRejectReason::IocNoMatch,
+            ),
+            (
+                "Order was never placed, already canceled, or filled. asset=4",
+                OrderRejectReason::OrderNotFound,
+            ),
+            (
+                "Too many cumulative requests sent (10215 > 10200) for cumulative volume traded $0.",
+                OrderRejectReason::RateLimited,
+            ),
+            ("Action expired", OrderRejectReason::Expired),
+        ] {
+            assert_eq!(OrderRejectReason::parse(message), reason, "{message}");
+        }
+
+        assert_eq!(
+            OrderRejectReason::parse("Something new"),
+            OrderRejectReason::Other("Something new".to_string())
+        );
+        assert!(OrderRejectReason::RateLimited.is_retryable());
+        assert!(!OrderRejectReason::InsufficientMargin.is_retryable());
+        // Would only fail again at the same price
+        assert!(!OrderRejectReason::IocNoMatch.is_retryable());
+    }
+
+    #[test]
+    fn exposes_reason_on_statuses() {
+        let data: ExchangeDataStatuses = serde_json::from_str(
+            r#"{"statuses":[{"resting":{"oid":1}},{"error":"Reduce only order would increase position. asset=4"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(data.statuses[0].reject_reason(), None);
+        assert_eq!(
+            data.statuses[1].reject_reason(),
+            Some(OrderRejectReason::ReduceOnlyWouldIncrease)
+        );
+
+        let response: ExchangeResponseStatus = serde_json::from_str(
+            r#"{"status":"err","response":"User or API Wallet 0x0d1d9635d0640821d15e323ac8adadfa9c111414 does not exist."}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            response.reject_reason(),
+            Some(OrderRejectReason::UnknownSigner)
+        );
+    }
+}
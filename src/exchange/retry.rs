@@ -0,0 +1,161 @@
+use crate::{prelude::*, Actions, Error, InfoClient, SignedAction};
+use ethers::types::H160;
+use log::debug;
+use serde_json::{json, Value};
+use std::time::Duration;
+use uuid::Uuid;
+
+/// When and how often [`ExchangeClient`](crate::ExchangeClient) resends an action whose
+/// request failed without a response from the exchange.
+///
+/// Retries resend the signed action unchanged, so an action that did reach the exchange is
+/// rejected for reusing its nonce instead of executing twice. Before resending orders that
+/// carry a cloid, the client looks them up by cloid, and if the first attempt placed them
+/// returns their current statuses instead.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// Attempts in total, including the first
+    pub max_attempts: u32,
+    /// Wait before the first retry, doubled for every further one
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    /// Time after which an attempt counts as failed, waiting indefinitely if `None`
+    pub timeout: Option<Duration>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(250),
+            max_backoff: Duration::from_secs(4),
+            timeout: Some(Duration::from_secs(10)),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Wait before retry number `retry`, starting at 1.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(retry.saturating_sub(1)))
+            .min(self.max_backoff)
+    }
+
+    /// Network failures, timeouts and `5xx` responses, after which the action may or may not
+    /// have reached the exchange.
+    pub fn is_retryable(error: &Error) -> bool {
+        matches!(
+            error,
+            Error::GenericRequest(_) | Error::ServerRequest { .. }
+        )
+    }
+}
+
+/// Response the exchange would have given to `signed` if it placed the orders, built from
+/// their statuses and fills, or `None` if it isn't an order with cloids or none of them is
+/// known.
+pub(crate) async fn placed_order_response(
+    info_client: &InfoClient,
+    signed: &SignedAction,
+    is_mainnet: bool,
+) -> Result<Option<String>> {
+    let Actions::Order(bulk_order) = &signed.action else {
+        return Ok(None);
+    };
+    if bulk_order.orders.iter().all(|order| order.cloid.is_none()) {
+        return Ok(None);
+    }
+    let user: H160 = match signed.vault_address {
+        Some(vault_address) => vault_address,
+        None => signed.recover_signer(is_mainnet)?,
+    };
+
+    let mut orders = Vec::new();
+    for order in &bulk_order.orders {
+        orders.push(match &order.cloid {
+            Some(cloid) => {
+                let cloid = Uuid::parse_str(cloid.trim_start_matches("0x"))
+                    .map_err(|e| Error::GenericParse(e.to_string()))?;
+                info_client.query_order_by_cloid(user, cloid).await?.order
+            }
+            None => None,
+        });
+    }
+    if orders.iter().all(Option::is_none) {
+        return Ok(None);
+    }
+    let fills = if orders.iter().flatten().any(|info| info.status == "filled") {
+        info_client.user_fills(user).await?
+    } else {
+        Vec::new()
+    };
+
+    let statuses: Vec<Value> = orders
+        .into_iter()
+        .map(|info| {
+            let Some(info) = info else {
+                return json!({"error": "Order status unknown after retrying"});
+            };
+            let oid = info.order.oid;
+            match info.status.as_str() {
+                "open" | "triggered" => json!({"resting": {"oid": oid}}),
+                "filled" => {
+                    let (sz, notional) = fills
+                        .iter()
+                        .filter(|fill| fill.oid == oid)
+                        .filter_map(|fill| {
+                            Some((fill.sz.parse::<f64>().ok()?, fill.px.parse::<f64>().ok()?))
+                        })
+                        .fold((0.0, 0.0), |(sz, notional), (fill_sz, px)| {
+                            (sz + fill_sz, notional + fill_sz * px)
+                        });
+                    let avg_px = if sz > 0.0 {
+                        (notional / sz).to_string()
+                    } else {
+                        info.order.limit_px
+                    };
+                    json!({"filled": {"totalSz": info.order.orig_sz, "avgPx": avg_px, "oid": oid}})
+                }
+                status => json!({"error": format!("Order {oid} is {status}")}),
+            }
+        })
+        .collect();
+
+    debug!("Orders were placed before retrying: {statuses:?}");
+    let response: Value = json!({
+        "status": "ok",
+        "response": {"type": "order", "data": {"statuses": statuses}},
+    });
+    Ok(Some(response.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles_up_to_max() {
+        let policy = RetryPolicy {
+            max_attempts: 5,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(350),
+            timeout: None,
+        };
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(350));
+        assert_eq!(policy.backoff(40), Duration::from_millis(350));
+
+        assert!(RetryPolicy::is_retryable(&Error::ServerRequest {
+            status_code: 502,
+            error_message: String::new(),
+        }));
+        assert!(!RetryPolicy::is_retryable(&Error::ClientRequest {
+            status_code: 422,
+            error_code: None,
+            error_message: String::new(),
+            error_data: None,
+        }));
+    }
+}
@@ -0,0 +1,332 @@
+use crate::{
+    prelude::*, ClientLimit, ClientOrder, ClientOrderRequest, Error, ExchangeClient,
+    ExchangeDataStatus, HyperliquidSigner, OrderValidator, Px, Sz,
+};
+use log::debug;
+
+/// How a [`ScaleOrder`] spreads its size over the levels, from the first price to the last.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScaleWeighting {
+    /// Sizes change linearly, with the last level `skew` times the size of the first, so a
+    /// skew of 1 sizes all levels equally
+    Linear { skew: f64 },
+    /// Every level is `ratio` times the size of the one before
+    Geometric { ratio: f64 },
+}
+
+impl Default for ScaleWeighting {
+    fn default() -> Self {
+        ScaleWeighting::Linear { skew: 1.0 }
+    }
+}
+
+impl ScaleWeighting {
+    fn weights(&self, levels: usize) -> Result<Vec<f64>> {
+        let factor = match *self {
+            ScaleWeighting::Linear { skew } => skew,
+            ScaleWeighting::Geometric { ratio } => ratio,
+        };
+        if !(factor.is_finite() && factor > 0.0) {
+            return Err(Error::GenericRequest(format!(
+                "Scale order weighting must be positive: {self:?}"
+            )));
+        }
+        let last = levels.saturating_sub(1).max(1) as f64;
+        Ok((0..levels)
+            .map(|level| match *self {
+                ScaleWeighting::Linear { skew } => 1.0 + (skew - 1.0) * level as f64 / last,
+                ScaleWeighting::Geometric { ratio } => ratio.powi(level as i32),
+            })
+            .collect())
+    }
+}
+
+/// Limit orders spreading a total size over evenly spaced prices from `start_px` to `end_px`,
+/// placed with [`ExchangeClient::scale_order`].
+#[derive(Debug, Clone)]
+pub struct ScaleOrder {
+    asset: String,
+    is_buy: bool,
+    sz: Sz,
+    start_px: Px,
+    end_px: Px,
+    levels: usize,
+    weighting: ScaleWeighting,
+    reduce_only: bool,
+    tif: String,
+}
+
+impl ScaleOrder {
+    /// Scale of `levels` good til cancelled orders, equally sized until
+    /// [`with_weighting`](ScaleOrder::with_weighting) says otherwise.
+    pub fn new(
+        asset: impl Into<String>,
+        is_buy: bool,
+        sz: Sz,
+        start_px: Px,
+        end_px: Px,
+        levels: usize,
+    ) -> ScaleOrder {
+        ScaleOrder {
+            asset: asset.into(),
+            is_buy,
+            sz,
+            start_px,
+            end_px,
+            levels,
+            weighting: ScaleWeighting::default(),
+            reduce_only: false,
+            tif: "Gtc".to_string(),
+        }
+    }
+
+    pub fn with_weighting(mut self, weighting: ScaleWeighting) -> ScaleOrder {
+        self.weighting = weighting;
+        self
+    }
+
+    pub fn with_reduce_only(mut self, reduce_only: bool) -> ScaleOrder {
+        self.reduce_only = reduce_only;
+        self
+    }
+
+    /// Time in force of every level, e.g. `"Alo"` so no level takes liquidity.
+    pub fn with_tif(mut self, tif: impl Into<String>) -> ScaleOrder {
+        self.tif = tif.into();
+        self
+    }
+
+    /// The orders of each level, from `start_px` to `end_px`, with prices rounded to the
+    /// asset's tick and significant figure rules and sizes in whole lots adding up to the
+    /// total size rounded to a lot.
+    ///
+    /// Fails with [`Error::OrderValidation`] for the first level that still breaks a rule,
+    /// e.g. when the total size is too small to give every level a lot or the minimum value.
+    pub fn orders(&self, validator: &OrderValidator) -> Result<Vec<ClientOrderRequest>> {
+        if self.levels == 0 {
+            return Err(Error::GenericRequest(
+                "Scale order needs at least one level".to_string(),
+            ));
+        }
+        let sz_decimals = validator.assets().resolve(&self.asset)?.sz_decimals;
+        let lot = 10f64.powi(sz_decimals as i32);
+        let total_lots = (self.sz.round_to_decimals(sz_decimals).to_f64() * lot).round() as u64;
+
+        // Whole lots per level, with those lost to rounding down handed to the levels that
+        // lost the largest fraction of one
+        let weights = self.weighting.weights(self.levels)?;
+        let weight_sum: f64 = weights.iter().sum();
+        let exact: Vec<f64> = weights
+            .iter()
+            .map(|weight| total_lots as f64 * weight / weight_sum)
+            .collect();
+        let mut lots: Vec<u64> = exact.iter().map(|lots| lots.floor() as u64).collect();
+        let mut by_remainder: Vec<usize> = (0..self.levels).collect();
+        by_remainder.sort_by(|&a, &b| {
+            (exact[b] - exact[b].floor()).total_cmp(&(exact[a] - exact[a].floor()))
+        });
+        let leftover = total_lots.saturating_sub(lots.iter().sum());
+        for &level in by_remainder.iter().cycle().take(leftover as usize) {
+            lots[level] += 1;
+        }
+
+        let (start, end) = (self.start_px.to_f64(), self.end_px.to_f64());
+        let last = self.levels.saturating_sub(1).max(1) as f64;
+        lots.into_iter()
+            .enumerate()
+            .map(|(level, lots)| {
+                let px = start + (end - start) * level as f64 / last;
+                let order = ClientOrderRequest {
+                    asset: self.asset.clone(),
+                    is_buy: self.is_buy,
+                    reduce_only: self.reduce_only,
+                    limit_px: Px::try_from(px)?,
+                    sz: Sz::try_from(lots as f64 / lot)?,
+                    cloid: None,
+                    order_type: ClientOrder::Limit(ClientLimit {
+                        tif: self.tif.clone(),
+                    }),
+                };
+                validator
+                    .round(order)
+                    .map_err(|errors| Error::OrderValidation {
+                        index: level,
+                        errors,
+                    })
+            })
+            .collect()
+    }
+}
+
+/// Outcome of one level of a [`ScaleOrder`].
+#[derive(Debug, Clone)]
+pub struct ScaleLevelStatus {
+    pub limit_px: Px,
+    pub sz: Sz,
+    pub status: Result<ExchangeDataStatus>,
+}
+
+/// Outcomes of the levels of a [`ScaleOrder`], from `start_px` to `end_px`.
+#[derive(Debug, Clone)]
+pub struct ScaleOrderStatus {
+    pub levels: Vec<ScaleLevelStatus>,
+}
+
+impl ScaleOrderStatus {
+    /// Levels resting on the book.
+    pub fn rested(&self) -> Vec<&ScaleLevelStatus> {
+        self.matching(|status| matches!(status, Ok(ExchangeDataStatus::Resting(_))))
+    }
+
+    /// Levels filled on placement.
+    pub fn filled(&self) -> Vec<&ScaleLevelStatus> {
+        self.matching(|status| matches!(status, Ok(ExchangeDataStatus::Filled(_))))
+    }
+
+    /// Levels the exchange rejected or whose request failed.
+    pub fn failed(&self) -> Vec<&ScaleLevelStatus> {
+        self.matching(|status| matches!(status, Ok(ExchangeDataStatus::Error(_)) | Err(_)))
+    }
+
+    fn matching(
+        &self,
+        predicate: impl Fn(&Result<ExchangeDataStatus>) -> bool,
+    ) -> Vec<&ScaleLevelStatus> {
+        self.levels
+            .iter()
+            .filter(|level| predicate(&level.status))
+            .collect()
+    }
+}
+
+impl ExchangeClient {
+    /// Places the levels of `scale` with [`bulk_order`](ExchangeClient::bulk_order), in
+    /// batches of at most [`MAX_BULK_ACTION_SIZE`](crate::MAX_BULK_ACTION_SIZE).
+    ///
+    /// Nothing is sent if a level breaks the asset's rules after rounding. Once sending, a
+    /// batch failing doesn't stop the remaining ones; its levels report the error instead.
+    pub async fn scale_order(
+        &self,
+        scale: ScaleOrder,
+        wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ScaleOrderStatus> {
+        let assets = self.meta_cache().assets_for([scale.asset.as_str()]).await?;
+        let orders = scale.orders(&OrderValidator::from_assets(assets))?;
+        debug!("Placing {} scale order levels", orders.len());
+
+        let placed: Vec<_> = orders
+            .iter()
+            .map(|order| (order.limit_px, order.sz))
+            .collect();
+        let statuses = self.bulk_order_chunked(orders, wallet).await;
+        let levels = placed
+            .into_iter()
+            .zip(statuses)
+            .map(|((limit_px, sz), status)| ScaleLevelStatus {
+                limit_px,
+                sz,
+                status,
+            })
+            .collect();
+        Ok(ScaleOrderStatus { levels })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_server, Meta, SpotMeta};
+
+    const META: &str = r#"{"universe":[{"name":"ETH","szDecimals":4}]}"#;
+
+    fn validator() -> OrderValidator {
+        let meta: Meta = serde_json::from_str(META).unwrap();
+        let spot_meta: SpotMeta = serde_json::from_str(test_server::EMPTY_SPOT_META).unwrap();
+        OrderValidator::new(&meta, &spot_meta)
+    }
+
+    fn levels(orders: &[ClientOrderRequest]) -> Vec<(String, String)> {
+        orders
+            .iter()
+            .map(|order| (order.limit_px.to_string(), order.sz.to_string()))
+            .collect()
+    }
+
+    fn scale(sz: &str, start_px: &str) -> ScaleOrder {
+        ScaleOrder::new(
+            "ETH",
+            true,
+            sz.parse().unwrap(),
+            start_px.parse().unwrap(),
+            "1900".parse().unwrap(),
+            3,
+        )
+    }
+
+    #[test]
+    fn test_spreads_size_in_whole_lots() -> Result<()> {
+        let validator = validator();
+        let pairs = |levels: &[(&str, &str)]| {
+            levels
+                .iter()
+                .map(|(px, sz)| (px.to_string(), sz.to_string()))
+                .collect::<Vec<_>>()
+        };
+
+        let even = scale("1", "2000.123").orders(&validator)?;
+        assert_eq!(
+            levels(&even),
+            pairs(&[("2000.1", "0.3334"), ("1950", "0.3333"), ("1900", "0.3333")])
+        );
+
+        let geometric = scale("1", "2000")
+            .with_weighting(ScaleWeighting::Geometric { ratio: 2.0 })
+            .orders(&validator)?;
+        assert_eq!(
+            levels(&geometric),
+            pairs(&[("2000", "0.1429"), ("1950", "0.2857"), ("1900", "0.5714")])
+        );
+
+        let linear = scale("0.6", "2000")
+            .with_weighting(ScaleWeighting::Linear { skew: 3.0 })
+            .orders(&validator)?;
+        assert_eq!(
+            levels(&linear),
+            pairs(&[("2000", "0.1"), ("1950", "0.2"), ("1900", "0.3")])
+        );
+
+        // Too small to give every level the minimum value
+        assert!(matches!(
+            scale("0.01", "2000").orders(&validator),
+            Err(Error::OrderValidation { index: 0, .. })
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reports_status_per_level() -> Result<()> {
+        let base_url = test_server::serve_exchange(META, test_server::EMPTY_SPOT_META, |_, _| {
+            let statuses = r#"[{"resting":{"oid":1}},{"filled":{"totalSz":"0.3333","avgPx":"1950","oid":2}},{"error":"Post only order would have immediately matched"}]"#;
+            format!(
+                r#"{{"status":"ok","response":{{"type":"order","data":{{"statuses":{statuses}}}}}}}"#
+            )
+        })
+        .await;
+        let client = test_server::test_client(base_url).await?;
+
+        let status = client
+            .scale_order(scale("1", "2000").with_tif("Alo"), None)
+            .await?;
+        assert_eq!(status.levels.len(), 3);
+        let px = |levels: Vec<&ScaleLevelStatus>| {
+            levels
+                .iter()
+                .map(|level| level.limit_px.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(px(status.rested()), ["2000"]);
+        assert_eq!(px(status.filled()), ["1950"]);
+        assert_eq!(px(status.failed()), ["1900"]);
+        Ok(())
+    }
+}
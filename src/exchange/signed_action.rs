@@ -0,0 +1,352 @@
+use crate::{
+    exchange::actions::{SpotSend, UsdSend, Withdraw3},
+    prelude::*,
+    recover_l1_signer,
+    signature::sign_l1_action,
+    Actions, Error, HyperliquidSigner,
+};
+use ethers::types::{transaction::eip712::Eip712, Signature, H160, H256};
+use serde::{Deserialize, Serialize};
+
+/// Chain id the exchange expects in the EIP-712 domain of user-signed actions.
+const SIGNATURE_CHAIN_ID: u64 = 421614;
+
+fn eip712_digest<T: Eip712>(payload: &T) -> Result<H256> {
+    payload
+        .encode_eip712()
+        .map(H256::from)
+        .map_err(|e| Error::Eip712(e.to_string()))
+}
+
+/// EIP-712 digest and nonce of actions the exchange authenticates as typed data rather
+/// than through a connection id, or `None` for L1 actions.
+fn typed_data_digest(action: &Actions) -> Result<Option<(H256, u64)>> {
+    let digest = match action {
+        Actions::UsdSend(a) => (eip712_digest(a)?, a.time),
+        Actions::Withdraw3(a) => (eip712_digest(a)?, a.time),
+        Actions::SpotSend(a) => (eip712_digest(a)?, a.time),
+        Actions::ApproveAgent(a) => (eip712_digest(a)?, a.nonce),
+        Actions::CDeposit(a) => (eip712_digest(a)?, a.nonce),
+        Actions::CWithdraw(a) => (eip712_digest(a)?, a.nonce),
+        Actions::TokenDelegate(a) => (eip712_digest(a)?, a.nonce),
+        _ => return Ok(None),
+    };
+    Ok(Some(digest))
+}
+
+fn hyperliquid_chain(is_mainnet: bool) -> String {
+    if is_mainnet {
+        "Mainnet".to_string()
+    } else {
+        "Testnet".to_string()
+    }
+}
+
+/// An action together with its signature and nonce, exactly as posted to `/exchange`.
+///
+/// Signing needs only the wallet, so a `SignedAction` can be created on a machine without
+/// network access, moved as JSON and submitted later with
+/// [`ExchangeClient::submit_signed`](crate::ExchangeClient::submit_signed). The exchange only
+/// accepts nonces close to its current time, so submit within a day of signing, and before
+/// `expires_after` if set.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedAction {
+    pub action: Actions,
+    pub signature: Signature,
+    pub nonce: u64,
+    pub vault_address: Option<H160>,
+    /// Time in milliseconds after which the exchange rejects the action, L1 actions only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_after: Option<u64>,
+}
+
+impl SignedAction {
+    /// Signs an L1 action such as an order, cancel or vault transfer. `nonce` is usually the
+    /// current time in milliseconds.
+    pub async fn sign_l1<S: HyperliquidSigner + ?Sized>(
+        action: Actions,
+        nonce: u64,
+        vault_address: Option<H160>,
+        expires_after: Option<u64>,
+        is_mainnet: bool,
+        signer: &S,
+    ) -> Result<SignedAction> {
+        let connection_id = action.hash(nonce, vault_address, expires_after)?;
+        let signature = sign_l1_action(signer, connection_id, is_mainnet).await?;
+        Ok(SignedAction {
+            action,
+            signature,
+            nonce,
+            vault_address,
+            expires_after,
+        })
+    }
+
+    /// Signs an action the exchange authenticates with EIP-712 typed data, such as transfers
+    /// and withdrawals. The nonce is the `time` or `nonce` field of the action itself.
+    pub async fn sign_user_action<S: HyperliquidSigner + ?Sized>(
+        action: Actions,
+        signer: &S,
+    ) -> Result<SignedAction> {
+        let Some((digest, nonce)) = typed_data_digest(&action)? else {
+            return Err(Error::GenericRequest(
+                "Action is not signed as EIP-712 typed data, use SignedAction::sign_l1".to_string(),
+            ));
+        };
+        let signature = signer.sign_hash(digest).await?;
+        Ok(SignedAction {
+            action,
+            signature,
+            nonce,
+            vault_address: None,
+            expires_after: None,
+        })
+    }
+
+    /// Sends `amount` USDC from the perp balance to `destination`.
+    pub async fn usdc_transfer<S: HyperliquidSigner + ?Sized>(
+        amount: &str,
+        destination: &str,
+        nonce: u64,
+        is_mainnet: bool,
+        signer: &S,
+    ) -> Result<SignedAction> {
+        SignedAction::sign_user_action(
+            Actions::UsdSend(UsdSend {
+                signature_chain_id: SIGNATURE_CHAIN_ID.into(),
+                hyperliquid_chain: hyperliquid_chain(is_mainnet),
+                destination: destination.to_string(),
+                amount: amount.to_string(),
+                time: nonce,
+            }),
+            signer,
+        )
+        .await
+    }
+
+    /// Withdraws `amount` USDC through the bridge to `destination` on Arbitrum.
+    pub async fn withdraw_from_bridge<S: HyperliquidSigner + ?Sized>(
+        amount: &str,
+        destination: &str,
+        nonce: u64,
+        is_mainnet: bool,
+        signer: &S,
+    ) -> Result<SignedAction> {
+        SignedAction::sign_user_action(
+            Actions::Withdraw3(Withdraw3 {
+                signature_chain_id: SIGNATURE_CHAIN_ID.into(),
+                hyperliquid_chain: hyperliquid_chain(is_mainnet),
+                destination: destination.to_string(),
+                amount: amount.to_string(),
+                time: nonce,
+            }),
+            signer,
+        )
+        .await
+    }
+
+    /// Sends `amount` of the spot `token` (`"NAME:tokenId"`) to `destination`.
+    pub async fn spot_transfer<S: HyperliquidSigner + ?Sized>(
+        amount: &str,
+        destination: &str,
+        token: &str,
+        nonce: u64,
+        is_mainnet: bool,
+        signer: &S,
+    ) -> Result<SignedAction> {
+        SignedAction::sign_user_action(
+            Actions::SpotSend(SpotSend {
+                signature_chain_id: SIGNATURE_CHAIN_ID.into(),
+                hyperliquid_chain: hyperliquid_chain(is_mainnet),
+                destination: destination.to_string(),
+                amount: amount.to_string(),
+                time: nonce,
+                token: token.to_string(),
+            }),
+            signer,
+        )
+        .await
+    }
+
+    /// Recovers the address that signed the action. `is_mainnet` only matters for L1 actions,
+    /// typed data names its chain itself.
+    pub fn recover_signer(&self, is_mainnet: bool) -> Result<H160> {
+        let Some((digest, nonce)) = typed_data_digest(&self.action)? else {
+            let connection_id =
+                self.action
+                    .hash(self.nonce, self.vault_address, self.expires_after)?;
+            return recover_l1_signer(connection_id, is_mainnet, &self.signature);
+        };
+
+        if nonce != self.nonce {
+            return Err(Error::SignatureFailure(format!(
+                "Payload nonce {} doesn't match the signed nonce {nonce}",
+                self.nonce
+            )));
+        }
+        self.signature
+            .recover(digest)
+            .map_err(|e| Error::SignatureFailure(e.to_string()))
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string(self).map_err(|e| Error::JsonParse(e.to_string()))
+    }
+
+    pub fn from_json(json: &str) -> Result<SignedAction> {
+        serde_json::from_str(json).map_err(|e| Error::JsonParse(e.to_string()))
+    }
+}
+
+/// Recovers the signer of an `/exchange` request body, to compare with the expected signer.
+///
+/// The network can't be read from every payload: L1 actions such as orders and cancels are
+/// signed over a phantom agent whose source differs between mainnet and testnet, and nothing
+/// in their JSON records which one was used. Recovering with the wrong network doesn't fail
+/// but yields an unrelated address, so `is_mainnet` must say which network the payload is
+/// meant for. User-signed actions name their chain in `hyperliquidChain` and ignore it.
+pub fn verify_payload(json: &str, is_mainnet: bool) -> Result<H160> {
+    SignedAction::from_json(json)?.recover_signer(is_mainnet)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exchange::{actions::BulkCancel, cancel::CancelRequest};
+    use ethers::signers::LocalWallet;
+
+    fn get_wallet() -> Result<LocalWallet> {
+        crate::test_server::TEST_KEY
+            .parse::<LocalWallet>()
+            .map_err(|e| Error::Wallet(e.to_string()))
+    }
+
+    #[tokio::test]
+    async fn test_signed_action_json_round_trip() -> Result<()> {
+        let wallet = get_wallet()?;
+        let vault_address: H160 = "0x1719884eb866cb12b2287399b15f7db5e7d775ea"
+            .parse()
+            .unwrap();
+
+        let cancel = SignedAction::sign_l1(
+            Actions::Cancel(BulkCancel {
+                cancels: vec![CancelRequest {
+                    asset: 1,
+                    oid: 82382,
+                }],
+            }),
+            1583838,
+            Some(vault_address),
+            Some(1583838 + 60_000),
+            true,
+            &wallet,
+        )
+        .await?;
+        let withdraw = SignedAction::withdraw_from_bridge(
+            "1",
+            "0x0D1d9635D0640821d15e323ac8AdADfA9c111414",
+            1583838,
+            false,
+            &wallet,
+        )
+        .await?;
+
+        for signed in [cancel, withdraw] {
+            let json = signed.to_json()?;
+            let parsed = SignedAction::from_json(&json)?;
+            assert_eq!(parsed.to_json()?, json);
+            assert_eq!(parsed.signature, signed.signature);
+            assert_eq!(parsed.nonce, 1583838);
+            assert_eq!(
+                parsed
+                    .action
+                    .hash(parsed.nonce, parsed.vault_address, parsed.expires_after)?,
+                signed
+                    .action
+                    .hash(signed.nonce, signed.vault_address, signed.expires_after)?
+            );
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_signed_action_matches_wire_payload() -> Result<()> {
+        let wallet = get_wallet()?;
+        let signed = SignedAction::usdc_transfer(
+            "1",
+            "0x0D1d9635D0640821d15e323ac8AdADfA9c111414",
+            1583838,
+            true,
+            &wallet,
+        )
+        .await?;
+
+        let payload: serde_json::Value = serde_json::from_str(&signed.to_json()?).unwrap();
+        assert_eq!(payload["action"]["type"], "usdSend");
+        assert_eq!(payload["action"]["hyperliquidChain"], "Mainnet");
+        assert_eq!(payload["action"]["time"], 1583838);
+        assert_eq!(payload["nonce"], 1583838);
+        assert_eq!(payload["vaultAddress"], serde_json::Value::Null);
+        assert!(payload.get("expiresAfter").is_none());
+        assert_eq!(payload["signature"]["v"], signed.signature.v);
+
+        assert!(SignedAction::sign_user_action(
+            Actions::Cancel(BulkCancel { cancels: vec![] }),
+            &wallet
+        )
+        .await
+        .is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_verify_payload_recovers_signer() -> Result<()> {
+        let wallet = get_wallet()?;
+        let address = HyperliquidSigner::address(&wallet);
+
+        let cancel = SignedAction::sign_l1(
+            Actions::Cancel(BulkCancel {
+                cancels: vec![CancelRequest {
+                    asset: 1,
+                    oid: 82382,
+                }],
+            }),
+            1583838,
+            None,
+            None,
+            true,
+            &wallet,
+        )
+        .await?;
+        let json = cancel.to_json()?;
+        assert_eq!(verify_payload(&json, true)?, address);
+        assert_ne!(verify_payload(&json, false)?, address);
+
+        // The expiry is signed as well, extending it changes the recovered signer
+        let mut extended = cancel.clone();
+        extended.expires_after = Some(1583838 + 60_000);
+        assert!(extended.to_json()?.contains(r#""expiresAfter":1643838"#));
+        assert_ne!(verify_payload(&extended.to_json()?, true)?, address);
+
+        let withdraw = SignedAction::withdraw_from_bridge(
+            "1",
+            "0x0D1d9635D0640821d15e323ac8AdADfA9c111414",
+            1583838,
+            false,
+            &wallet,
+        )
+        .await?;
+        assert_eq!(verify_payload(&withdraw.to_json()?, false)?, address);
+
+        let mut tampered = withdraw.clone();
+        tampered.nonce += 1;
+        assert!(verify_payload(&tampered.to_json()?, false).is_err());
+        if let Actions::Withdraw3(withdraw) = &mut tampered.action {
+            withdraw.amount = "1000".to_string();
+            withdraw.time += 1;
+        }
+        assert_ne!(verify_payload(&tampered.to_json()?, false)?, address);
+        Ok(())
+    }
+}
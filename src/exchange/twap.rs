@@ -0,0 +1,44 @@
+use crate::{prelude::*, AssetResolver, Sz};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug)]
+pub struct ClientTwapRequest {
+    pub asset: String,
+    pub is_buy: bool,
+    pub sz: Sz,
+    pub reduce_only: bool,
+    /// Duration of the TWAP in minutes
+    pub minutes: u32,
+    pub randomize: bool,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct TwapRequest {
+    #[serde(rename = "a", alias = "asset")]
+    pub asset: u32,
+    #[serde(rename = "b", alias = "isBuy")]
+    pub is_buy: bool,
+    #[serde(rename = "s", alias = "sz")]
+    pub sz: String,
+    #[serde(rename = "r", alias = "reduceOnly")]
+    pub reduce_only: bool,
+    #[serde(rename = "m", alias = "minutes")]
+    pub minutes: u32,
+    #[serde(rename = "t", alias = "randomize")]
+    pub randomize: bool,
+}
+
+impl ClientTwapRequest {
+    pub(crate) fn convert(self, assets: &AssetResolver) -> Result<TwapRequest> {
+        let asset = assets.asset_index(&self.asset)?;
+
+        Ok(TwapRequest {
+            asset,
+            is_buy: self.is_buy,
+            sz: self.sz.to_string(),
+            reduce_only: self.reduce_only,
+            minutes: self.minutes,
+            randomize: self.randomize,
+        })
+    }
+}
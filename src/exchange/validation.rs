@@ -0,0 +1,351 @@
+use crate::{
+    meta::{Meta, SpotMeta},
+    prelude::*,
+    AssetInfo, AssetResolver, ClientOrder, ClientOrderRequest, Error, Px, Sz,
+};
+use std::sync::Arc;
+use thiserror::Error;
+
+/// Smallest order value in USDC the exchange accepts for orders that aren't reduce only.
+pub const MIN_ORDER_NOTIONAL: f64 = 10.0;
+/// Prices may have at most this many significant figures, unless they are integers.
+pub const MAX_PRICE_SIGNIFICANT_FIGURES: u32 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationMode {
+    /// Reject orders that break an asset's rules before signing them
+    Strict,
+    /// Round prices and sizes to the asset's rules, only rejecting orders rounding can't fix
+    AutoFix,
+}
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum OrderValidationError {
+    #[error("Asset {0} not found")]
+    UnknownAsset(String),
+    #[error("Price {px} for {asset} has more than {max_decimals} decimals")]
+    PriceDecimals {
+        asset: String,
+        px: Px,
+        max_decimals: u32,
+    },
+    #[error("Price {px} for {asset} has more than {max_significant_figures} significant figures")]
+    PriceSignificantFigures {
+        asset: String,
+        px: Px,
+        max_significant_figures: u32,
+    },
+    #[error("Size {sz} for {asset} has more than {sz_decimals} decimals")]
+    SizeDecimals {
+        asset: String,
+        sz: Sz,
+        sz_decimals: u32,
+    },
+    #[error("Size for {asset} is zero")]
+    ZeroSize { asset: String },
+    #[error("Order value {notional} for {asset} is below the minimum of {min_notional}")]
+    BelowMinNotional {
+        asset: String,
+        notional: f64,
+        min_notional: f64,
+    },
+}
+
+/// Checks orders against the tick size, significant figure, lot size and minimum notional
+/// rules of their asset before they are signed.
+#[derive(Debug, Clone)]
+pub struct OrderValidator {
+    assets: Arc<AssetResolver>,
+    min_notional: f64,
+}
+
+impl OrderValidator {
+    pub fn new(meta: &Meta, spot_meta: &SpotMeta) -> OrderValidator {
+        OrderValidator::from_assets(AssetResolver::new(meta, spot_meta))
+    }
+
+    pub fn from_assets(assets: impl Into<Arc<AssetResolver>>) -> OrderValidator {
+        OrderValidator {
+            assets: assets.into(),
+            min_notional: MIN_ORDER_NOTIONAL,
+        }
+    }
+
+    pub fn with_min_notional(mut self, min_notional: f64) -> OrderValidator {
+        self.min_notional = min_notional;
+        self
+    }
+
+    pub fn assets(&self) -> &AssetResolver {
+        &self.assets
+    }
+
+    /// Returns every rule the order breaks.
+    pub fn validate(
+        &self,
+        order: &ClientOrderRequest,
+    ) -> std::result::Result<(), Vec<OrderValidationError>> {
+        let info = self.resolve(&order.asset)?;
+        let max_decimals = max_price_decimals(info);
+
+        let mut errors = Vec::new();
+        let mut prices = vec![order.limit_px];
+        if let ClientOrder::Trigger(trigger) = &order.order_type {
+            prices.push(trigger.trigger_px);
+        }
+        for px in prices {
+            if px.decimals() > max_decimals {
+                errors.push(OrderValidationError::PriceDecimals {
+                    asset: order.asset.clone(),
+                    px,
+                    max_decimals,
+                });
+            }
+            if !px.is_integer() && px.significant_figures() > MAX_PRICE_SIGNIFICANT_FIGURES {
+                errors.push(OrderValidationError::PriceSignificantFigures {
+                    asset: order.asset.clone(),
+                    px,
+                    max_significant_figures: MAX_PRICE_SIGNIFICANT_FIGURES,
+                });
+            }
+        }
+
+        if order.sz.decimals() > info.sz_decimals {
+            errors.push(OrderValidationError::SizeDecimals {
+                asset: order.asset.clone(),
+                sz: order.sz,
+                sz_decimals: info.sz_decimals,
+            });
+        }
+        if order.sz.is_zero() {
+            errors.push(OrderValidationError::ZeroSize {
+                asset: order.asset.clone(),
+            });
+        }
+
+        // Reduce only orders may be smaller, so that small positions can still be closed
+        let notional = order.limit_px.to_f64() * order.sz.to_f64();
+        if !order.reduce_only && !order.sz.is_zero() && notional < self.min_notional {
+            errors.push(OrderValidationError::BelowMinNotional {
+                asset: order.asset.clone(),
+                notional,
+                min_notional: self.min_notional,
+            });
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Rounds prices and size to the asset's rules and validates the result.
+    ///
+    /// Prices move toward the passive side, down for buys and up for sells, and sizes are
+    /// truncated, so the rounded order never trades at a worse price or for more than asked.
+    pub fn round(
+        &self,
+        mut order: ClientOrderRequest,
+    ) -> std::result::Result<ClientOrderRequest, Vec<OrderValidationError>> {
+        let info = self.resolve(&order.asset)?;
+        let max_decimals = max_price_decimals(info);
+
+        order.limit_px = round_price(order.limit_px, order.is_buy, max_decimals);
+        if let ClientOrder::Trigger(trigger) = &mut order.order_type {
+            trigger.trigger_px = round_price(trigger.trigger_px, order.is_buy, max_decimals);
+        }
+        order.sz = order.sz.truncate_to_decimals(info.sz_decimals);
+
+        self.validate(&order)?;
+        Ok(order)
+    }
+
+    /// Validates or rounds a batch of orders, failing on the first order that is invalid.
+    pub fn apply(
+        &self,
+        orders: Vec<ClientOrderRequest>,
+        mode: ValidationMode,
+    ) -> Result<Vec<ClientOrderRequest>> {
+        orders
+            .into_iter()
+            .enumerate()
+            .map(|(index, order)| {
+                let checked = match mode {
+                    ValidationMode::Strict => self.validate(&order).map(|_| order),
+                    ValidationMode::AutoFix => self.round(order),
+                };
+                checked.map_err(|errors| Error::OrderValidation { index, errors })
+            })
+            .collect()
+    }
+
+    fn resolve(&self, asset: &str) -> std::result::Result<&AssetInfo, Vec<OrderValidationError>> {
+        self.assets
+            .resolve(asset)
+            .map_err(|_| vec![OrderValidationError::UnknownAsset(asset.to_string())])
+    }
+}
+
+fn max_price_decimals(info: &AssetInfo) -> u32 {
+    info.id
+        .max_price_decimals()
+        .saturating_sub(info.sz_decimals)
+}
+
+fn round_price(px: Px, is_buy: bool, max_decimals: u32) -> Px {
+    if px.is_integer() {
+        px
+    } else if is_buy {
+        px.truncate_to_significant_and_decimal(MAX_PRICE_SIGNIFICANT_FIGURES, max_decimals)
+    } else {
+        px.ceil_to_significant_and_decimal(MAX_PRICE_SIGNIFICANT_FIGURES, max_decimals)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ClientLimit, ClientTrigger};
+
+    fn validator() -> OrderValidator {
+        let meta: Meta = serde_json::from_str(
+            r#"{"universe":[{"name":"BTC","szDecimals":5},{"name":"ETH","szDecimals":4}]}"#,
+        )
+        .unwrap();
+        let spot_meta: SpotMeta = serde_json::from_str(
+            r#"{
+                "universe":[{"tokens":[1,0],"name":"PURR/USDC","index":0,"isCanonical":true}],
+                "tokens":[
+                    {"name":"USDC","szDecimals":8,"weiDecimals":8,"index":0,"tokenId":"0x6d1e7cde53ba9467b783cb7c530ce054","isCanonical":true},
+                    {"name":"PURR","szDecimals":0,"weiDecimals":5,"index":1,"tokenId":"0xc1fb593aeffbeb02f85e0308e9956a90","isCanonical":true}
+                ]
+            }"#,
+        )
+        .unwrap();
+        OrderValidator::new(&meta, &spot_meta)
+    }
+
+    fn order(asset: &str, limit_px: &str, sz: &str) -> ClientOrderRequest {
+        ClientOrderRequest {
+            asset: asset.to_string(),
+            is_buy: true,
+            reduce_only: false,
+            limit_px: limit_px.parse().unwrap(),
+            sz: sz.parse().unwrap(),
+            cloid: None,
+            order_type: ClientOrder::Limit(ClientLimit {
+                tif: "Gtc".to_string(),
+            }),
+        }
+    }
+
+    #[test]
+    fn accepts_valid_orders() {
+        let validator = validator();
+        assert!(validator.validate(&order("ETH", "1800.5", "0.01")).is_ok());
+        assert!(validator.validate(&order("BTC", "123456", "0.001")).is_ok());
+        assert!(validator
+            .validate(&order("PURR/USDC", "0.12345", "100"))
+            .is_ok());
+    }
+
+    #[test]
+    fn reports_every_broken_rule() {
+        let errors = validator()
+            .validate(&order("ETH", "1800.123", "0.00001"))
+            .unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                OrderValidationError::PriceDecimals {
+                    asset: "ETH".to_string(),
+                    px: "1800.123".parse().unwrap(),
+                    max_decimals: 2,
+                },
+                OrderValidationError::PriceSignificantFigures {
+                    asset: "ETH".to_string(),
+                    px: "1800.123".parse().unwrap(),
+                    max_significant_figures: 5,
+                },
+                OrderValidationError::SizeDecimals {
+                    asset: "ETH".to_string(),
+                    sz: "0.00001".parse().unwrap(),
+                    sz_decimals: 4,
+                },
+                OrderValidationError::BelowMinNotional {
+                    asset: "ETH".to_string(),
+                    notional: 1800.123 * 0.00001,
+                    min_notional: MIN_ORDER_NOTIONAL,
+                },
+            ]
+        );
+
+        let mut reduce_only = order("ETH", "1800", "0.001");
+        reduce_only.reduce_only = true;
+        assert!(validator().validate(&reduce_only).is_ok());
+
+        assert_eq!(
+            validator().validate(&order("DOGE", "1", "1")).unwrap_err(),
+            vec![OrderValidationError::UnknownAsset("DOGE".to_string())]
+        );
+    }
+
+    #[test]
+    fn rounds_to_asset_rules() {
+        let mut trigger = order("ETH", "1800.126", "0.012345");
+        trigger.order_type = ClientOrder::Trigger(ClientTrigger {
+            is_market: true,
+            trigger_px: "1790.004".parse().unwrap(),
+            tpsl: "sl".to_string(),
+        });
+
+        let rounded = validator().round(trigger).unwrap();
+        assert_eq!(rounded.limit_px.to_string(), "1800.1");
+        assert_eq!(rounded.sz.to_string(), "0.0123");
+        let ClientOrder::Trigger(trigger) = rounded.order_type else {
+            panic!("order type changed while rounding");
+        };
+        assert_eq!(trigger.trigger_px.to_string(), "1790");
+
+        // Buys round down and sells up, never past the caller's limit
+        let buy = validator().round(order("ETH", "2000.15", "0.01")).unwrap();
+        assert_eq!(buy.limit_px.to_string(), "2000.1");
+        let mut sell = order("ETH", "2000.11", "0.01");
+        sell.is_buy = false;
+        assert_eq!(
+            validator().round(sell).unwrap().limit_px.to_string(),
+            "2000.2"
+        );
+
+        // Rounding can't lift an order above the minimum notional
+        assert!(matches!(
+            validator()
+                .round(order("ETH", "1800", "0.001"))
+                .unwrap_err()[..],
+            [OrderValidationError::BelowMinNotional { .. }]
+        ));
+    }
+
+    #[test]
+    fn apply_reports_index_of_invalid_order() {
+        let orders = vec![
+            order("ETH", "1800", "0.01"),
+            order("ETH", "1800.123", "0.01"),
+        ];
+        match validator().apply(orders, ValidationMode::Strict) {
+            Err(Error::OrderValidation { index, errors }) => {
+                assert_eq!(index, 1);
+                assert_eq!(errors.len(), 2);
+            }
+            other => panic!("expected validation error, got {other:?}"),
+        }
+
+        let orders = vec![
+            order("ETH", "1800", "0.01"),
+            order("ETH", "1800.123", "0.01"),
+        ];
+        let rounded = validator().apply(orders, ValidationMode::AutoFix).unwrap();
+        assert_eq!(rounded[1].limit_px.to_string(), "1800.1");
+    }
+}
@@ -1,31 +1,13 @@
 use crate::{consts::*, prelude::*, Error};
 use chrono::prelude::Utc;
-use log::info;
 use rand::{thread_rng, Rng};
-use std::sync::{
-    atomic::{AtomicU64, Ordering},
-    LazyLock,
-};
 use uuid::Uuid;
 
-fn now_timestamp_ms() -> u64 {
+pub(crate) fn now_timestamp_ms() -> u64 {
     let now = Utc::now();
     now.timestamp_millis() as u64
 }
 
-pub(crate) fn next_nonce() -> u64 {
-    let nonce = CUR_NONCE.fetch_add(1, Ordering::Relaxed);
-    let now_ms = now_timestamp_ms();
-    if nonce > now_ms + 1000 {
-        info!("nonce progressed too far ahead {nonce} {now_ms}");
-    }
-    // more than 300 seconds behind
-    if nonce + 300000 < now_ms {
-        CUR_NONCE.fetch_max(now_ms, Ordering::Relaxed);
-    }
-    nonce
-}
-
 pub(crate) const WIRE_DECIMALS: u8 = 8;
 
 pub(crate) fn float_to_string_for_hashing(x: f64) -> String {
@@ -78,24 +60,57 @@ pub fn bps_diff(x: f64, y: f64) -> u16 {
     }
 }
 
-#[derive(Copy, Clone)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum BaseUrl {
     Localhost,
     Testnet,
     Mainnet,
+    /// Self-hosted node or proxy. `http` is the API root, `ws` the full websocket URL and
+    /// `is_mainnet` the chain actions are signed for.
+    Custom {
+        http: String,
+        ws: String,
+        is_mainnet: bool,
+    },
 }
 
 impl BaseUrl {
-    pub(crate) fn get_url(&self) -> String {
+    pub fn http_url(&self) -> &str {
         match self {
-            BaseUrl::Localhost => LOCAL_API_URL.to_string(),
-            BaseUrl::Mainnet => MAINNET_API_URL.to_string(),
-            BaseUrl::Testnet => TESTNET_API_URL.to_string(),
+            BaseUrl::Localhost => LOCAL_API_URL,
+            BaseUrl::Mainnet => MAINNET_API_URL,
+            BaseUrl::Testnet => TESTNET_API_URL,
+            BaseUrl::Custom { http, .. } => http.trim_end_matches('/'),
         }
     }
-}
 
-static CUR_NONCE: LazyLock<AtomicU64> = LazyLock::new(|| AtomicU64::new(now_timestamp_ms()));
+    /// Websocket URL, served under `/ws` with the matching `ws` or `wss` scheme unless set
+    /// explicitly.
+    pub fn ws_url(&self) -> String {
+        if let BaseUrl::Custom { ws, .. } = self {
+            return ws.clone();
+        }
+        let http_url = self.http_url();
+        let host = http_url
+            .strip_prefix("https://")
+            .map(|host| format!("wss://{host}"))
+            .or_else(|| {
+                http_url
+                    .strip_prefix("http://")
+                    .map(|host| format!("ws://{host}"))
+            })
+            .unwrap_or_else(|| http_url.to_string());
+        format!("{host}/ws")
+    }
+
+    pub fn is_mainnet(&self) -> bool {
+        match self {
+            BaseUrl::Mainnet => true,
+            BaseUrl::Localhost | BaseUrl::Testnet => false,
+            BaseUrl::Custom { is_mainnet, .. } => *is_mainnet,
+        }
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -141,4 +156,25 @@ mod tests {
             "987654321".to_string()
         );
     }
+
+    #[test]
+    fn base_url_test() {
+        assert_eq!(BaseUrl::Mainnet.ws_url(), "wss://api.hyperliquid.xyz/ws");
+        assert_eq!(
+            BaseUrl::Testnet.ws_url(),
+            "wss://api.hyperliquid-testnet.xyz/ws"
+        );
+        assert_eq!(BaseUrl::Localhost.ws_url(), "ws://localhost:3001/ws");
+        assert!(BaseUrl::Mainnet.is_mainnet());
+        assert!(!BaseUrl::Testnet.is_mainnet());
+
+        let custom = BaseUrl::Custom {
+            http: "http://10.0.0.5:8080/".to_string(),
+            ws: "ws://10.0.0.5:8081/ws".to_string(),
+            is_mainnet: true,
+        };
+        assert_eq!(custom.http_url(), "http://10.0.0.5:8080");
+        assert_eq!(custom.ws_url(), "ws://10.0.0.5:8081/ws");
+        assert!(custom.is_mainnet());
+    }
 }
@@ -1,14 +1,17 @@
 use crate::{
+    helpers::uuid_to_hex_string,
     info::{
         CandlesSnapshotResponse, FundingHistoryResponse, L2SnapshotResponse, OpenOrdersResponse,
         OrderInfo, RecentTradesResponse, UserFillsResponse, UserStateResponse,
     },
-    meta::{Meta, SpotMeta, SpotMetaAndAssetCtxs},
+    meta::{Meta, PerpDex, SpotMeta, SpotMetaAndAssetCtxs},
     prelude::*,
     req::HttpClient,
     ws::{backend::WsBackend, Subscription},
-    BaseUrl, Error, Message, OrderStatusResponse, ReferralResponse, UserFeesResponse,
-    UserFundingResponse, UserTokenBalanceResponse,
+    BaseUrl, BasicOrderInfo, DelegationResponse, DelegatorRewardResponse, DelegatorSummaryResponse,
+    Error, Message, OrderStatusResponse, RateLimiter, ReferralResponse, SubAccountResponse,
+    UserFeesResponse, UserFundingResponse, UserRateLimitResponse, UserTokenBalanceResponse,
+    UserVaultEquity, VaultDetailsResponse,
 };
 
 use ethers::types::H160;
@@ -16,6 +19,7 @@ use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, sync::Arc};
 use tokio::sync::mpsc::UnboundedSender;
+use uuid::Uuid;
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -26,6 +30,15 @@ pub struct CandleSnapshotRequest {
     end_time: u64,
 }
 
+/// Order looked up by the exchange's id or by the client order id it was placed with.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum OrderId {
+    Oid(u64),
+    /// Cloid as a `0x`-prefixed hex string
+    Cloid(String),
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(tag = "type")]
 #[serde(rename_all = "camelCase")]
@@ -45,14 +58,26 @@ pub enum InfoRequest {
     UserFees {
         user: H160,
     },
+    UserRateLimit {
+        user: H160,
+    },
     OpenOrders {
         user: H160,
     },
+    FrontendOpenOrders {
+        user: H160,
+    },
     OrderStatus {
         user: H160,
-        oid: u64,
+        oid: OrderId,
     },
     Meta,
+    /// Sent as a `meta` request, so only `Meta` is deserialized from one
+    #[serde(rename = "meta", skip_deserializing)]
+    PerpDexMeta {
+        dex: String,
+    },
+    PerpDexs,
     SpotMeta,
     SpotMetaAndAssetCtxs,
     AllMids,
@@ -87,6 +112,27 @@ pub enum InfoRequest {
     HistoricalOrders {
         user: H160,
     },
+    SubAccounts {
+        user: H160,
+    },
+    #[serde(rename_all = "camelCase")]
+    VaultDetails {
+        vault_address: H160,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        user: Option<H160>,
+    },
+    UserVaultEquities {
+        user: H160,
+    },
+    Delegations {
+        user: H160,
+    },
+    DelegatorSummary {
+        user: H160,
+    },
+    DelegatorRewards {
+        user: H160,
+    },
 }
 
 #[derive(Debug)]
@@ -106,7 +152,7 @@ impl InfoClient {
     }
 
     async fn new_internal(base_url: Option<BaseUrl>, reconnect: bool) -> Result<InfoClient> {
-        let base_url = base_url.unwrap_or(BaseUrl::Mainnet).get_url();
+        let base_url = base_url.unwrap_or(BaseUrl::Mainnet);
 
         Ok(InfoClient {
             http_client: HttpClient::new(base_url),
@@ -134,15 +180,26 @@ impl InfoClient {
         base_url: Option<BaseUrl>,
         reconnect: bool,
     ) -> Result<InfoClient> {
-        let base_url = base_url.unwrap_or(BaseUrl::Mainnet).get_url();
+        let base_url = base_url.unwrap_or(BaseUrl::Mainnet);
 
         Ok(InfoClient {
-            http_client: HttpClient { client, base_url },
+            http_client: HttpClient {
+                client,
+                base_url,
+                rate_limiter: None,
+            },
             ws: None,
             reconnect,
         })
     }
 
+    /// Checks every request against `rate_limiter` before sending it. Pass the same limiter,
+    /// or one made with [`RateLimiter::for_account`], to clients sharing an IP address.
+    pub fn with_rate_limiter(mut self, rate_limiter: RateLimiter) -> InfoClient {
+        self.http_client.rate_limiter = Some(rate_limiter);
+        self
+    }
+
     pub async fn subscribe(
         &mut self,
         subscription: Subscription,
@@ -150,11 +207,8 @@ impl InfoClient {
     ) -> Result<u32> {
         if self.ws.is_none() {
             self.ws = Some(
-                crate::ws::make_ws_backend(
-                    &format!("wss{}/ws", &self.http_client.base_url[5..]),
-                    self.reconnect,
-                )
-                .await?,
+                crate::ws::make_ws_backend(&self.http_client.base_url.ws_url(), self.reconnect)
+                    .await?,
             );
         }
 
@@ -197,6 +251,13 @@ impl InfoClient {
         self.send_info_request(input).await
     }
 
+    /// Open orders with their trigger, reduce-only and time in force details, including
+    /// trigger orders still waiting for their trigger price.
+    pub async fn frontend_open_orders(&self, address: H160) -> Result<Vec<BasicOrderInfo>> {
+        let input = InfoRequest::FrontendOpenOrders { user: address };
+        self.send_info_request(input).await
+    }
+
     pub async fn user_state(&self, address: H160) -> Result<UserStateResponse> {
         let input = InfoRequest::UserState { user: address };
         self.send_info_request(input).await
@@ -217,11 +278,33 @@ impl InfoClient {
         self.send_info_request(input).await
     }
 
+    /// Requests the address has used and may use, see [`RateLimiter::sync`].
+    ///
+    /// [`RateLimiter::sync`]: crate::RateLimiter::sync
+    pub async fn user_rate_limit(&self, address: H160) -> Result<UserRateLimitResponse> {
+        let input = InfoRequest::UserRateLimit { user: address };
+        self.send_info_request(input).await
+    }
+
     pub async fn meta(&self) -> Result<Meta> {
         let input = InfoRequest::Meta;
         self.send_info_request(input).await
     }
 
+    /// Universe of the builder-deployed perp dex named `dex`, with names prefixed `"dex:"`.
+    pub async fn perp_dex_meta(&self, dex: &str) -> Result<Meta> {
+        let input = InfoRequest::PerpDexMeta {
+            dex: dex.to_string(),
+        };
+        self.send_info_request(input).await
+    }
+
+    /// Perp dexes in the order their asset ids are numbered by, `None` for the main dex.
+    pub async fn perp_dexs(&self) -> Result<Vec<Option<PerpDex>>> {
+        let input = InfoRequest::PerpDexs;
+        self.send_info_request(input).await
+    }
+
     pub async fn spot_meta(&self) -> Result<SpotMeta> {
         let input = InfoRequest::SpotMeta;
         self.send_info_request(input).await
@@ -299,7 +382,22 @@ impl InfoClient {
     }
 
     pub async fn query_order_by_oid(&self, address: H160, oid: u64) -> Result<OrderStatusResponse> {
-        let input = InfoRequest::OrderStatus { user: address, oid };
+        let input = InfoRequest::OrderStatus {
+            user: address,
+            oid: OrderId::Oid(oid),
+        };
+        self.send_info_request(input).await
+    }
+
+    pub async fn query_order_by_cloid(
+        &self,
+        address: H160,
+        cloid: Uuid,
+    ) -> Result<OrderStatusResponse> {
+        let input = InfoRequest::OrderStatus {
+            user: address,
+            oid: OrderId::Cloid(uuid_to_hex_string(cloid)),
+        };
         self.send_info_request(input).await
     }
 
@@ -312,4 +410,45 @@ impl InfoClient {
         let input = InfoRequest::HistoricalOrders { user: address };
         self.send_info_request(input).await
     }
+
+    pub async fn sub_accounts(&self, address: H160) -> Result<Vec<SubAccountResponse>> {
+        let input = InfoRequest::SubAccounts { user: address };
+        // The exchange returns null rather than an empty list for accounts without any
+        let sub_accounts: Option<Vec<SubAccountResponse>> = self.send_info_request(input).await?;
+        Ok(sub_accounts.unwrap_or_default())
+    }
+
+    /// Details of a vault. When `user` is set, the response also includes that user's
+    /// position in the vault as `follower_state`.
+    pub async fn vault_details(
+        &self,
+        vault_address: H160,
+        user: Option<H160>,
+    ) -> Result<VaultDetailsResponse> {
+        let input = InfoRequest::VaultDetails {
+            vault_address,
+            user,
+        };
+        self.send_info_request(input).await
+    }
+
+    pub async fn user_vault_equities(&self, address: H160) -> Result<Vec<UserVaultEquity>> {
+        let input = InfoRequest::UserVaultEquities { user: address };
+        self.send_info_request(input).await
+    }
+
+    pub async fn delegations(&self, address: H160) -> Result<Vec<DelegationResponse>> {
+        let input = InfoRequest::Delegations { user: address };
+        self.send_info_request(input).await
+    }
+
+    pub async fn delegator_summary(&self, address: H160) -> Result<DelegatorSummaryResponse> {
+        let input = InfoRequest::DelegatorSummary { user: address };
+        self.send_info_request(input).await
+    }
+
+    pub async fn delegator_rewards(&self, address: H160) -> Result<Vec<DelegatorRewardResponse>> {
+        let input = InfoRequest::DelegatorRewards { user: address };
+        self.send_info_request(input).await
+    }
 }
@@ -1,24 +1,46 @@
 #![deny(unreachable_pub)]
+mod api;
+mod asset;
 mod consts;
+mod decimal;
 mod errors;
 mod exchange;
 mod helpers;
 mod info;
 mod market_maker;
 mod meta;
+mod meta_cache;
+mod mock;
 mod net;
+mod nonce;
 mod prelude;
 mod proxy_digest;
+mod rate_limit;
 mod req;
 mod signature;
+#[cfg(test)]
+mod test_server;
 pub mod ws;
+pub use api::{ExchangeApi, InfoApi};
+pub use asset::{AssetId, AssetInfo, AssetResolver};
 pub use consts::{EPSILON, LOCAL_API_URL, MAINNET_API_URL, TESTNET_API_URL};
+pub use decimal::{Px, Sz};
 pub use errors::Error;
 pub use exchange::*;
 pub use helpers::{bps_diff, truncate_float, BaseUrl};
 pub use info::{info_client::*, *};
 pub use market_maker::{MarketMaker, MarketMakerInput, MarketMakerRestingOrder};
-pub use meta::{AssetMeta, Meta};
+pub use meta::{AssetMeta, Meta, PerpDex, SpotMeta, SpotMetaAndAssetCtxs};
+pub use meta_cache::MetaCache;
+pub use mock::{MockCall, MockClient};
+pub use nonce::{FileNonceStore, InMemoryNonceStore, NextNonce, NonceManager, NonceStore};
+pub use rate_limit::{
+    exchange_weight, info_weight, RateLimitMode, RateLimiter, IP_WEIGHT_PER_MINUTE,
+};
+pub use signature::{
+    recover_l1_signer, recover_typed_data_signer, HyperliquidSigner, RemoteSigner,
+    RemoteSignerTransport,
+};
 pub use ws::*;
 
 // Deprecation notice for the old client parameter pattern
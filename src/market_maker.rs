@@ -8,8 +8,8 @@ use tokio::sync::mpsc::unbounded_channel;
 
 use crate::{
     bps_diff, truncate_float, BaseUrl, ClientCancelRequest, ClientLimit, ClientOrder,
-    ClientOrderRequest, ExchangeClient, ExchangeDataStatus, ExchangeResponseStatus, InfoClient,
-    Message, Subscription, UserData, EPSILON,
+    ClientOrderRequest, ExchangeApi, ExchangeClient, ExchangeDataStatus, ExchangeResponseStatus,
+    InfoApi, InfoClient, Message, Px, Subscription, Sz, UserData, EPSILON,
 };
 #[derive(Debug)]
 pub struct MarketMakerRestingOrder {
@@ -41,19 +41,30 @@ pub struct MarketMaker {
     pub upper_resting: MarketMakerRestingOrder,
     pub cur_position: f64,
     pub latest_mid_price: f64,
-    pub info_client: InfoClient,
-    pub exchange_client: ExchangeClient,
+    pub info_client: Box<dyn InfoApi>,
+    pub exchange_client: Box<dyn ExchangeApi>,
     pub user_address: H160,
 }
 
 impl MarketMaker {
     pub async fn new(input: MarketMakerInput) -> MarketMaker {
-        let user_address = input.wallet.address();
-
         let info_client = InfoClient::new(Some(BaseUrl::Testnet)).await.unwrap();
-        let exchange_client = ExchangeClient::new(input.wallet, Some(BaseUrl::Testnet), None, None)
-            .await
-            .unwrap();
+        let exchange_client =
+            ExchangeClient::new(input.wallet.clone(), Some(BaseUrl::Testnet), None, None)
+                .await
+                .unwrap();
+
+        MarketMaker::with_clients(input, Box::new(info_client), Box::new(exchange_client))
+    }
+
+    /// Market maker trading through the given clients, e.g. [`MockClient`](crate::MockClient)s
+    /// in tests, instead of testnet ones for `input.wallet`.
+    pub fn with_clients(
+        input: MarketMakerInput,
+        info_client: Box<dyn InfoApi>,
+        exchange_client: Box<dyn ExchangeApi>,
+    ) -> MarketMaker {
+        let user_address = input.wallet.address();
 
         MarketMaker {
             asset: input.asset,
@@ -190,6 +201,13 @@ impl MarketMaker {
         price: f64,
         is_buy: bool,
     ) -> (f64, u64) {
+        let (limit_px, sz) = match (Px::try_from(price), Sz::try_from(amount)) {
+            (Ok(limit_px), Ok(sz)) => (limit_px, sz),
+            _ => {
+                error!("Invalid order price {price} or size {amount}");
+                return (0.0, 0);
+            }
+        };
         let order = self
             .exchange_client
             .order(
@@ -197,8 +215,8 @@ impl MarketMaker {
                     asset,
                     is_buy,
                     reduce_only: false,
-                    limit_px: price,
-                    sz: amount,
+                    limit_px,
+                    sz,
                     cloid: None,
                     order_type: ClientOrder::Limit(ClientLimit {
                         tif: "Gtc".to_string(),
@@ -331,3 +349,57 @@ impl MarketMaker {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MockClient;
+
+    fn resting(oid: u64) -> String {
+        format!(
+            r#"{{"status":"ok","response":{{"type":"order","data":{{"statuses":[{{"resting":{{"oid":{oid}}}}}]}}}}}}"#
+        )
+    }
+
+    #[tokio::test]
+    async fn test_quotes_around_mid() {
+        let mock = MockClient::new();
+        mock.respond_json::<ExchangeResponseStatus>("order", &resting(1))
+            .respond_json::<ExchangeResponseStatus>("order", &resting(2));
+
+        let wallet = crate::test_server::test_wallet();
+        let mut market_maker = MarketMaker::with_clients(
+            MarketMakerInput {
+                asset: "ETH".to_string(),
+                target_liquidity: 0.25,
+                half_spread: 10,
+                max_bps_diff: 2,
+                max_absolute_position_size: 0.5,
+                decimals: 1,
+                wallet,
+            },
+            Box::new(mock.clone()),
+            Box::new(mock.clone()),
+        );
+        market_maker.latest_mid_price = 2000.0;
+        market_maker.potentially_update().await;
+
+        let orders = mock.calls_to("order");
+        assert_eq!(orders.len(), 2);
+        let (lower, upper) = (
+            market_maker.lower_resting.price,
+            market_maker.upper_resting.price,
+        );
+        assert!(1997.0 < lower && lower < 2000.0 && 2000.0 < upper && upper < 2003.0);
+        let limit_px = |px: f64| format!("limit_px: {:?}", Px::try_from(px).unwrap());
+        assert!(orders[0].args[0].contains("is_buy: true"));
+        assert!(orders[0].args[0].contains(&limit_px(lower)));
+        assert!(orders[1].args[0].contains(&limit_px(upper)));
+        assert_eq!(market_maker.lower_resting.oid, 1);
+        assert_eq!(market_maker.upper_resting.oid, 2);
+
+        // Unchanged mid: nothing to cancel or replace
+        market_maker.potentially_update().await;
+        assert_eq!(mock.calls().len(), 2);
+    }
+}
@@ -8,6 +8,12 @@ pub struct Meta {
     pub universe: Vec<AssetMeta>,
 }
 
+/// Builder-deployed perp dex listed by [`InfoClient::perp_dexs`](crate::InfoClient::perp_dexs).
+#[derive(Deserialize, Debug, Clone)]
+pub struct PerpDex {
+    pub name: String,
+}
+
 #[derive(Deserialize, Debug, Clone)]
 pub struct SpotMeta {
     pub universe: Vec<SpotAssetMeta>,
@@ -68,6 +74,8 @@ pub struct SpotAssetContext {
 pub struct AssetMeta {
     pub name: String,
     pub sz_decimals: u32,
+    #[serde(default)]
+    pub is_delisted: bool,
 }
 
 #[derive(Deserialize, Debug, Clone)]
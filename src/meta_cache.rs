@@ -0,0 +1,307 @@
+use crate::{prelude::*, AssetResolver, BaseUrl, Error, InfoClient, Meta};
+use futures_util::future::try_join_all;
+use log::{debug, error};
+use reqwest::Client;
+use std::{
+    sync::{Arc, Mutex, RwLock, Weak},
+    time::{Duration, Instant},
+};
+use tokio::task::JoinHandle;
+
+/// Refreshes triggered by unknown asset names are at least this far apart, so a typo in a
+/// hot loop doesn't turn into a metadata request per order.
+const MISS_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug)]
+struct MetaCacheInner {
+    info_client: InfoClient,
+    assets: RwLock<Arc<AssetResolver>>,
+    /// Builder-deployed perp dexes whose assets are loaded with every fetch
+    perp_dexs: RwLock<Vec<String>>,
+    last_miss_refresh: Mutex<Option<Instant>>,
+    refreshing: tokio::sync::Mutex<()>,
+}
+
+/// Perp and spot metadata shared by any number of [`ExchangeClient`](crate::ExchangeClient)s.
+///
+/// Lookups see the latest snapshot. Names missing from it trigger a refresh, so assets
+/// listed after startup resolve without restarting, and [`spawn_refresh`] keeps the
+/// snapshot current in the background. Assets that were delisted since resolve to
+/// [`Error::AssetDelisted`]. Perps of builder-deployed dexes are included once the dexes
+/// are named with [`with_perp_dexs`].
+///
+/// Clones share the same snapshot.
+///
+/// [`with_perp_dexs`]: MetaCache::with_perp_dexs
+///
+/// [`spawn_refresh`]: MetaCache::spawn_refresh
+#[derive(Debug, Clone)]
+pub struct MetaCache {
+    inner: Arc<MetaCacheInner>,
+}
+
+impl MetaCache {
+    pub async fn new(base_url: BaseUrl) -> Result<MetaCache> {
+        MetaCache::from_info_client(InfoClient::new(Some(base_url)).await?, None).await
+    }
+
+    pub async fn with_client(client: &'static Client, base_url: BaseUrl) -> Result<MetaCache> {
+        MetaCache::from_info_client(InfoClient::with_client(client, Some(base_url)).await?, None)
+            .await
+    }
+
+    /// Cache fetching through `info_client`, starting from `meta` instead of fetching it if
+    /// given. Spot metadata is always fetched.
+    pub async fn from_info_client(
+        info_client: InfoClient,
+        meta: Option<Meta>,
+    ) -> Result<MetaCache> {
+        let meta = match meta {
+            Some(meta) => meta,
+            None => info_client.meta().await?,
+        };
+        let assets = AssetResolver::new(&meta, &info_client.spot_meta().await?);
+
+        Ok(MetaCache {
+            inner: Arc::new(MetaCacheInner {
+                info_client,
+                assets: RwLock::new(Arc::new(assets)),
+                perp_dexs: RwLock::new(Vec::new()),
+                last_miss_refresh: Mutex::new(None),
+                refreshing: tokio::sync::Mutex::new(()),
+            }),
+        })
+    }
+
+    /// Also loads the perps of the builder-deployed dexes named in `dexs`, now and with every
+    /// later refresh, so they resolve as `"dex:COIN"`. Clones share the setting.
+    pub async fn with_perp_dexs(
+        self,
+        dexs: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Result<MetaCache> {
+        {
+            let mut perp_dexs = self
+                .inner
+                .perp_dexs
+                .write()
+                .map_err(|e| Error::GenericRequest(e.to_string()))?;
+            *perp_dexs = dexs.into_iter().map(Into::into).collect();
+        }
+        self.refresh().await?;
+        Ok(self)
+    }
+
+    /// Client the metadata is fetched with, also usable for other info requests.
+    pub fn info_client(&self) -> &InfoClient {
+        &self.inner.info_client
+    }
+
+    pub fn base_url(&self) -> &BaseUrl {
+        &self.inner.info_client.http_client.base_url
+    }
+
+    /// Latest snapshot, without refreshing.
+    pub fn assets(&self) -> Arc<AssetResolver> {
+        match self.inner.assets.read() {
+            Ok(assets) => assets.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    /// Snapshot knowing every name in `names`, refreshing first if the latest one doesn't.
+    /// Names still unknown after refreshing are left for the lookup to report.
+    pub async fn assets_for<'a>(
+        &self,
+        names: impl IntoIterator<Item = &'a str>,
+    ) -> Result<Arc<AssetResolver>> {
+        let assets = self.assets();
+        let Some(missing) = names.into_iter().find(|name| !assets.contains(name)) else {
+            return Ok(assets);
+        };
+
+        let _refreshing = self.inner.refreshing.lock().await;
+        // Another task may have refreshed while this one waited for the lock
+        let assets = self.assets();
+        if assets.contains(missing) {
+            return Ok(assets);
+        }
+        {
+            let mut last_miss_refresh = self
+                .inner
+                .last_miss_refresh
+                .lock()
+                .map_err(|e| Error::GenericRequest(e.to_string()))?;
+            if last_miss_refresh.is_some_and(|last| last.elapsed() < MISS_REFRESH_INTERVAL) {
+                return Ok(assets);
+            }
+            *last_miss_refresh = Some(Instant::now());
+        }
+
+        debug!("Refreshing metadata, {missing:?} is unknown");
+        self.fetch().await
+    }
+
+    /// Fetches the metadata now and returns the new snapshot.
+    pub async fn refresh(&self) -> Result<Arc<AssetResolver>> {
+        let _refreshing = self.inner.refreshing.lock().await;
+        self.fetch().await
+    }
+
+    async fn fetch(&self) -> Result<Arc<AssetResolver>> {
+        let info_client = &self.inner.info_client;
+        let (meta, spot_meta) = tokio::try_join!(info_client.meta(), info_client.spot_meta())?;
+        let mut assets = AssetResolver::new(&meta, &spot_meta);
+        self.add_perp_dexs(&mut assets).await?;
+        let assets = Arc::new(assets);
+
+        match self.inner.assets.write() {
+            Ok(mut current) => *current = assets.clone(),
+            Err(poisoned) => *poisoned.into_inner() = assets.clone(),
+        }
+        Ok(assets)
+    }
+
+    async fn add_perp_dexs(&self, assets: &mut AssetResolver) -> Result<()> {
+        let names = self
+            .inner
+            .perp_dexs
+            .read()
+            .map_err(|e| Error::GenericRequest(e.to_string()))?
+            .clone();
+        if names.is_empty() {
+            return Ok(());
+        }
+
+        let info_client = &self.inner.info_client;
+        let listed = &info_client.perp_dexs().await?;
+        let metas = try_join_all(names.iter().map(|name| async move {
+            let dex = listed
+                .iter()
+                .position(|dex| dex.as_ref().is_some_and(|dex| &dex.name == name))
+                .ok_or_else(|| Error::GenericRequest(format!("Unknown perp dex {name:?}")))?;
+            Ok::<_, Error>((dex as u32, info_client.perp_dex_meta(name).await?))
+        }))
+        .await?;
+        for (dex, meta) in metas {
+            assets.add_perp_dex(dex, &meta);
+        }
+        Ok(())
+    }
+
+    /// Refreshes the metadata every `interval` until the task is aborted or every clone of
+    /// the cache is dropped. Failed refreshes are logged and retried at the next interval.
+    pub fn spawn_refresh(&self, interval: Duration) -> JoinHandle<()> {
+        let inner: Weak<MetaCacheInner> = Arc::downgrade(&self.inner);
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                let Some(inner) = inner.upgrade() else {
+                    return;
+                };
+                if let Err(e) = (MetaCache { inner }).refresh().await {
+                    error!("Failed to refresh metadata: {e}");
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        test_server::{serve, EMPTY_SPOT_META},
+        ClientLimit, ClientOrder, ClientOrderRequest,
+    };
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Info endpoint serving `metas[version]` as perp metadata
+    async fn serve_meta(metas: Vec<&'static str>, version: Arc<AtomicUsize>) -> BaseUrl {
+        serve(move |_, body| {
+            if body.contains(r#""type":"spotMeta""#) {
+                EMPTY_SPOT_META.to_string()
+            } else {
+                metas[version.load(Ordering::SeqCst).min(metas.len() - 1)].to_string()
+            }
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_refreshes_on_miss_and_tracks_delistings() -> Result<()> {
+        let version = Arc::new(AtomicUsize::new(0));
+        let base_url = serve_meta(
+            vec![
+                r#"{"universe":[{"name":"BTC","szDecimals":5}]}"#,
+                r#"{"universe":[{"name":"BTC","szDecimals":5},{"name":"NEW","szDecimals":2}]}"#,
+                r#"{"universe":[{"name":"BTC","szDecimals":5,"isDelisted":true},{"name":"NEW","szDecimals":2}]}"#,
+            ],
+            version.clone(),
+        )
+        .await;
+
+        let cache = MetaCache::new(base_url).await?;
+        let shared = cache.clone();
+        assert!(matches!(
+            cache.assets().asset_id("NEW"),
+            Err(Error::AssetNotFound)
+        ));
+
+        // Listed after startup: the miss refreshes the snapshot for every clone
+        version.store(1, Ordering::SeqCst);
+        let assets = cache.assets_for(["BTC", "NEW"]).await?;
+        assert_eq!(assets.asset_index("NEW")?, 1);
+        assert_eq!(shared.assets().asset_index("NEW")?, 1);
+
+        // Further misses right away don't refetch
+        version.store(2, Ordering::SeqCst);
+        let assets = cache.assets_for(["TYPO"]).await?;
+        assert_eq!(assets.asset_index("BTC")?, 0);
+
+        cache.refresh().await?;
+        assert!(matches!(
+            shared.assets().asset_id("BTC"),
+            Err(Error::AssetDelisted(_))
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_loads_perp_dexs_on_every_fetch() -> Result<()> {
+        let base_url = serve(|_, body| {
+            if body.contains(r#""type":"perpDexs""#) {
+                r#"[null,{"name":"other"},{"name":"test"}]"#.to_string()
+            } else if body.contains(r#""dex":"test""#) {
+                r#"{"universe":[{"name":"test:ABC","szDecimals":2}]}"#.to_string()
+            } else if body.contains(r#""type":"spotMeta""#) {
+                EMPTY_SPOT_META.to_string()
+            } else {
+                r#"{"universe":[{"name":"BTC","szDecimals":5}]}"#.to_string()
+            }
+        })
+        .await;
+
+        let cache = MetaCache::new(base_url)
+            .await?
+            .with_perp_dexs(["test"])
+            .await?;
+        let order = ClientOrderRequest {
+            asset: "test:ABC".to_string(),
+            is_buy: true,
+            reduce_only: false,
+            limit_px: "10".parse()?,
+            sz: "1".parse()?,
+            cloid: None,
+            order_type: ClientOrder::Limit(ClientLimit {
+                tif: "Gtc".to_string(),
+            }),
+        };
+        // Second builder dex: 100000 + 2 * 10000
+        assert_eq!(order.convert(&cache.assets())?.asset, 120_000);
+
+        cache.refresh().await?;
+        assert_eq!(cache.assets().asset_index("test:ABC")?, 120_000);
+        assert_eq!(cache.assets().asset_index("BTC")?, 0);
+        Ok(())
+    }
+}
@@ -0,0 +1,868 @@
+use crate::{
+    prelude::*, AccountFilter, BasicOrderInfo, BookDepth, BracketOrderStatus, BuilderInfo,
+    CancelOutcome, CandlesSnapshotResponse, ClientBracketOrder, ClientCancelRequest,
+    ClientCancelRequestCloid, ClientModifyRequest, ClientModifyRequestCloid, ClientOrderRequest,
+    ClientTwapRequest, CloseOutcome, CreateSubAccountResponseStatus, CreateVaultResponseStatus,
+    DelegationResponse, DelegatorRewardResponse, DelegatorSummaryResponse, Error, ExchangeApi,
+    ExchangeResponseStatus, FillEstimate, FundingHistoryResponse, HyperliquidSigner, InfoApi,
+    L2SnapshotResponse, MarketCloseParams, MarketOrderParams, Message, Meta, OpenOrdersResponse,
+    OrderGrouping, OrderInfo, OrderStatusResponse, RecentTradesResponse, ReferralResponse,
+    ScaleOrder, ScaleOrderStatus, SignedAction, SpotMeta, SpotMetaAndAssetCtxs, SubAccountResponse,
+    Subscription, TwapResponseStatus, UserFeesResponse, UserFillsResponse, UserFundingResponse,
+    UserRateLimitResponse, UserStateResponse, UserTokenBalanceResponse, UserVaultEquity,
+    VaultDetailsResponse,
+};
+use ethers::types::H160;
+use serde::de::DeserializeOwned;
+use std::{
+    any::{type_name, Any},
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+use tokio::sync::mpsc::UnboundedSender;
+use uuid::Uuid;
+
+/// Method call received by a [`MockClient`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockCall {
+    /// Name of the [`ExchangeApi`] or [`InfoApi`] method
+    pub method: &'static str,
+    /// Debug representation of every argument except the wallet
+    pub args: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    /// Scripted results per method, each a boxed `Result<T>` of the method's return type
+    responses: HashMap<&'static str, VecDeque<Box<dyn Any + Send>>>,
+    calls: Vec<MockCall>,
+    subscribers: HashMap<u32, (Subscription, UnboundedSender<Message>)>,
+    next_subscription_id: u32,
+}
+
+/// In-memory [`ExchangeApi`] and [`InfoApi`] answering every call with the next result
+/// scripted for its method, for testing strategies without an exchange.
+///
+/// Calls without a scripted result fail with [`Error::GenericRequest`]. Subscriptions need
+/// no script: they receive whatever is passed to [`publish`](MockClient::publish).
+///
+/// Clones share the script and the recorded calls, so a test can keep one while the code
+/// under test owns another.
+#[derive(Debug, Clone, Default)]
+pub struct MockClient {
+    state: Arc<Mutex<MockState>>,
+}
+
+impl MockClient {
+    pub fn new() -> MockClient {
+        MockClient::default()
+    }
+
+    /// Queues `response` as the result of the next unanswered call to `method`.
+    ///
+    /// `T` must be the method's return type, e.g. [`ExchangeResponseStatus`] for `"order"`.
+    pub fn respond<T: Send + 'static>(&self, method: &'static str, response: Result<T>) -> &Self {
+        self.lock()
+            .responses
+            .entry(method)
+            .or_default()
+            .push_back(Box::new(response));
+        self
+    }
+
+    /// Queues the response the exchange would send as `json`. Invalid JSON is queued as an
+    /// [`Error::JsonParse`] for the call to return.
+    pub fn respond_json<T: DeserializeOwned + Send + 'static>(
+        &self,
+        method: &'static str,
+        json: &str,
+    ) -> &Self {
+        let response: Result<T> =
+            serde_json::from_str(json).map_err(|e| Error::JsonParse(e.to_string()));
+        self.respond(method, response)
+    }
+
+    /// Calls received so far, oldest first.
+    pub fn calls(&self) -> Vec<MockCall> {
+        self.lock().calls.clone()
+    }
+
+    /// Calls received so far to `method`.
+    pub fn calls_to(&self, method: &str) -> Vec<MockCall> {
+        self.lock()
+            .calls
+            .iter()
+            .filter(|call| call.method == method)
+            .cloned()
+            .collect()
+    }
+
+    /// Sends `message` to every active subscription, returning how many received it.
+    pub fn publish(&self, message: Message) -> usize {
+        let mut state = self.lock();
+        state
+            .subscribers
+            .retain(|_, (_, sender)| !sender.is_closed());
+        state
+            .subscribers
+            .values()
+            .filter(|(_, sender)| sender.send(message.clone()).is_ok())
+            .count()
+    }
+
+    /// Subscriptions not yet unsubscribed.
+    pub fn subscriptions(&self) -> Vec<Subscription> {
+        self.lock()
+            .subscribers
+            .values()
+            .map(|(subscription, _)| subscription.clone())
+            .collect()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, MockState> {
+        // A test panicking mid-call doesn't leave the script in an inconsistent state
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn call<T: 'static>(&self, method: &'static str, args: Vec<String>) -> Result<T> {
+        let mut state = self.lock();
+        state.calls.push(MockCall { method, args });
+        let response = state
+            .responses
+            .get_mut(method)
+            .and_then(VecDeque::pop_front)
+            .ok_or_else(|| Error::GenericRequest(format!("No response scripted for {method}")))?;
+        match response.downcast::<Result<T>>() {
+            Ok(response) => *response,
+            Err(_) => Err(Error::GenericRequest(format!(
+                "Response scripted for {method} is not a {}",
+                type_name::<Result<T>>()
+            ))),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl ExchangeApi for MockClient {
+    async fn submit_signed(&self, signed: &SignedAction) -> Result<ExchangeResponseStatus> {
+        self.call("submit_signed", vec![format!("{signed:?}")])
+    }
+
+    async fn usdc_transfer(
+        &self,
+        amount: &str,
+        destination: &str,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        self.call(
+            "usdc_transfer",
+            vec![format!("{amount:?}"), format!("{destination:?}")],
+        )
+    }
+
+    async fn class_transfer(
+        &self,
+        usdc: f64,
+        to_perp: bool,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        self.call(
+            "class_transfer",
+            vec![format!("{usdc:?}"), format!("{to_perp:?}")],
+        )
+    }
+
+    async fn vault_transfer(
+        &self,
+        is_deposit: bool,
+        usd: u64,
+        vault_address: Option<H160>,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        self.call(
+            "vault_transfer",
+            vec![
+                format!("{is_deposit:?}"),
+                format!("{usd:?}"),
+                format!("{vault_address:?}"),
+            ],
+        )
+    }
+
+    async fn create_vault(
+        &self,
+        name: String,
+        description: String,
+        initial_usd: u64,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<CreateVaultResponseStatus> {
+        self.call(
+            "create_vault",
+            vec![
+                format!("{name:?}"),
+                format!("{description:?}"),
+                format!("{initial_usd:?}"),
+            ],
+        )
+    }
+
+    async fn vault_modify(
+        &self,
+        vault_address: H160,
+        allow_deposits: Option<bool>,
+        always_close_on_withdraw: Option<bool>,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        self.call(
+            "vault_modify",
+            vec![
+                format!("{vault_address:?}"),
+                format!("{allow_deposits:?}"),
+                format!("{always_close_on_withdraw:?}"),
+            ],
+        )
+    }
+
+    async fn vault_distribute(
+        &self,
+        vault_address: H160,
+        usd: u64,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        self.call(
+            "vault_distribute",
+            vec![format!("{vault_address:?}"), format!("{usd:?}")],
+        )
+    }
+
+    async fn market_open(&self, params: MarketOrderParams<'_>) -> Result<ExchangeResponseStatus> {
+        self.call("market_open", vec![format!("{params:?}")])
+    }
+
+    async fn market_open_with_builder(
+        &self,
+        params: MarketOrderParams<'_>,
+        builder: BuilderInfo,
+    ) -> Result<ExchangeResponseStatus> {
+        self.call(
+            "market_open_with_builder",
+            vec![format!("{params:?}"), format!("{builder:?}")],
+        )
+    }
+
+    async fn market_close(&self, params: MarketCloseParams<'_>) -> Result<ExchangeResponseStatus> {
+        self.call("market_close", vec![format!("{params:?}")])
+    }
+
+    async fn market_open_with_book(
+        &self,
+        params: MarketOrderParams<'_>,
+        book: Option<&BookDepth>,
+    ) -> Result<(ExchangeResponseStatus, FillEstimate)> {
+        self.call(
+            "market_open_with_book",
+            vec![format!("{params:?}"), format!("{book:?}")],
+        )
+    }
+
+    async fn market_close_with_book(
+        &self,
+        params: MarketCloseParams<'_>,
+        book: Option<&BookDepth>,
+    ) -> Result<(ExchangeResponseStatus, FillEstimate)> {
+        self.call(
+            "market_close_with_book",
+            vec![format!("{params:?}"), format!("{book:?}")],
+        )
+    }
+
+    async fn order(
+        &self,
+        order: ClientOrderRequest,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        self.call("order", vec![format!("{order:?}")])
+    }
+
+    async fn order_with_builder(
+        &self,
+        order: ClientOrderRequest,
+        _wallet: Option<&dyn HyperliquidSigner>,
+        builder: BuilderInfo,
+    ) -> Result<ExchangeResponseStatus> {
+        self.call(
+            "order_with_builder",
+            vec![format!("{order:?}"), format!("{builder:?}")],
+        )
+    }
+
+    async fn bulk_order(
+        &self,
+        orders: Vec<ClientOrderRequest>,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        self.call("bulk_order", vec![format!("{orders:?}")])
+    }
+
+    async fn bracket_order(
+        &self,
+        bracket: ClientBracketOrder,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<BracketOrderStatus> {
+        self.call("bracket_order", vec![format!("{bracket:?}")])
+    }
+
+    async fn scale_order(
+        &self,
+        scale: ScaleOrder,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ScaleOrderStatus> {
+        self.call("scale_order", vec![format!("{scale:?}")])
+    }
+
+    async fn bulk_order_with_grouping(
+        &self,
+        orders: Vec<ClientOrderRequest>,
+        grouping: OrderGrouping,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        self.call(
+            "bulk_order_with_grouping",
+            vec![format!("{orders:?}"), format!("{grouping:?}")],
+        )
+    }
+
+    async fn bulk_order_with_builder(
+        &self,
+        orders: Vec<ClientOrderRequest>,
+        _wallet: Option<&dyn HyperliquidSigner>,
+        builder: BuilderInfo,
+    ) -> Result<ExchangeResponseStatus> {
+        self.call(
+            "bulk_order_with_builder",
+            vec![format!("{orders:?}"), format!("{builder:?}")],
+        )
+    }
+
+    async fn cancel(
+        &self,
+        cancel: ClientCancelRequest,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        self.call("cancel", vec![format!("{cancel:?}")])
+    }
+
+    async fn bulk_cancel(
+        &self,
+        cancels: Vec<ClientCancelRequest>,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        self.call("bulk_cancel", vec![format!("{cancels:?}")])
+    }
+
+    async fn modify(
+        &self,
+        modify: ClientModifyRequest,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        self.call("modify", vec![format!("{modify:?}")])
+    }
+
+    async fn bulk_modify(
+        &self,
+        modifies: Vec<ClientModifyRequest>,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        self.call("bulk_modify", vec![format!("{modifies:?}")])
+    }
+
+    async fn modify_by_cloid(
+        &self,
+        modify: ClientModifyRequestCloid,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        self.call("modify_by_cloid", vec![format!("{modify:?}")])
+    }
+
+    async fn bulk_modify_by_cloid(
+        &self,
+        modifies: Vec<ClientModifyRequestCloid>,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        self.call("bulk_modify_by_cloid", vec![format!("{modifies:?}")])
+    }
+
+    async fn cancel_by_cloid(
+        &self,
+        cancel: ClientCancelRequestCloid,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        self.call("cancel_by_cloid", vec![format!("{cancel:?}")])
+    }
+
+    async fn bulk_cancel_by_cloid(
+        &self,
+        cancels: Vec<ClientCancelRequestCloid>,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        self.call("bulk_cancel_by_cloid", vec![format!("{cancels:?}")])
+    }
+
+    async fn update_leverage(
+        &self,
+        leverage: u32,
+        coin: &str,
+        is_cross: bool,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        self.call(
+            "update_leverage",
+            vec![
+                format!("{leverage:?}"),
+                format!("{coin:?}"),
+                format!("{is_cross:?}"),
+            ],
+        )
+    }
+
+    async fn update_isolated_margin(
+        &self,
+        amount: f64,
+        coin: &str,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        self.call(
+            "update_isolated_margin",
+            vec![format!("{amount:?}"), format!("{coin:?}")],
+        )
+    }
+
+    async fn approve_agent(
+        &self,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<(String, ExchangeResponseStatus)> {
+        self.call("approve_agent", vec![])
+    }
+
+    async fn withdraw_from_bridge(
+        &self,
+        amount: &str,
+        destination: &str,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        self.call(
+            "withdraw_from_bridge",
+            vec![format!("{amount:?}"), format!("{destination:?}")],
+        )
+    }
+
+    async fn spot_transfer(
+        &self,
+        amount: &str,
+        destination: &str,
+        token: &str,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        self.call(
+            "spot_transfer",
+            vec![
+                format!("{amount:?}"),
+                format!("{destination:?}"),
+                format!("{token:?}"),
+            ],
+        )
+    }
+
+    async fn c_deposit(
+        &self,
+        wei: u64,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        self.call("c_deposit", vec![format!("{wei:?}")])
+    }
+
+    async fn c_withdraw(
+        &self,
+        wei: u64,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        self.call("c_withdraw", vec![format!("{wei:?}")])
+    }
+
+    async fn token_delegate(
+        &self,
+        validator: H160,
+        wei: u64,
+        is_undelegate: bool,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        self.call(
+            "token_delegate",
+            vec![
+                format!("{validator:?}"),
+                format!("{wei:?}"),
+                format!("{is_undelegate:?}"),
+            ],
+        )
+    }
+
+    async fn set_referrer(
+        &self,
+        code: String,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        self.call("set_referrer", vec![format!("{code:?}")])
+    }
+
+    async fn approve_builder_fee(
+        &self,
+        builder: String,
+        max_fee_rate: String,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        self.call(
+            "approve_builder_fee",
+            vec![format!("{builder:?}"), format!("{max_fee_rate:?}")],
+        )
+    }
+
+    async fn schedule_cancel(
+        &self,
+        time: Option<u64>,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        self.call("schedule_cancel", vec![format!("{time:?}")])
+    }
+
+    async fn create_sub_account(
+        &self,
+        name: String,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<CreateSubAccountResponseStatus> {
+        self.call("create_sub_account", vec![format!("{name:?}")])
+    }
+
+    async fn sub_account_transfer(
+        &self,
+        sub_account_user: H160,
+        is_deposit: bool,
+        usd: u64,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        self.call(
+            "sub_account_transfer",
+            vec![
+                format!("{sub_account_user:?}"),
+                format!("{is_deposit:?}"),
+                format!("{usd:?}"),
+            ],
+        )
+    }
+
+    async fn sub_account_spot_transfer(
+        &self,
+        sub_account_user: H160,
+        is_deposit: bool,
+        token: &str,
+        amount: &str,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<ExchangeResponseStatus> {
+        self.call(
+            "sub_account_spot_transfer",
+            vec![
+                format!("{sub_account_user:?}"),
+                format!("{is_deposit:?}"),
+                format!("{token:?}"),
+                format!("{amount:?}"),
+            ],
+        )
+    }
+
+    async fn twap_order(
+        &self,
+        twap: ClientTwapRequest,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<TwapResponseStatus> {
+        self.call("twap_order", vec![format!("{twap:?}")])
+    }
+
+    async fn twap_cancel(
+        &self,
+        asset: &str,
+        twap_id: u64,
+        _wallet: Option<&dyn HyperliquidSigner>,
+    ) -> Result<TwapResponseStatus> {
+        self.call(
+            "twap_cancel",
+            vec![format!("{asset:?}"), format!("{twap_id:?}")],
+        )
+    }
+
+    async fn cancel_all(&self, filter: &AccountFilter) -> Result<Vec<CancelOutcome>> {
+        self.call("cancel_all", vec![format!("{filter:?}")])
+    }
+
+    async fn close_all_positions(
+        &self,
+        filter: &AccountFilter,
+        slippage: Option<f64>,
+    ) -> Result<Vec<CloseOutcome>> {
+        self.call(
+            "close_all_positions",
+            vec![format!("{filter:?}"), format!("{slippage:?}")],
+        )
+    }
+}
+
+#[async_trait::async_trait]
+impl InfoApi for MockClient {
+    async fn subscribe(
+        &mut self,
+        subscription: Subscription,
+        sender_channel: UnboundedSender<Message>,
+    ) -> Result<u32> {
+        let mut state = self.lock();
+        state.calls.push(MockCall {
+            method: "subscribe",
+            args: vec![format!("{subscription:?}")],
+        });
+        let subscription_id = state.next_subscription_id;
+        state.next_subscription_id += 1;
+        state
+            .subscribers
+            .insert(subscription_id, (subscription, sender_channel));
+        Ok(subscription_id)
+    }
+
+    async fn unsubscribe(&mut self, subscription_id: u32) -> Result<()> {
+        let mut state = self.lock();
+        state.calls.push(MockCall {
+            method: "unsubscribe",
+            args: vec![format!("{subscription_id:?}")],
+        });
+        state
+            .subscribers
+            .remove(&subscription_id)
+            .map(|_| ())
+            .ok_or(Error::SubscriptionNotFound)
+    }
+
+    async fn open_orders(&self, address: H160) -> Result<Vec<OpenOrdersResponse>> {
+        self.call("open_orders", vec![format!("{address:?}")])
+    }
+
+    async fn frontend_open_orders(&self, address: H160) -> Result<Vec<BasicOrderInfo>> {
+        self.call("frontend_open_orders", vec![format!("{address:?}")])
+    }
+
+    async fn user_state(&self, address: H160) -> Result<UserStateResponse> {
+        self.call("user_state", vec![format!("{address:?}")])
+    }
+
+    async fn user_states(&self, addresses: Vec<H160>) -> Result<Vec<UserStateResponse>> {
+        self.call("user_states", vec![format!("{addresses:?}")])
+    }
+
+    async fn user_token_balances(&self, address: H160) -> Result<UserTokenBalanceResponse> {
+        self.call("user_token_balances", vec![format!("{address:?}")])
+    }
+
+    async fn user_fees(&self, address: H160) -> Result<UserFeesResponse> {
+        self.call("user_fees", vec![format!("{address:?}")])
+    }
+
+    async fn user_rate_limit(&self, address: H160) -> Result<UserRateLimitResponse> {
+        self.call("user_rate_limit", vec![format!("{address:?}")])
+    }
+
+    async fn meta(&self) -> Result<Meta> {
+        self.call("meta", vec![])
+    }
+
+    async fn spot_meta(&self) -> Result<SpotMeta> {
+        self.call("spot_meta", vec![])
+    }
+
+    async fn spot_meta_and_asset_contexts(&self) -> Result<Vec<SpotMetaAndAssetCtxs>> {
+        self.call("spot_meta_and_asset_contexts", vec![])
+    }
+
+    async fn all_mids(&self) -> Result<HashMap<String, String>> {
+        self.call("all_mids", vec![])
+    }
+
+    async fn user_fills(&self, address: H160) -> Result<Vec<UserFillsResponse>> {
+        self.call("user_fills", vec![format!("{address:?}")])
+    }
+
+    async fn funding_history(
+        &self,
+        coin: String,
+        start_time: u64,
+        end_time: Option<u64>,
+    ) -> Result<Vec<FundingHistoryResponse>> {
+        self.call(
+            "funding_history",
+            vec![
+                format!("{coin:?}"),
+                format!("{start_time:?}"),
+                format!("{end_time:?}"),
+            ],
+        )
+    }
+
+    async fn user_funding_history(
+        &self,
+        user: H160,
+        start_time: u64,
+        end_time: Option<u64>,
+    ) -> Result<Vec<UserFundingResponse>> {
+        self.call(
+            "user_funding_history",
+            vec![
+                format!("{user:?}"),
+                format!("{start_time:?}"),
+                format!("{end_time:?}"),
+            ],
+        )
+    }
+
+    async fn recent_trades(&self, coin: String) -> Result<Vec<RecentTradesResponse>> {
+        self.call("recent_trades", vec![format!("{coin:?}")])
+    }
+
+    async fn l2_snapshot(&self, coin: String) -> Result<L2SnapshotResponse> {
+        self.call("l2_snapshot", vec![format!("{coin:?}")])
+    }
+
+    async fn candles_snapshot(
+        &self,
+        coin: String,
+        interval: String,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<Vec<CandlesSnapshotResponse>> {
+        self.call(
+            "candles_snapshot",
+            vec![
+                format!("{coin:?}"),
+                format!("{interval:?}"),
+                format!("{start_time:?}"),
+                format!("{end_time:?}"),
+            ],
+        )
+    }
+
+    async fn query_order_by_oid(&self, address: H160, oid: u64) -> Result<OrderStatusResponse> {
+        self.call(
+            "query_order_by_oid",
+            vec![format!("{address:?}"), format!("{oid:?}")],
+        )
+    }
+
+    async fn query_order_by_cloid(
+        &self,
+        address: H160,
+        cloid: Uuid,
+    ) -> Result<OrderStatusResponse> {
+        self.call(
+            "query_order_by_cloid",
+            vec![format!("{address:?}"), format!("{cloid:?}")],
+        )
+    }
+
+    async fn query_referral_state(&self, address: H160) -> Result<ReferralResponse> {
+        self.call("query_referral_state", vec![format!("{address:?}")])
+    }
+
+    async fn historical_orders(&self, address: H160) -> Result<Vec<OrderInfo>> {
+        self.call("historical_orders", vec![format!("{address:?}")])
+    }
+
+    async fn sub_accounts(&self, address: H160) -> Result<Vec<SubAccountResponse>> {
+        self.call("sub_accounts", vec![format!("{address:?}")])
+    }
+
+    async fn vault_details(
+        &self,
+        vault_address: H160,
+        user: Option<H160>,
+    ) -> Result<VaultDetailsResponse> {
+        self.call(
+            "vault_details",
+            vec![format!("{vault_address:?}"), format!("{user:?}")],
+        )
+    }
+
+    async fn user_vault_equities(&self, address: H160) -> Result<Vec<UserVaultEquity>> {
+        self.call("user_vault_equities", vec![format!("{address:?}")])
+    }
+
+    async fn delegations(&self, address: H160) -> Result<Vec<DelegationResponse>> {
+        self.call("delegations", vec![format!("{address:?}")])
+    }
+
+    async fn delegator_summary(&self, address: H160) -> Result<DelegatorSummaryResponse> {
+        self.call("delegator_summary", vec![format!("{address:?}")])
+    }
+
+    async fn delegator_rewards(&self, address: H160) -> Result<Vec<DelegatorRewardResponse>> {
+        self.call("delegator_rewards", vec![format!("{address:?}")])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AllMids, AllMidsData, ClientCancelRequest};
+    use tokio::sync::mpsc::unbounded_channel;
+
+    #[tokio::test]
+    async fn test_answers_calls_from_script() {
+        let mock = MockClient::new();
+        mock.respond_json::<ExchangeResponseStatus>(
+            "cancel",
+            r#"{"status":"ok","response":{"type":"cancel","data":{"statuses":["success"]}}}"#,
+        )
+        .respond("cancel", Ok(1u64));
+        let exchange: &dyn ExchangeApi = &mock;
+
+        let cancel = || ClientCancelRequest {
+            asset: "ETH".to_string(),
+            oid: 7,
+        };
+        assert!(matches!(
+            exchange.cancel(cancel(), None).await,
+            Ok(ExchangeResponseStatus::Ok(_))
+        ));
+        // Scripted with the wrong type
+        assert!(exchange.cancel(cancel(), None).await.is_err());
+        // Nothing left in the script
+        assert!(matches!(
+            exchange.cancel(cancel(), None).await,
+            Err(Error::GenericRequest(_))
+        ));
+        assert_eq!(mock.calls_to("cancel").len(), 3);
+        assert!(mock.calls()[0].args[0].contains("oid: 7"));
+    }
+
+    #[tokio::test]
+    async fn test_publishes_to_subscribers() -> Result<()> {
+        let mut mock = MockClient::new();
+        let (sender, mut receiver) = unbounded_channel();
+        let subscription_id = mock.subscribe(Subscription::AllMids, sender).await?;
+
+        let mids = Message::AllMids(AllMids {
+            data: AllMidsData {
+                mids: HashMap::from([("ETH".to_string(), "2000.5".to_string())]),
+            },
+        });
+        assert_eq!(mock.publish(mids.clone()), 1);
+        assert!(matches!(receiver.recv().await, Some(Message::AllMids(_))));
+
+        mock.unsubscribe(subscription_id).await?;
+        assert_eq!(mock.publish(mids), 0);
+        assert!(mock.subscriptions().is_empty());
+        Ok(())
+    }
+}
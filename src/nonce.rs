@@ -0,0 +1,376 @@
+use crate::{helpers::now_timestamp_ms, prelude::*, Error, InfoClient};
+use ethers::types::H160;
+use fs2::FileExt;
+use log::info;
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt::Debug,
+    fs::{self, OpenOptions},
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicI64, Ordering},
+        Arc, LazyLock, Mutex,
+    },
+};
+
+/// The exchange remembers this many of the highest nonces of every signer. A nonce is
+/// accepted if it is above the smallest of them and not one of them.
+const RECENT_NONCES: usize = 100;
+
+/// Coin whose order book timestamp is used as the server time by [`NonceManager::sync_clock`].
+const CLOCK_SYNC_COIN: &str = "BTC";
+
+/// Function turning the last nonce of a signer, `None` if it has none yet, into the next one.
+pub type NextNonce = Box<dyn FnOnce(Option<u64>) -> u64 + Send>;
+
+/// Persists the last nonce used by every signer.
+///
+/// Stores shared between processes must make [`update`](NonceStore::update) atomic across
+/// all of them, that is what keeps processes using the same agent key from colliding.
+/// Stores doing blocking IO must move it off the async runtime, as
+/// [`FileNonceStore`] does.
+#[async_trait::async_trait]
+pub trait NonceStore: Debug + Send + Sync {
+    /// Replaces the last nonce of `address` with `next(last)` and returns the new value.
+    async fn update(&self, address: H160, next: NextNonce) -> Result<u64>;
+}
+
+/// Keeps nonces in memory, for signers used by a single process.
+#[derive(Debug, Default)]
+pub struct InMemoryNonceStore {
+    last: Mutex<HashMap<H160, u64>>,
+}
+
+impl InMemoryNonceStore {
+    pub fn new() -> InMemoryNonceStore {
+        InMemoryNonceStore::default()
+    }
+}
+
+#[async_trait::async_trait]
+impl NonceStore for InMemoryNonceStore {
+    async fn update(&self, address: H160, next: NextNonce) -> Result<u64> {
+        let mut last = self
+            .last
+            .lock()
+            .map_err(|e| Error::NonceStore(e.to_string()))?;
+        let nonce = next(last.get(&address).copied());
+        last.insert(address, nonce);
+        Ok(nonce)
+    }
+}
+
+/// Keeps nonces in one file per signer in a directory, so that processes on the same
+/// machine sharing an agent key never use the same nonce.
+///
+/// Updates hold an OS advisory lock on a lock file next to the nonce file, which the OS
+/// releases when a process holding it exits or crashes. The file IO and the wait for the
+/// lock run on tokio's blocking threads.
+#[derive(Debug, Clone)]
+pub struct FileNonceStore {
+    dir: PathBuf,
+}
+
+impl FileNonceStore {
+    /// Uses `dir`, creating it if needed.
+    pub fn new(dir: impl Into<PathBuf>) -> Result<FileNonceStore> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir).map_err(|e| Error::NonceStore(e.to_string()))?;
+        Ok(FileNonceStore { dir })
+    }
+
+    fn read(path: &Path) -> Result<Option<u64>> {
+        match fs::read_to_string(path) {
+            Ok(contents) => {
+                contents.trim().parse().map(Some).map_err(|_| {
+                    Error::NonceStore(format!("Corrupt nonce file {}", path.display()))
+                })
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(Error::NonceStore(e.to_string())),
+        }
+    }
+
+    fn write(path: &Path, nonce: u64) -> Result<()> {
+        // Written next to the nonce file and renamed over it so a crash never leaves it
+        // half written
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, nonce.to_string())
+            .and_then(|_| fs::rename(&tmp_path, path))
+            .map_err(|e| Error::NonceStore(e.to_string()))
+    }
+
+    fn update_blocking(path: &Path, next: NextNonce) -> Result<u64> {
+        // The lock file is never removed, removing it while another process waits on it
+        // would let a third one lock a new file at the same path
+        let lock = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path.with_extension("lock"))
+            .map_err(|e| Error::NonceStore(e.to_string()))?;
+        lock.lock_exclusive()
+            .map_err(|e| Error::NonceStore(e.to_string()))?;
+
+        let res = Self::read(path).and_then(|last| {
+            let nonce = next(last);
+            Self::write(path, nonce)?;
+            Ok(nonce)
+        });
+        let _ = FileExt::unlock(&lock);
+        res
+    }
+}
+
+#[async_trait::async_trait]
+impl NonceStore for FileNonceStore {
+    async fn update(&self, address: H160, next: NextNonce) -> Result<u64> {
+        let path = self.dir.join(format!("{address:?}.nonce"));
+        tokio::task::spawn_blocking(move || Self::update_blocking(&path, next))
+            .await
+            .map_err(|e| Error::NonceStore(e.to_string()))?
+    }
+}
+
+#[derive(Debug)]
+struct NonceManagerInner {
+    store: Box<dyn NonceStore>,
+    clock_offset_ms: AtomicI64,
+    recent: Mutex<HashMap<H160, BTreeSet<u64>>>,
+}
+
+/// Hands out nonces per signer address.
+///
+/// Nonces are the current time in milliseconds, corrected by the offset to the server's
+/// clock, and strictly increase per signer across everything sharing the [`NonceStore`].
+/// Nonces of actions signed elsewhere are checked with [`register`](NonceManager::register)
+/// so that a payload submitted twice is caught before the exchange rejects it.
+///
+/// Clones share their state, as do all clients using [`NonceManager::shared`].
+#[derive(Debug, Clone)]
+pub struct NonceManager {
+    inner: Arc<NonceManagerInner>,
+}
+
+static SHARED: LazyLock<NonceManager> = LazyLock::new(NonceManager::in_memory);
+
+impl NonceManager {
+    pub fn new(store: impl NonceStore + 'static) -> NonceManager {
+        NonceManager {
+            inner: Arc::new(NonceManagerInner {
+                store: Box::new(store),
+                clock_offset_ms: AtomicI64::new(0),
+                recent: Mutex::new(HashMap::new()),
+            }),
+        }
+    }
+
+    pub fn in_memory() -> NonceManager {
+        NonceManager::new(InMemoryNonceStore::new())
+    }
+
+    /// Manager backed by a [`FileNonceStore`] in `dir`.
+    pub fn file(dir: impl Into<PathBuf>) -> Result<NonceManager> {
+        Ok(NonceManager::new(FileNonceStore::new(dir)?))
+    }
+
+    /// In-memory manager shared by every [`ExchangeClient`](crate::ExchangeClient) that
+    /// wasn't given one.
+    pub fn shared() -> NonceManager {
+        SHARED.clone()
+    }
+
+    /// Milliseconds the server's clock is ahead of the local one.
+    pub fn clock_offset_ms(&self) -> i64 {
+        self.inner.clock_offset_ms.load(Ordering::Relaxed)
+    }
+
+    pub fn set_clock_offset_ms(&self, offset_ms: i64) {
+        self.inner
+            .clock_offset_ms
+            .store(offset_ms, Ordering::Relaxed);
+    }
+
+    /// Sets the clock offset from a server timestamp taken just now.
+    pub fn observe_server_time(&self, server_time_ms: u64) {
+        self.set_clock_offset_ms(server_time_ms as i64 - now_timestamp_ms() as i64);
+    }
+
+    /// Measures the clock offset against the timestamp of an order book snapshot and returns
+    /// it. The request's round trip is split evenly between both directions.
+    pub async fn sync_clock(&self, info_client: &InfoClient) -> Result<i64> {
+        let sent = now_timestamp_ms();
+        let snapshot = info_client.l2_snapshot(CLOCK_SYNC_COIN.to_string()).await?;
+        let received = now_timestamp_ms();
+
+        let offset_ms = snapshot.time as i64 - (sent + received) as i64 / 2;
+        self.set_clock_offset_ms(offset_ms);
+        Ok(offset_ms)
+    }
+
+    /// Current server time in milliseconds according to the local clock and the offset.
+    pub fn server_time_ms(&self) -> u64 {
+        (now_timestamp_ms() as i64 + self.clock_offset_ms()).max(0) as u64
+    }
+
+    /// Reserves the next nonce for `address`: the server time, or one more than the last
+    /// nonce in the store if that is later.
+    pub async fn next_nonce(&self, address: H160) -> Result<u64> {
+        let now_ms = self.server_time_ms();
+        let next = move |last: Option<u64>| last.map_or(now_ms, |last| now_ms.max(last + 1));
+        let nonce = self.inner.store.update(address, Box::new(next)).await?;
+        if nonce > now_ms + 1000 {
+            info!("nonce progressed too far ahead {nonce} {now_ms}");
+        }
+        self.remember(address, nonce)?;
+        Ok(nonce)
+    }
+
+    /// Records a nonce chosen outside this manager, such as the one of a
+    /// [`SignedAction`](crate::SignedAction), and fails with [`Error::NonceReused`] if the
+    /// exchange would reject it as already used.
+    pub async fn register(&self, address: H160, nonce: u64) -> Result<()> {
+        // Stored before it is remembered, so a nonce the store failed to record can be
+        // registered again
+        let next = move |last: Option<u64>| last.unwrap_or(0).max(nonce);
+        self.inner.store.update(address, Box::new(next)).await?;
+        self.remember(address, nonce)
+    }
+
+    fn remember(&self, address: H160, nonce: u64) -> Result<()> {
+        let mut recent = self
+            .inner
+            .recent
+            .lock()
+            .map_err(|e| Error::NonceStore(e.to_string()))?;
+        let recent = recent.entry(address).or_default();
+
+        let too_old =
+            recent.len() >= RECENT_NONCES && recent.first().is_some_and(|&lowest| nonce <= lowest);
+        if too_old || !recent.insert(nonce) {
+            return Err(Error::NonceReused { address, nonce });
+        }
+        if recent.len() > RECENT_NONCES {
+            recent.pop_first();
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Store whose first update fails
+    #[derive(Debug, Default)]
+    struct FlakyNonceStore {
+        failed: std::sync::atomic::AtomicBool,
+        inner: InMemoryNonceStore,
+    }
+
+    #[async_trait::async_trait]
+    impl NonceStore for FlakyNonceStore {
+        async fn update(&self, address: H160, next: NextNonce) -> Result<u64> {
+            if !self.failed.swap(true, Ordering::Relaxed) {
+                return Err(Error::NonceStore("disk full".to_string()));
+            }
+            self.inner.update(address, next).await
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("hl-nonces-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[tokio::test]
+    async fn test_nonces_increase_per_signer() -> Result<()> {
+        let manager = NonceManager::in_memory();
+        let (a, b) = (H160::from_low_u64_be(1), H160::from_low_u64_be(2));
+
+        let first = manager.next_nonce(a).await?;
+        let second = manager.next_nonce(a).await?;
+        assert!(second > first);
+        assert!(first.abs_diff(now_timestamp_ms()) < 1000);
+        assert!(manager.next_nonce(b).await? < second + 1000);
+
+        manager.set_clock_offset_ms(60_000);
+        assert!(manager.next_nonce(a).await? >= now_timestamp_ms() + 59_000);
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_file_store_shared_between_managers() -> Result<()> {
+        let dir = temp_dir("shared");
+        let address = H160::from_low_u64_be(1);
+
+        // Two managers on the same directory stand in for two processes
+        let managers = [NonceManager::file(&dir)?, NonceManager::file(&dir)?];
+        let handles: Vec<_> = managers
+            .into_iter()
+            .map(|manager| {
+                tokio::spawn(async move {
+                    let mut nonces = Vec::new();
+                    for _ in 0..200 {
+                        nonces.push(manager.next_nonce(address).await?);
+                    }
+                    Ok::<_, Error>(nonces)
+                })
+            })
+            .collect();
+
+        let mut nonces = BTreeSet::new();
+        for handle in handles {
+            for nonce in handle.await.unwrap()? {
+                assert!(nonces.insert(nonce), "nonce {nonce} handed out twice");
+            }
+        }
+
+        let next = NonceManager::file(&dir)?.next_nonce(address).await?;
+        assert!(next > *nonces.last().unwrap());
+        fs::remove_dir_all(&dir).unwrap();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reused_nonce_detection() -> Result<()> {
+        let manager = NonceManager::in_memory();
+        let address = H160::from_low_u64_be(1);
+
+        let nonce = now_timestamp_ms() + 10_000;
+        manager.register(address, nonce).await?;
+        assert!(matches!(
+            manager.register(address, nonce).await,
+            Err(Error::NonceReused { .. })
+        ));
+        // Generated nonces move past registered ones
+        assert!(manager.next_nonce(address).await? > nonce);
+
+        // Once the window is full, nonces at or below its lowest are rejected as well
+        let manager = NonceManager::in_memory();
+        for nonce in 1..=RECENT_NONCES as u64 {
+            manager.register(address, nonce * 10).await?;
+        }
+        assert!(manager.register(address, 5).await.is_err());
+        manager.register(address, 15).await?;
+        assert!(manager.register(address, 10).await.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_register_retry_after_store_failure() -> Result<()> {
+        let manager = NonceManager::new(FlakyNonceStore::default());
+        let address = H160::from_low_u64_be(1);
+
+        let nonce = now_timestamp_ms();
+        assert!(matches!(
+            manager.register(address, nonce).await,
+            Err(Error::NonceStore(_))
+        ));
+        manager.register(address, nonce).await?;
+        assert!(manager.next_nonce(address).await? > nonce);
+        Ok(())
+    }
+}
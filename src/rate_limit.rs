@@ -0,0 +1,319 @@
+use crate::{prelude::*, Error, InfoClient, UserRateLimitResponse};
+use ethers::types::H160;
+use log::debug;
+use serde_json::Value;
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// Weight of all REST requests from one IP address the exchange accepts per minute.
+pub const IP_WEIGHT_PER_MINUTE: u32 = 1200;
+
+/// Extra requests an address over its request cap may still send cancels with.
+const CANCEL_CAP_BONUS: u64 = 100_000;
+
+/// An address over its request cap may still send one request this often.
+const OVER_CAP_INTERVAL: Duration = Duration::from_secs(10);
+
+/// What a [`RateLimiter`] does with requests exceeding the budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitMode {
+    /// Delay requests until the budget allows them
+    Queue,
+    /// Fail requests with [`Error::RateLimited`]
+    Reject,
+}
+
+/// Weight of an `/info` request of the given `type`.
+pub fn info_weight(request_type: &str) -> u32 {
+    match request_type {
+        "l2Book"
+        | "allMids"
+        | "clearinghouseState"
+        | "orderStatus"
+        | "spotClearinghouseState"
+        | "exchangeStatus" => 2,
+        "userRole" => 60,
+        _ => 20,
+    }
+}
+
+/// Weight of an `/exchange` request carrying `batch_len` orders, cancels or modifies.
+pub fn exchange_weight(batch_len: usize) -> u32 {
+    1 + (batch_len / 40) as u32
+}
+
+#[derive(Debug)]
+struct IpBudget {
+    tokens: f64,
+    updated: Instant,
+}
+
+impl IpBudget {
+    const PER_SECOND: f64 = IP_WEIGHT_PER_MINUTE as f64 / 60.0;
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * Self::PER_SECOND).min(IP_WEIGHT_PER_MINUTE as f64);
+        self.updated = now;
+    }
+}
+
+#[derive(Debug, Default)]
+struct AddressBudget {
+    /// Requests used and allowed as last reported by the exchange, plus those sent since
+    synced: Option<(u64, u64)>,
+    last_over_cap: Option<Instant>,
+}
+
+/// Budgets requests against the exchange's rate limits before they are sent, instead of
+/// finding out from `429` responses.
+///
+/// Every request spends its weight from the IP budget of [`IP_WEIGHT_PER_MINUTE`], which
+/// refills continuously. Exchange actions also spend one request per order, cancel or modify
+/// from the address budget once it was synced with [`sync`](RateLimiter::sync); until then
+/// only the IP budget is enforced.
+///
+/// Clones share both budgets. Clients of several accounts behind the same IP address should
+/// use [`for_account`](RateLimiter::for_account) so each account gets its own address budget.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    mode: RateLimitMode,
+    ip: Arc<Mutex<IpBudget>>,
+    address: Arc<Mutex<AddressBudget>>,
+}
+
+impl RateLimiter {
+    pub fn new(mode: RateLimitMode) -> RateLimiter {
+        RateLimiter {
+            mode,
+            ip: Arc::new(Mutex::new(IpBudget {
+                tokens: IP_WEIGHT_PER_MINUTE as f64,
+                updated: Instant::now(),
+            })),
+            address: Arc::new(Mutex::new(AddressBudget::default())),
+        }
+    }
+
+    /// Limiter sharing this one's IP budget, with an address budget of its own.
+    pub fn for_account(&self) -> RateLimiter {
+        RateLimiter {
+            address: Arc::new(Mutex::new(AddressBudget::default())),
+            ..self.clone()
+        }
+    }
+
+    pub fn mode(&self) -> RateLimitMode {
+        self.mode
+    }
+
+    /// IP weight currently available.
+    pub fn available_weight(&self) -> Result<u32> {
+        let mut ip = lock(&self.ip)?;
+        ip.refill(Instant::now());
+        Ok(ip.tokens.max(0.0) as u32)
+    }
+
+    /// Requests the account may still send before reaching its cap, if synced.
+    pub fn remaining_requests(&self) -> Result<Option<u64>> {
+        Ok(lock(&self.address)?
+            .synced
+            .map(|(used, cap)| cap.saturating_sub(used)))
+    }
+
+    /// Replaces the address budget with the one the exchange reported.
+    pub fn observe_user_rate_limit(&self, rate_limit: &UserRateLimitResponse) -> Result<()> {
+        let mut address = lock(&self.address)?;
+        address.synced = Some((rate_limit.n_requests_used, rate_limit.n_requests_cap));
+        Ok(())
+    }
+
+    /// Fetches the address budget of `user` and adopts it.
+    pub async fn sync(
+        &self,
+        info_client: &InfoClient,
+        user: H160,
+    ) -> Result<UserRateLimitResponse> {
+        let rate_limit = info_client.user_rate_limit(user).await?;
+        self.observe_user_rate_limit(&rate_limit)?;
+        Ok(rate_limit)
+    }
+
+    /// Empties the IP budget, for when the exchange answered `429` regardless.
+    pub(crate) fn exhaust(&self) -> Result<()> {
+        lock(&self.ip)?.tokens = 0.0;
+        Ok(())
+    }
+
+    /// Waits for or rejects a request to `url_path` with body `data`.
+    pub(crate) async fn acquire(&self, url_path: &str, data: &str) -> Result<()> {
+        let body: Value = serde_json::from_str(data).unwrap_or_default();
+        match url_path {
+            "/exchange" => {
+                let action = &body["action"];
+                let batch_len = ["orders", "cancels", "modifies"]
+                    .iter()
+                    .find_map(|key| action[key].as_array())
+                    .map_or(1, |batch| batch.len());
+                let is_cancel = matches!(action["type"].as_str(), Some("cancel" | "cancelByCloid"));
+
+                self.acquire_address(batch_len as u64, is_cancel).await?;
+                if let Err(e) = self.acquire_ip(exchange_weight(batch_len)).await {
+                    // The request is never sent, so it doesn't count against the address
+                    self.refund_address(batch_len as u64)?;
+                    return Err(e);
+                }
+                Ok(())
+            }
+            _ => {
+                let weight = info_weight(body["type"].as_str().unwrap_or_default());
+                self.acquire_ip(weight).await
+            }
+        }
+    }
+
+    async fn acquire_ip(&self, weight: u32) -> Result<()> {
+        let wait = {
+            let mut ip = lock(&self.ip)?;
+            ip.refill(Instant::now());
+            let weight = weight as f64;
+            if ip.tokens < weight && self.mode == RateLimitMode::Reject {
+                return Err(Error::RateLimited(format!(
+                    "request weight {weight} exceeds the {} left of the IP budget",
+                    ip.tokens.max(0.0) as u32
+                )));
+            }
+            // Queued requests reserve their weight right away, so they are served in order
+            ip.tokens -= weight;
+            Duration::from_secs_f64((-ip.tokens).max(0.0) / IpBudget::PER_SECOND)
+        };
+        if !wait.is_zero() {
+            debug!("IP budget exhausted, waiting {wait:?}");
+            tokio::time::sleep(wait).await;
+        }
+        Ok(())
+    }
+
+    async fn acquire_address(&self, requests: u64, is_cancel: bool) -> Result<()> {
+        let wait = {
+            let mut address = lock(&self.address)?;
+            let Some((used, synced_cap)) = address.synced else {
+                return Ok(());
+            };
+            let cap = if is_cancel {
+                (synced_cap + CANCEL_CAP_BONUS).min(synced_cap * 2)
+            } else {
+                synced_cap
+            };
+
+            let now = Instant::now();
+            let wait = if used + requests <= cap {
+                Duration::ZERO
+            } else if self.mode == RateLimitMode::Reject {
+                return Err(Error::RateLimited(format!(
+                    "{used} of {cap} requests used by the address"
+                )));
+            } else {
+                let next = address
+                    .last_over_cap
+                    .map_or(now, |last| (last + OVER_CAP_INTERVAL).max(now));
+                address.last_over_cap = Some(next);
+                next - now
+            };
+            address.synced = Some((used + requests, synced_cap));
+            wait
+        };
+        if !wait.is_zero() {
+            debug!("Address over its request cap, waiting {wait:?}");
+            tokio::time::sleep(wait).await;
+        }
+        Ok(())
+    }
+
+    fn refund_address(&self, requests: u64) -> Result<()> {
+        let mut address = lock(&self.address)?;
+        if let Some((used, cap)) = address.synced {
+            address.synced = Some((used.saturating_sub(requests), cap));
+        }
+        Ok(())
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> Result<std::sync::MutexGuard<'_, T>> {
+    mutex
+        .lock()
+        .map_err(|e| Error::GenericRequest(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CANCEL: &str = r#"{"action":{"type":"cancel","cancels":[{"a":0,"o":1},{"a":0,"o":2}]}}"#;
+
+    #[tokio::test]
+    async fn test_rejects_over_ip_budget() -> Result<()> {
+        let limiter = RateLimiter::new(RateLimitMode::Reject);
+        for _ in 0..IP_WEIGHT_PER_MINUTE / 20 {
+            limiter.acquire("/info", r#"{"type":"meta"}"#).await?;
+        }
+        assert!(limiter.available_weight()? < 2);
+        assert!(matches!(
+            limiter.acquire("/info", r#"{"type":"allMids"}"#).await,
+            Err(Error::RateLimited(_))
+        ));
+
+        // Other accounts behind the same IP share the budget
+        let other = limiter.for_account();
+        assert!(other.acquire("/info", r#"{"type":"meta"}"#).await.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_counts_batch_against_address_budget() -> Result<()> {
+        let limiter = RateLimiter::new(RateLimitMode::Reject);
+        // Not synced: only the IP budget applies
+        limiter.acquire("/exchange", CANCEL).await?;
+        assert_eq!(limiter.remaining_requests()?, None);
+
+        let rate_limit: UserRateLimitResponse = serde_json::from_str(
+            r#"{"cumVlm":"2854574.593578","nRequestsUsed":9999,"nRequestsCap":10000}"#,
+        )
+        .unwrap();
+        limiter.observe_user_rate_limit(&rate_limit)?;
+
+        let order = r#"{"action":{"type":"order","orders":[{"a":0},{"a":1}],"grouping":"na"}}"#;
+        assert!(matches!(
+            limiter.acquire("/exchange", order).await,
+            Err(Error::RateLimited(_))
+        ));
+        // Cancels have a higher cap
+        limiter.acquire("/exchange", CANCEL).await?;
+        assert_eq!(limiter.remaining_requests()?, Some(0));
+
+        assert_eq!(exchange_weight(1), 1);
+        assert_eq!(exchange_weight(80), 3);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_ip_rejection_keeps_address_budget() -> Result<()> {
+        let limiter = RateLimiter::new(RateLimitMode::Reject);
+        let rate_limit: UserRateLimitResponse =
+            serde_json::from_str(r#"{"cumVlm":"0","nRequestsUsed":0,"nRequestsCap":1000}"#)
+                .unwrap();
+        limiter.observe_user_rate_limit(&rate_limit)?;
+        limiter.exhaust()?;
+
+        // Weight 11, which the budget takes over half a second to refill
+        let cancels = vec![r#"{"a":0,"o":1}"#; 400].join(",");
+        let cancel = format!(r#"{{"action":{{"type":"cancel","cancels":[{cancels}]}}}}"#);
+        assert!(matches!(
+            limiter.acquire("/exchange", &cancel).await,
+            Err(Error::RateLimited(_))
+        ));
+        assert_eq!(limiter.remaining_requests()?, Some(1000));
+        Ok(())
+    }
+}
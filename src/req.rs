@@ -1,4 +1,4 @@
-use crate::{prelude::*, BaseUrl, Error};
+use crate::{prelude::*, BaseUrl, Error, RateLimiter};
 use reqwest::{Client, Response};
 use serde::Deserialize;
 
@@ -9,10 +9,12 @@ struct ErrorData {
     msg: String,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct HttpClient<'c> {
     pub client: &'c Client,
-    pub base_url: String,
+    pub base_url: BaseUrl,
+    /// Budget every request is checked against before it is sent, if any
+    pub rate_limiter: Option<RateLimiter>,
 }
 
 async fn parse_response(response: Response) -> Result<String> {
@@ -52,7 +54,10 @@ async fn parse_response(response: Response) -> Result<String> {
 
 impl HttpClient<'_> {
     pub async fn post(&self, url_path: &'static str, data: String) -> Result<String> {
-        let full_url = format!("{}{url_path}", self.base_url);
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.acquire(url_path, &data).await?;
+        }
+        let full_url = format!("{}{url_path}", self.base_url.http_url());
         let request = self
             .client
             .post(full_url)
@@ -65,17 +70,28 @@ impl HttpClient<'_> {
             .execute(request)
             .await
             .map_err(|e| Error::GenericRequest(e.to_string()))?;
-        parse_response(result).await
+        let response = parse_response(result).await;
+        if let (
+            Some(rate_limiter),
+            Err(Error::ClientRequest {
+                status_code: 429, ..
+            }),
+        ) = (&self.rate_limiter, &response)
+        {
+            rate_limiter.exhaust()?;
+        }
+        response
     }
 
     pub fn is_mainnet(&self) -> bool {
-        self.base_url == BaseUrl::Mainnet.get_url()
+        self.base_url.is_mainnet()
     }
 
-    pub fn new(base_url: String) -> Self {
+    pub fn new(base_url: BaseUrl) -> Self {
         Self {
             client: crate::net::get_client(),
             base_url,
+            rate_limiter: None,
         }
     }
 }
@@ -1,13 +1,9 @@
-use ethers::{
-    core::k256::{elliptic_curve::FieldBytes, Secp256k1},
-    signers::LocalWallet,
-    types::{transaction::eip712::Eip712, Signature, H256, U256},
-};
+use ethers::types::{transaction::eip712::Eip712, Signature, H256};
 
-use crate::{prelude::*, proxy_digest::Sha256Proxy, signature::agent::l1, Error};
+use crate::{prelude::*, signature::agent::l1, Error, HyperliquidSigner};
 
-pub(crate) fn sign_l1_action(
-    wallet: &LocalWallet,
+pub(crate) async fn sign_l1_action<S: HyperliquidSigner + ?Sized>(
+    signer: &S,
     connection_id: H256,
     is_mainnet: bool,
 ) -> Result<Signature> {
@@ -17,38 +13,27 @@ pub(crate) fn sign_l1_action(
             source,
             connection_id,
         },
-        wallet,
+        signer,
     )
+    .await
 }
 
-pub(crate) fn sign_typed_data<T: Eip712>(payload: &T, wallet: &LocalWallet) -> Result<Signature> {
+pub(crate) async fn sign_typed_data<T: Eip712, S: HyperliquidSigner + ?Sized>(
+    payload: &T,
+    signer: &S,
+) -> Result<Signature> {
     let encoded = payload
         .encode_eip712()
         .map_err(|e| Error::Eip712(e.to_string()))?;
 
-    sign_hash(H256::from(encoded), wallet)
-}
-
-fn sign_hash(hash: H256, wallet: &LocalWallet) -> Result<Signature> {
-    let (sig, rec_id) = wallet
-        .signer()
-        .sign_digest_recoverable(Sha256Proxy::from(hash))
-        .map_err(|e| Error::SignatureFailure(e.to_string()))?;
-
-    let v = u8::from(rec_id) as u64 + 27;
-
-    let r_bytes: FieldBytes<Secp256k1> = sig.r().into();
-    let s_bytes: FieldBytes<Secp256k1> = sig.s().into();
-    let r = U256::from_big_endian(r_bytes.as_slice());
-    let s = U256::from_big_endian(s_bytes.as_slice());
-
-    Ok(Signature { r, s, v })
+    signer.sign_hash(H256::from(encoded)).await
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{UsdSend, Withdraw3};
+    use crate::{CDeposit, CWithdraw, TokenDelegate, UsdSend, Withdraw3};
+    use ethers::signers::LocalWallet;
     use std::str::FromStr;
 
     fn get_wallet() -> Result<LocalWallet> {
@@ -58,8 +43,8 @@ mod tests {
             .map_err(|e| Error::Wallet(e.to_string()))
     }
 
-    #[test]
-    fn test_sign_l1_action() -> Result<()> {
+    #[tokio::test]
+    async fn test_sign_l1_action() -> Result<()> {
         let wallet = get_wallet()?;
         let connection_id =
             H256::from_str("0xde6c4037798a4434ca03cd05f00e3b803126221375cd1e7eaaaf041768be06eb")
@@ -67,19 +52,23 @@ mod tests {
 
         let expected_mainnet_sig = "fa8a41f6a3fa728206df80801a83bcbfbab08649cd34d9c0bfba7c7b2f99340f53a00226604567b98a1492803190d65a201d6805e5831b7044f17fd530aec7841c";
         assert_eq!(
-            sign_l1_action(&wallet, connection_id, true)?.to_string(),
+            sign_l1_action(&wallet, connection_id, true)
+                .await?
+                .to_string(),
             expected_mainnet_sig
         );
         let expected_testnet_sig = "1713c0fc661b792a50e8ffdd59b637b1ed172d9a3aa4d801d9d88646710fb74b33959f4d075a7ccbec9f2374a6da21ffa4448d58d0413a0d335775f680a881431c";
         assert_eq!(
-            sign_l1_action(&wallet, connection_id, false)?.to_string(),
+            sign_l1_action(&wallet, connection_id, false)
+                .await?
+                .to_string(),
             expected_testnet_sig
         );
         Ok(())
     }
 
-    #[test]
-    fn test_sign_usd_transfer_action() -> Result<()> {
+    #[tokio::test]
+    async fn test_sign_usd_transfer_action() -> Result<()> {
         let wallet = get_wallet()?;
 
         let usd_send = UsdSend {
@@ -92,14 +81,14 @@ mod tests {
 
         let expected_sig = "214d507bbdaebba52fa60928f904a8b2df73673e3baba6133d66fe846c7ef70451e82453a6d8db124e7ed6e60fa00d4b7c46e4d96cb2bd61fd81b6e8953cc9d21b";
         assert_eq!(
-            sign_typed_data(&usd_send, &wallet)?.to_string(),
+            sign_typed_data(&usd_send, &wallet).await?.to_string(),
             expected_sig
         );
         Ok(())
     }
 
-    #[test]
-    fn test_sign_withdraw_from_bridge_action() -> Result<()> {
+    #[tokio::test]
+    async fn test_sign_withdraw_from_bridge_action() -> Result<()> {
         let wallet = get_wallet()?;
 
         let usd_send = Withdraw3 {
@@ -112,7 +101,68 @@ mod tests {
 
         let expected_sig = "b3172e33d2262dac2b4cb135ce3c167fda55dafa6c62213564ab728b9f9ba76b769a938e9f6d603dae7154c83bf5a4c3ebab81779dc2db25463a3ed663c82ae41c";
         assert_eq!(
-            sign_typed_data(&usd_send, &wallet)?.to_string(),
+            sign_typed_data(&usd_send, &wallet).await?.to_string(),
+            expected_sig
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_sign_c_deposit_action() -> Result<()> {
+        let wallet = get_wallet()?;
+
+        let c_deposit = CDeposit {
+            signature_chain_id: 421614.into(),
+            hyperliquid_chain: "Testnet".to_string(),
+            wei: 100000000,
+            nonce: 1690393044548,
+        };
+
+        let expected_sig = "236016598893e096f334bc60e1e70ad73558b073f439852a703617725fecf42535d704febfb519dd41f47d889899504fb10dee1dc1183131c936b5546159994b1b";
+        assert_eq!(
+            sign_typed_data(&c_deposit, &wallet).await?.to_string(),
+            expected_sig
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_sign_c_withdraw_action() -> Result<()> {
+        let wallet = get_wallet()?;
+
+        let c_withdraw = CWithdraw {
+            signature_chain_id: 421614.into(),
+            hyperliquid_chain: "Testnet".to_string(),
+            wei: 100000000,
+            nonce: 1690393044548,
+        };
+
+        let expected_sig = "9cb3f555c1c4b9861fe4ee3a294add9cf9d9c362ef02e2e54c1735bf21d65574758a01f015666b7353d87f74e910a3080dabf31ee9b80dd4347b34f37f7c1c7f1b";
+        assert_eq!(
+            sign_typed_data(&c_withdraw, &wallet).await?.to_string(),
+            expected_sig
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_sign_token_delegate_action() -> Result<()> {
+        let wallet = get_wallet()?;
+
+        let token_delegate = TokenDelegate {
+            signature_chain_id: 421614.into(),
+            hyperliquid_chain: "Testnet".to_string(),
+            validator: "0x5ac99df645f3414876c816caa18b2d234024b487"
+                .parse()
+                .map_err(|_| Error::GenericParse("bad address".to_string()))?,
+            wei: 100000000,
+            is_undelegate: false,
+            nonce: 1690393044548,
+        };
+
+        let expected_sig = "4ac2988821f24c19ab789fbe572d11f4b9b98fd98eef6f04f9ba858abb3494dc35458ac91367d27aca4a5819efcd1a20eb304c31ec9bf3f757270a84199dab101c";
+        assert_eq!(
+            sign_typed_data(&token_delegate, &wallet).await?.to_string(),
             expected_sig
         );
         Ok(())
@@ -1,4 +1,10 @@
 pub(crate) mod agent;
 mod create_signature;
+mod remote_signer;
+mod signer;
+mod verify;
 
 pub(crate) use create_signature::{sign_l1_action, sign_typed_data};
+pub use remote_signer::{RemoteSigner, RemoteSignerTransport};
+pub use signer::HyperliquidSigner;
+pub use verify::{recover_l1_signer, recover_typed_data_signer};
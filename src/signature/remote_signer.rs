@@ -0,0 +1,252 @@
+use async_trait::async_trait;
+use ethers::types::{Signature, H160, H256};
+use serde::{Deserialize, Serialize};
+#[cfg(unix)]
+use std::path::PathBuf;
+use std::{str::FromStr, time::Duration};
+#[cfg(unix)]
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::UnixStream,
+};
+
+use crate::{prelude::*, Error, HyperliquidSigner};
+
+/// How long an HTTP signing service gets to answer a request
+const HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Where a [`RemoteSigner`] reaches its signing service.
+#[derive(Debug, Clone)]
+pub enum RemoteSignerTransport {
+    /// Requests are POSTed as JSON to the URL
+    Http { url: String },
+    /// Requests are written as a single line of JSON and answered with a single line
+    #[cfg(unix)]
+    UnixSocket { path: PathBuf },
+}
+
+#[derive(Serialize, Debug)]
+struct SignRequest {
+    address: H160,
+    hash: H256,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+enum SignResponse {
+    Signature { signature: String },
+    Error { error: String },
+}
+
+/// Signer that keeps the key in a separate signing service.
+///
+/// For every signature the service receives `{"address": "0x…", "hash": "0x…"}` and
+/// answers with `{"signature": "0x…"}`, the 65 byte `r || s || v` signature of the hash as
+/// hex, or `{"error": "…"}`. Signatures that don't recover to `address` are rejected, as are
+/// HTTP answers with a non-2xx status or slower than ten seconds.
+#[derive(Debug, Clone)]
+pub struct RemoteSigner {
+    address: H160,
+    transport: RemoteSignerTransport,
+}
+
+impl RemoteSigner {
+    pub fn new(address: H160, transport: RemoteSignerTransport) -> RemoteSigner {
+        RemoteSigner { address, transport }
+    }
+
+    pub fn http(address: H160, url: &str) -> RemoteSigner {
+        RemoteSigner::new(
+            address,
+            RemoteSignerTransport::Http {
+                url: url.to_string(),
+            },
+        )
+    }
+
+    #[cfg(unix)]
+    pub fn unix_socket(address: H160, path: impl Into<PathBuf>) -> RemoteSigner {
+        RemoteSigner::new(
+            address,
+            RemoteSignerTransport::UnixSocket { path: path.into() },
+        )
+    }
+
+    async fn request(&self, request: &SignRequest) -> Result<String> {
+        let body = serde_json::to_string(request).map_err(|e| Error::JsonParse(e.to_string()))?;
+        match &self.transport {
+            RemoteSignerTransport::Http { url } => {
+                let response = crate::net::get_client()
+                    .post(url)
+                    .header("Content-Type", "application/json")
+                    .timeout(HTTP_TIMEOUT)
+                    .body(body)
+                    .send()
+                    .await
+                    .map_err(|e| Error::SignatureFailure(e.to_string()))?;
+                let status = response.status();
+                let text = response
+                    .text()
+                    .await
+                    .map_err(|e| Error::SignatureFailure(e.to_string()))?;
+                if !status.is_success() {
+                    return Err(Error::SignatureFailure(format!(
+                        "Remote signer answered {status}: {text}"
+                    )));
+                }
+                Ok(text)
+            }
+            #[cfg(unix)]
+            RemoteSignerTransport::UnixSocket { path } => {
+                let mut stream = UnixStream::connect(path)
+                    .await
+                    .map_err(|e| Error::SignatureFailure(e.to_string()))?;
+                stream
+                    .write_all(format!("{body}\n").as_bytes())
+                    .await
+                    .map_err(|e| Error::SignatureFailure(e.to_string()))?;
+
+                let mut line = String::new();
+                BufReader::new(stream)
+                    .read_line(&mut line)
+                    .await
+                    .map_err(|e| Error::SignatureFailure(e.to_string()))?;
+                Ok(line)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl HyperliquidSigner for RemoteSigner {
+    fn address(&self) -> H160 {
+        self.address
+    }
+
+    async fn sign_hash(&self, hash: H256) -> Result<Signature> {
+        let response = self
+            .request(&SignRequest {
+                address: self.address,
+                hash,
+            })
+            .await?;
+
+        let signature =
+            match serde_json::from_str(&response).map_err(|e| Error::JsonParse(e.to_string()))? {
+                SignResponse::Signature { signature } => Signature::from_str(&signature)
+                    .map_err(|e| Error::SignatureFailure(e.to_string()))?,
+                SignResponse::Error { error } => return Err(Error::SignatureFailure(error)),
+            };
+
+        let recovered = signature
+            .recover(hash)
+            .map_err(|e| Error::SignatureFailure(e.to_string()))?;
+        if recovered != self.address {
+            return Err(Error::SignatureFailure(format!(
+                "Remote signer returned a signature by {recovered:?}, expected {:?}",
+                self.address
+            )));
+        }
+        Ok(signature)
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::test_server;
+    use ethers::signers::LocalWallet;
+    use tokio::net::UnixListener;
+
+    fn get_wallet() -> Result<LocalWallet> {
+        let priv_key = "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e";
+        priv_key
+            .parse::<LocalWallet>()
+            .map_err(|e| Error::Wallet(e.to_string()))
+    }
+
+    /// Signing daemon answering a single request with `wallet`
+    async fn serve_once(listener: UnixListener, wallet: LocalWallet) {
+        let (stream, _) = listener.accept().await.unwrap();
+        let (reader, mut writer) = stream.into_split();
+        let mut line = String::new();
+        BufReader::new(reader).read_line(&mut line).await.unwrap();
+
+        let request: serde_json::Value = serde_json::from_str(&line).unwrap();
+        let hash: H256 = serde_json::from_value(request["hash"].clone()).unwrap();
+        let signature = HyperliquidSigner::sign_hash(&wallet, hash).await.unwrap();
+        let response = serde_json::json!({ "signature": format!("0x{signature}") });
+        writer
+            .write_all(format!("{response}\n").as_bytes())
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_unix_socket_signer_matches_local_wallet() -> Result<()> {
+        let wallet = get_wallet()?;
+        let path = std::env::temp_dir().join(format!("hl-signer-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixListener::bind(&path).unwrap();
+        tokio::spawn(serve_once(listener, wallet.clone()));
+
+        let hash = H256::from_low_u64_be(1583838);
+        let remote = RemoteSigner::unix_socket(HyperliquidSigner::address(&wallet), &path);
+        assert_eq!(
+            remote.sign_hash(hash).await?,
+            HyperliquidSigner::sign_hash(&wallet, hash).await?
+        );
+
+        std::fs::remove_file(&path).unwrap();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_remote_signer_rejects_signature_by_other_key() -> Result<()> {
+        let wallet = get_wallet()?;
+        let path =
+            std::env::temp_dir().join(format!("hl-signer-other-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = UnixListener::bind(&path).unwrap();
+        tokio::spawn(serve_once(listener, wallet));
+
+        let remote = RemoteSigner::unix_socket(H160::zero(), &path);
+        assert!(matches!(
+            remote.sign_hash(H256::zero()).await,
+            Err(Error::SignatureFailure(_))
+        ));
+
+        std::fs::remove_file(&path).unwrap();
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_http_signer_matches_local_wallet() -> Result<()> {
+        let wallet = get_wallet()?;
+        let hash = H256::from_low_u64_be(1583838);
+        let signature = HyperliquidSigner::sign_hash(&wallet, hash).await?;
+        let base_url =
+            test_server::serve(move |_, _| format!(r#"{{"signature":"0x{signature}"}}"#)).await;
+
+        let remote = RemoteSigner::http(HyperliquidSigner::address(&wallet), base_url.http_url());
+        assert_eq!(remote.sign_hash(hash).await?, signature);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_http_signer_rejects_error_status() -> Result<()> {
+        let base_url =
+            test_server::serve_with_status(|_, _| (500, "signing service unavailable".to_string()))
+                .await;
+
+        let remote = RemoteSigner::http(H160::zero(), base_url.http_url());
+        match remote.sign_hash(H256::zero()).await {
+            Err(Error::SignatureFailure(message)) => {
+                assert!(message.contains("500"), "{message}");
+                assert!(message.contains("signing service unavailable"), "{message}");
+            }
+            other => panic!("expected a signature failure, got {other:?}"),
+        }
+        Ok(())
+    }
+}
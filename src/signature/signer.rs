@@ -0,0 +1,54 @@
+use async_trait::async_trait;
+use ethers::{
+    core::k256::{elliptic_curve::FieldBytes, Secp256k1},
+    signers::{LocalWallet, Signer},
+    types::{Signature, H160, H256, U256},
+};
+use std::{fmt::Debug, sync::Arc};
+
+use crate::{prelude::*, proxy_digest::Sha256Proxy, Error};
+
+/// Produces the signatures the exchange expects for actions.
+///
+/// Implementations sign the 32 byte EIP-712 digest as is, without hashing or prefixing it
+/// further, and return a recoverable signature with `v` of 27 or 28.
+#[async_trait]
+pub trait HyperliquidSigner: Debug + Send + Sync {
+    fn address(&self) -> H160;
+
+    async fn sign_hash(&self, hash: H256) -> Result<Signature>;
+}
+
+#[async_trait]
+impl HyperliquidSigner for LocalWallet {
+    fn address(&self) -> H160 {
+        Signer::address(self)
+    }
+
+    async fn sign_hash(&self, hash: H256) -> Result<Signature> {
+        let (sig, rec_id) = self
+            .signer()
+            .sign_digest_recoverable(Sha256Proxy::from(hash))
+            .map_err(|e| Error::SignatureFailure(e.to_string()))?;
+
+        let v = u8::from(rec_id) as u64 + 27;
+
+        let r_bytes: FieldBytes<Secp256k1> = sig.r().into();
+        let s_bytes: FieldBytes<Secp256k1> = sig.s().into();
+        let r = U256::from_big_endian(r_bytes.as_slice());
+        let s = U256::from_big_endian(s_bytes.as_slice());
+
+        Ok(Signature { r, s, v })
+    }
+}
+
+#[async_trait]
+impl<S: HyperliquidSigner + ?Sized> HyperliquidSigner for Arc<S> {
+    fn address(&self) -> H160 {
+        self.as_ref().address()
+    }
+
+    async fn sign_hash(&self, hash: H256) -> Result<Signature> {
+        self.as_ref().sign_hash(hash).await
+    }
+}
@@ -0,0 +1,64 @@
+use ethers::types::{transaction::eip712::Eip712, Signature, H160, H256};
+
+use crate::{prelude::*, signature::agent::l1, Error};
+
+/// Recovers the address that signed an L1 action with the given connection id, see
+/// [`Actions::hash`](crate::Actions::hash).
+///
+/// A signature made for the other network recovers to an unrelated address rather than
+/// failing, so compare the result with the expected signer.
+pub fn recover_l1_signer(
+    connection_id: H256,
+    is_mainnet: bool,
+    signature: &Signature,
+) -> Result<H160> {
+    let source = if is_mainnet { "a" } else { "b" }.to_string();
+    recover_typed_data_signer(
+        &l1::Agent {
+            source,
+            connection_id,
+        },
+        signature,
+    )
+}
+
+/// Recovers the address that signed EIP-712 typed data, such as a `UsdSend` or `Withdraw3`.
+pub fn recover_typed_data_signer<T: Eip712>(payload: &T, signature: &Signature) -> Result<H160> {
+    let encoded = payload
+        .encode_eip712()
+        .map_err(|e| Error::Eip712(e.to_string()))?;
+
+    signature
+        .recover(H256::from(encoded))
+        .map_err(|e| Error::SignatureFailure(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::signers::{LocalWallet, Signer};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_recover_l1_signer() -> Result<()> {
+        let connection_id =
+            H256::from_str("0xde6c4037798a4434ca03cd05f00e3b803126221375cd1e7eaaaf041768be06eb")
+                .map_err(|e| Error::GenericParse(e.to_string()))?;
+        let signature = Signature::from_str("fa8a41f6a3fa728206df80801a83bcbfbab08649cd34d9c0bfba7c7b2f99340f53a00226604567b98a1492803190d65a201d6805e5831b7044f17fd530aec7841c")
+            .map_err(|e| Error::GenericParse(e.to_string()))?;
+        let expected = "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e"
+            .parse::<LocalWallet>()
+            .map_err(|e| Error::Wallet(e.to_string()))?
+            .address();
+
+        assert_eq!(
+            recover_l1_signer(connection_id, true, &signature)?,
+            expected
+        );
+        assert_ne!(
+            recover_l1_signer(connection_id, false, &signature)?,
+            expected
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,133 @@
+use crate::{prelude::*, BaseUrl, ExchangeClient, MetaCache};
+use ethers::signers::LocalWallet;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+
+/// Private key of the wallet signing test requests
+pub(crate) const TEST_KEY: &str =
+    "e908f86dbb4d55ac876378565aafeabc187f6690f046459397b17d9b9a19688e";
+
+/// Spot metadata listing no tokens
+pub(crate) const EMPTY_SPOT_META: &str = r#"{"universe":[],"tokens":[]}"#;
+
+async fn read_request(stream: &mut TcpStream) -> String {
+    let mut request = Vec::new();
+    let mut buf = [0; 1024];
+    loop {
+        let len = stream.read(&mut buf).await.unwrap();
+        request.extend_from_slice(&buf[..len]);
+        let text = String::from_utf8_lossy(&request).to_string();
+        let Some(header_end) = text.find("\r\n\r\n") else {
+            if len == 0 {
+                return text;
+            }
+            continue;
+        };
+        let content_length = text[..header_end]
+            .lines()
+            .find_map(|line| {
+                let line = line.to_lowercase();
+                line.strip_prefix("content-length:")
+                    .and_then(|len| len.trim().parse::<usize>().ok())
+            })
+            .unwrap_or(0);
+        if len == 0 || request.len() >= header_end + 4 + content_length {
+            return text;
+        }
+    }
+}
+
+/// Local API answering every request with the JSON `handler` returns for its path and body.
+pub(crate) async fn serve<F>(handler: F) -> BaseUrl
+where
+    F: Fn(&str, &str) -> String + Send + Sync + 'static,
+{
+    serve_with_status(move |path, body| (200, handler(path, body))).await
+}
+
+/// Like [`serve`], with `handler` also choosing the status code.
+pub(crate) async fn serve_with_status<F>(handler: F) -> BaseUrl
+where
+    F: Fn(&str, &str) -> (u16, String) + Send + Sync + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let http = format!("http://{}", listener.local_addr().unwrap());
+
+    tokio::spawn(async move {
+        loop {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let request = read_request(&mut stream).await;
+
+            let path = request.split_whitespace().nth(1).unwrap_or_default();
+            let body = request
+                .split_once("\r\n\r\n")
+                .map(|(_, body)| body)
+                .unwrap_or_default();
+            let (status, body) = handler(path, body);
+            let response = format!(
+                "HTTP/1.1 {status} OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+        }
+    });
+
+    BaseUrl::Custom {
+        ws: format!("{}/ws", http.replace("http", "ws")),
+        http,
+        is_mainnet: false,
+    }
+}
+
+/// Local exchange serving `meta` and `spot_meta` as metadata and answering every other
+/// request with the JSON `handler` returns for its path and body.
+pub(crate) async fn serve_exchange<F>(
+    meta: &'static str,
+    spot_meta: &'static str,
+    handler: F,
+) -> BaseUrl
+where
+    F: Fn(&str, &str) -> String + Send + Sync + 'static,
+{
+    serve_exchange_with_status(meta, spot_meta, move |path, body| {
+        (200, handler(path, body))
+    })
+    .await
+}
+
+/// Like [`serve_exchange`], with `handler` also choosing the status code.
+pub(crate) async fn serve_exchange_with_status<F>(
+    meta: &'static str,
+    spot_meta: &'static str,
+    handler: F,
+) -> BaseUrl
+where
+    F: Fn(&str, &str) -> (u16, String) + Send + Sync + 'static,
+{
+    serve_with_status(move |path, body| {
+        if path == "/info" && body.contains(r#""type":"spotMeta""#) {
+            (200, spot_meta.to_string())
+        } else if path == "/info" && body.contains(r#""type":"meta""#) {
+            (200, meta.to_string())
+        } else {
+            handler(path, body)
+        }
+    })
+    .await
+}
+
+pub(crate) fn test_wallet() -> LocalWallet {
+    TEST_KEY.parse().unwrap()
+}
+
+/// Client signing with [`test_wallet`] against the exchange at `base_url`.
+pub(crate) async fn test_client(base_url: BaseUrl) -> Result<ExchangeClient> {
+    let meta_cache = MetaCache::new(base_url).await?;
+    Ok(ExchangeClient::from_meta_cache(
+        test_wallet(),
+        meta_cache,
+        None,
+    ))
+}